@@ -1,28 +1,99 @@
-use std::{fs::OpenOptions, str::FromStr};
+use std::{fs::OpenOptions, process::ExitCode, str::FromStr, sync::{atomic::AtomicBool, Arc}};
 
 use frdm_tools::Image;
 use sal_core::{dbg::Dbg, error::Error};
-use sal_sync::{services::{conf::{ConfKeywd, ConfKind, ConfTree}, Service}, thread_pool::ThreadPool};
+use sal_sync::{services::{conf::{ConfKeywd, ConfKind, ConfTree, ConfTreeGet}, Service}, thread_pool::ThreadPool};
 
-use crate::modules::{CameraService, CameraServiceConf, ModbusService, ModbusServiceConf};
+use crate::modules::{CalibrationArgs, CameraService, Chessboard, CameraServiceConf, HoistService, HoistServiceConf, HttpService, HttpServiceConf, Metrics, ModbusService, ModbusServiceConf, RopeDefectService, RopeDefectServiceConf, RopeLength, ServiceRegistry, Shutdown, Supervisor, SupervisorConf};
 
 
 mod modules;
 mod tests;
+/// Exit code if some services are not finished in the `shutdown-timeout`
+const EXIT_SHUTDOWN_TIMEOUT: u8 = 2;
+/// Exit code if SIGINT / SIGTERM is received again while the services are stopping
+const EXIT_FORCED: u8 = 3;
 ///
 /// Application entry point
-fn main() -> Result<(), Error>{
+/// 
+/// Commands:
+/// - without arguments - starts the services of the `config.yaml`
+/// - `calibrate <images-dir> [--board 9x6] [--output calibration.yaml]` - intrinsic calibration of the camera by the chessboard images
+/// 
+/// Exit code:
+/// - 0 - all services finished or stopped by SIGINT / SIGTERM, calibration saved
+/// - 1 - configuration or start error, some service failed `max-failures` times in a row, calibration failed
+/// - 2 - some services are not finished in the `shutdown-timeout` after SIGINT / SIGTERM
+/// - 3 - SIGINT / SIGTERM received again while the services are stopping, the process is terminated immediately
+fn main() -> ExitCode {
     env_logger::Builder::new().filter_level(log::LevelFilter::Debug).init();
     let dbg = Dbg::own("position-tracking");
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some("calibrate") = args.first().map(String::as_str) {
+        let code = match calibrate(&dbg, &args[1..]) {
+            Ok(_) => ExitCode::SUCCESS,
+            Err(err) => {
+                log::error!("{dbg}.main | Calibration error: {}", err);
+                ExitCode::FAILURE
+            }
+        };
+        log::logger().flush();
+        return code;
+    }
+    let code = match run(&dbg) {
+        Ok(Shutdown::Finished) | Ok(Shutdown::Stopped) => {
+            log::info!("{dbg}.main | Exit");
+            ExitCode::SUCCESS
+        }
+        Ok(Shutdown::Timeout) => {
+            log::error!("{dbg}.main | Exit, some services are not finished in time");
+            ExitCode::from(EXIT_SHUTDOWN_TIMEOUT)
+        }
+        Err(err) => {
+            log::error!("{dbg}.main | Exit with error: {}", err);
+            ExitCode::FAILURE
+        }
+    };
+    log::logger().flush();
+    code
+}
+///
+/// Computes the intrinsic calibration of the camera by the chessboard images & saves it into the YAML file
+fn calibrate(dbg: &Dbg, args: &[String]) -> Result<(), Error> {
+    let args = CalibrationArgs::parse(args)?;
+    log::info!("{dbg}.calibrate | Calibrating by the images of {:?}, board {}x{}...", args.images, args.board.0, args.board.1);
+    let calibration = Chessboard::new(dbg, args.board).calibrate(&args.images)?;
+    calibration.save(&args.output)?;
+    log::info!("{dbg}.calibrate | Calibration saved into {:?}, RMS reprojection error: {:.4} px, images: {}", args.output, calibration.rms, calibration.images);
+    Ok(())
+}
+///
+/// Starts the services configured and supervises them until shutdown
+fn run(dbg: &Dbg) -> Result<Shutdown, Error> {
+    let dbg = dbg.clone();
+    let shutdown = Arc::new(AtomicBool::new(false));
+    for signal in [signal_hook::consts::SIGINT, signal_hook::consts::SIGTERM] {
+        // The second signal terminates the process if the shutdown hung, the flag is set by the first one
+        signal_hook::flag::register_conditional_shutdown(signal, EXIT_FORCED as i32, shutdown.clone()).map_err(|err| Error::new(&dbg, "run").pass(err.to_string()))?;
+        signal_hook::flag::register(signal, shutdown.clone()).map_err(|err| Error::new(&dbg, "run").pass(err.to_string()))?;
+    }
     let thread_pool = ThreadPool::new(&dbg, Some(8));
     let (position_send, position_recv) = kanal::unbounded();
     let mut position_recv = vec![position_recv];
+    let metrics = Metrics::new();
+    let rope = RopeLength::new();
     let conf = "config.yaml";
-    let file = OpenOptions::new().read(true).open(conf).map_err(|err| Error::new(&dbg, "main").pass(err.to_string()))?;
-    let conf = serde_yaml::from_reader(file).map_err(|err| Error::new(&dbg, "main").pass(err.to_string()))?;
-    // log::debug!("{dbg}.main | conf: {:#?}", conf);
+    let file = OpenOptions::new().read(true).open(conf).map_err(|err| Error::new(&dbg, "run").pass(err.to_string()))?;
+    let conf = serde_yaml::from_reader(file).map_err(|err| Error::new(&dbg, "run").pass(err.to_string()))?;
+    // log::debug!("{dbg}.run | conf: {:#?}", conf);
     let conf = ConfTree::new_root(conf);
-    let services: Vec<Box<dyn Service>> = conf.nodes()
+    let supervisor: Option<ConfTree> = conf.get("supervisor");
+    let supervisor = match supervisor {
+        Some(supervisor) => SupervisorConf::new(&dbg, supervisor),
+        None => SupervisorConf::default(),
+    };
+    let registry = ServiceRegistry::new(&dbg, metrics.clone()).with_stop_timeout(supervisor.shutdown_timeout);
+    let services: Vec<Box<dyn Service + Send>> = conf.nodes()
         .filter_map(|node| {
             match ConfKeywd::from_str(&node.key) {
                 Ok(keywd) => match keywd.kind() == ConfKind::Service.to_string() {
@@ -32,39 +103,55 @@ fn main() -> Result<(), Error>{
                 Err(_) => None,
             }
         })
-        .filter_map::<Box<dyn Service>, _>(|(keywd, node)| {
+        .filter_map::<Box<dyn Service + Send>, _>(|(keywd, node)| {
             match keywd.name().as_str() {
                 "CameraService" => {
                     let conf = CameraServiceConf::new(&dbg, node);
-                    match Image::load(&conf.template_match.template) {
-                        Ok(template) => {
-                            let service = CameraService::new(&dbg, conf, template, position_send.clone(), thread_pool.scheduler());
+                    let templates = conf.cameras.iter()
+                        .map(|camera| Image::load(&camera.template_match.template))
+                        .collect::<Result<Vec<_>, _>>();
+                    match templates {
+                        Ok(templates) => {
+                            let service = CameraService::new(&dbg, conf, templates, position_send.clone(), metrics.clone(), rope.clone(), thread_pool.scheduler());
                             Some(Box::new(service))
                         }
                         Err(err) => {
-                            log::debug!("{dbg}.main | Can't read template: {:?}", err);
+                            log::debug!("{dbg}.run | Can't read template: {:?}", err);
                             None
                         }
                     }
                 }
                 "ModbusService" => {
-                    log::debug!("{dbg}.main | ModbusService conf: {:#?}", node);
+                    log::debug!("{dbg}.run | ModbusService conf: {:#?}", node);
                     let conf = ModbusServiceConf::new(&dbg, node);
-                    let service = ModbusService::new(&dbg, conf, position_recv.pop().unwrap(), thread_pool.scheduler());
+                    let service = ModbusService::new(&dbg, conf, position_recv.pop().unwrap(), metrics.clone(), thread_pool.scheduler());
+                    Some(Box::new(service))
+                }
+                "HoistService" => {
+                    let conf = HoistServiceConf::new(&dbg, node);
+                    let service = HoistService::new(&dbg, conf, rope.clone(), metrics.clone(), thread_pool.scheduler());
+                    Some(Box::new(service))
+                }
+                "RopeDefectService" => {
+                    let conf = RopeDefectServiceConf::new(&dbg, node);
+                    let service = RopeDefectService::new(&dbg, conf, metrics.clone(), thread_pool.scheduler(), thread_pool.scheduler());
+                    Some(Box::new(service))
+                }
+                "HttpService" => {
+                    let conf = HttpServiceConf::new(&dbg, node);
+                    let service = HttpService::new(&dbg, conf, metrics.clone(), registry.clone(), thread_pool.scheduler());
                     Some(Box::new(service))
                 }
                 _ => {
-                    log::debug!("{dbg}.main | Unknown Service '{} {} {}' in the configuration", keywd.kind(), keywd.name(), keywd.title());
+                    log::debug!("{dbg}.run | Unknown Service '{} {} {}' in the configuration", keywd.kind(), keywd.name(), keywd.title());
                     None
                 }
             }
         })
         .collect();
-    for service in &services {
-        service.run()?;
+    for service in services {
+        registry.add(service);
     }
-    for service in &services {
-        service.wait()?;
-    }
-    Ok(())
+    registry.run_all()?;
+    Supervisor::new(&dbg, supervisor, registry, shutdown).run()
 }
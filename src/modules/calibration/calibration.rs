@@ -0,0 +1,122 @@
+use std::{fs::OpenOptions, path::Path};
+use sal_core::error::Error;
+use serde::{Deserialize, Serialize};
+
+use crate::modules::{Intrinsics, Position};
+
+/// Number of the iterations of the point undistortion
+const UNDISTORT_ITERATIONS: usize = 20;
+///
+/// Intrinsic calibration of the camera, the camera matrix & the lens distortion
+///
+/// Saved by the `calibrate` command, in the OpenCV distortion model
+///
+/// ### File example
+/// ```yaml
+/// image-size: [1200, 800]
+/// camera-matrix:
+///     - [1400.0, 0.0, 600.0]
+///     - [0.0, 1400.0, 400.0]
+///     - [0.0, 0.0, 1.0]
+/// distortion: [-0.12, 0.05, 0.0, 0.0, 0.0]   # k1, k2, p1, p2[, k3[, k4, k5, k6]]
+/// rms: 0.31
+/// images: 24
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Calibration {
+    /// Size of the calibration images, px
+    pub image_size: (i32, i32),
+    /// Camera matrix `K`, px
+    pub camera_matrix: [[f64; 3]; 3],
+    /// Distortion coefficients: k1, k2, p1, p2[, k3[, k4, k5, k6]]
+    pub distortion: Vec<f64>,
+    /// RMS reprojection error of the calibration, px
+    pub rms: f64,
+    /// Number of the images the pattern found on
+    pub images: usize,
+}
+//
+//
+impl Calibration {
+    ///
+    /// Returns [Calibration] loaded from the YAML file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let error = Error::new("Calibration", "load");
+        let file = OpenOptions::new().read(true).open(path).map_err(|err| error.pass_with(format!("Can't open {:?}", path), err.to_string()))?;
+        let calibration: Self = serde_yaml::from_reader(file).map_err(|err| error.pass_with(format!("Can't parse {:?}", path), err.to_string()))?;
+        if !matches!(calibration.distortion.len(), 4 | 5 | 8) {
+            return Err(error.err(format!("'distortion' must have 4, 5 or 8 coefficients, found {} in {:?}", calibration.distortion.len(), path)));
+        }
+        Ok(calibration)
+    }
+    ///
+    /// Returns an error if the calibration images aren't of the frame `width` x `height`, px,
+    /// the intrinsics are valid only for the frames of the calibrated size
+    pub fn check_size(&self, width: i32, height: i32) -> Result<(), Error> {
+        match self.image_size == (width, height) {
+            true => Ok(()),
+            false => Err(Error::new("Calibration", "check_size").err(format!(
+                "'image-size' {:?} doesn't match the frame size {} x {}", self.image_size, width, height,
+            ))),
+        }
+    }
+    ///
+    /// Saves the calibration into the YAML file
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let path = path.as_ref();
+        let error = Error::new("Calibration", "save");
+        let file = OpenOptions::new().write(true).create(true).truncate(true).open(path).map_err(|err| error.pass_with(format!("Can't create {:?}", path), err.to_string()))?;
+        serde_yaml::to_writer(file, self).map_err(|err| error.pass_with(format!("Can't write {:?}", path), err.to_string()))
+    }
+    ///
+    /// Returns the intrinsic parameters of the camera matrix
+    pub fn intrinsics(&self) -> Intrinsics {
+        let k = &self.camera_matrix;
+        Intrinsics { fx: k[0][0], fy: k[1][1], cx: k[0][2], cy: k[1][2] }
+    }
+    ///
+    /// Returns the coefficient `i`, missing ones are zero
+    fn coefficient(&self, i: usize) -> f64 {
+        self.distortion.get(i).copied().unwrap_or(0.0)
+    }
+    ///
+    /// Returns the radial factor & tangential shift of the normalized point `(x, y)`
+    fn distortion(&self, x: f64, y: f64) -> (f64, f64, f64) {
+        let [k1, k2, p1, p2, k3, k4, k5, k6] = std::array::from_fn(|i| self.coefficient(i));
+        let r2 = x * x + y * y;
+        let r4 = r2 * r2;
+        let r6 = r4 * r2;
+        let radial = (1.0 + k1 * r2 + k2 * r4 + k3 * r6) / (1.0 + k4 * r2 + k5 * r4 + k6 * r6);
+        let dx = 2.0 * p1 * x * y + p2 * (r2 + 2.0 * x * x);
+        let dy = p1 * (r2 + 2.0 * y * y) + 2.0 * p2 * x * y;
+        (radial, dx, dy)
+    }
+    ///
+    /// Returns the distorted position of the ideal `point`, px, as seen through the lens
+    pub fn distort(&self, point: Position<f64>) -> Position<f64> {
+        let Intrinsics { fx, fy, cx, cy } = self.intrinsics();
+        let (x, y) = ((point.x - cx) / fx, (point.y - cy) / fy);
+        let (radial, dx, dy) = self.distortion(x, y);
+        Position::new((x * radial + dx) * fx + cx, (y * radial + dy) * fy + cy)
+    }
+    ///
+    /// Returns the ideal position of the distorted `point` seen on the frame, px
+    ///
+    /// The distortion is inverted iteratively, the same way as OpenCV `undistortPoints` does
+    pub fn undistort(&self, point: Position<f64>) -> Position<f64> {
+        let Intrinsics { fx, fy, cx, cy } = self.intrinsics();
+        let (x0, y0) = ((point.x - cx) / fx, (point.y - cy) / fy);
+        let (mut x, mut y) = (x0, y0);
+        for _ in 0..UNDISTORT_ITERATIONS {
+            let (radial, dx, dy) = self.distortion(x, y);
+            if radial.abs() < f64::EPSILON {
+                break;
+            }
+            x = (x0 - dx) / radial;
+            y = (y0 - dy) / radial;
+        }
+        Position::new(x * fx + cx, y * fy + cy)
+    }
+}
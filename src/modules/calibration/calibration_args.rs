@@ -0,0 +1,60 @@
+use std::path::PathBuf;
+use sal_core::error::Error;
+
+/// Usage of the `calibrate` command
+pub const CALIBRATE_USAGE: &str = "calibrate <images-dir> [--board 9x6] [--output calibration.yaml]";
+///
+/// Arguments of the `calibrate` command
+///
+/// - `images-dir` - directory of the chessboard images taken by the camera
+/// - `--board` - number of the inner corners of the chessboard, columns x rows, default 9x6
+/// - `--output` - calibration file to be saved, default `calibration.yaml`, next to the `config.yaml`
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalibrationArgs {
+    /// Directory of the chessboard images
+    pub images: PathBuf,
+    /// Inner corners of the chessboard, columns & rows
+    pub board: (i32, i32),
+    /// Calibration file to be saved
+    pub output: PathBuf,
+}
+//
+//
+impl CalibrationArgs {
+    ///
+    /// Returns [CalibrationArgs] parsed from the command line arguments following `calibrate`
+    pub fn parse(args: &[String]) -> Result<Self, Error> {
+        let error = Error::new("CalibrationArgs", "parse");
+        let mut images = None;
+        let mut board = (9, 6);
+        let mut output = PathBuf::from("calibration.yaml");
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--board" => {
+                    let value = args.next().ok_or_else(|| error.err(format!("'--board' value expected, usage: {CALIBRATE_USAGE}")))?;
+                    board = Self::board(value).ok_or_else(|| error.err(format!("Wrong '--board' {value}, columns x rows of the inner corners expected, like 9x6")))?;
+                }
+                "--output" => {
+                    let value = args.next().ok_or_else(|| error.err(format!("'--output' value expected, usage: {CALIBRATE_USAGE}")))?;
+                    output = PathBuf::from(value);
+                }
+                _ if arg.starts_with("--") => return Err(error.err(format!("Unknown option '{arg}', usage: {CALIBRATE_USAGE}"))),
+                _ if images.is_none() => images = Some(PathBuf::from(arg)),
+                _ => return Err(error.err(format!("Unexpected argument '{arg}', usage: {CALIBRATE_USAGE}"))),
+            }
+        }
+        let images = images.ok_or_else(|| error.err(format!("Images directory expected, usage: {CALIBRATE_USAGE}")))?;
+        Ok(Self { images, board, output })
+    }
+    ///
+    /// Returns the columns & rows of the `value` like `9x6`
+    fn board(value: &str) -> Option<(i32, i32)> {
+        let (columns, rows) = value.split_once(['x', 'X'])?;
+        let (columns, rows) = (columns.trim().parse().ok()?, rows.trim().parse().ok()?);
+        match columns > 1 && rows > 1 {
+            true => Some((columns, rows)),
+            false => None,
+        }
+    }
+}
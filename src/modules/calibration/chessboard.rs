@@ -0,0 +1,125 @@
+use std::path::{Path, PathBuf};
+use opencv::{calib3d, core::{Mat, Point2f, Point3f, Size, TermCriteria, TermCriteria_Type, Vector}, imgcodecs, imgproc, prelude::*};
+use sal_core::{dbg::Dbg, error::Error};
+
+use crate::modules::Calibration;
+
+/// Minimum number of the images the chessboard found on
+const MIN_IMAGES: usize = 5;
+/// Extensions of the images to be read
+const EXTENSIONS: [&str; 5] = ["png", "jpg", "jpeg", "bmp", "tif"];
+///
+/// Intrinsic calibration of the camera by the chessboard images
+///
+/// - The inner corners of the chessboard are found on each image & refined to subpixel
+/// - The images the chessboard isn't found on are skipped
+/// - Camera matrix & distortion coefficients are computed by the OpenCV `calibrateCamera`
+pub struct Chessboard {
+    /// Inner corners of the chessboard, columns & rows
+    board: (i32, i32),
+    dbg: Dbg,
+}
+//
+//
+impl Chessboard {
+    ///
+    /// Returns [Chessboard] new instance
+    /// - `board` - inner corners of the chessboard, columns & rows
+    pub fn new(parent: impl Into<String>, board: (i32, i32)) -> Self {
+        Self { board, dbg: Dbg::new(parent, "Chessboard") }
+    }
+    ///
+    /// Returns the calibration computed by the images of the `dir`
+    pub fn calibrate(&self, dir: impl AsRef<Path>) -> Result<Calibration, Error> {
+        let dbg = &self.dbg;
+        let error = Error::new(dbg, "calibrate");
+        let (columns, rows) = self.board;
+        let board = Size::new(columns, rows);
+        // Corners of the board in its own plane, the square size is unit, it doesn't affect the intrinsics
+        let pattern: Vector<Point3f> = (0..rows)
+            .flat_map(|row| (0..columns).map(move |column| Point3f::new(column as f32, row as f32, 0.0)))
+            .collect();
+        let criteria = TermCriteria::new(TermCriteria_Type::COUNT as i32 + TermCriteria_Type::EPS as i32, 30, 0.001)
+            .map_err(|err| error.pass(err.to_string()))?;
+        let mut object_points: Vector<Vector<Point3f>> = Vector::new();
+        let mut image_points: Vector<Vector<Point2f>> = Vector::new();
+        let mut image_size: Option<Size> = None;
+        for path in Self::images(dir.as_ref()).map_err(|err| error.pass(err))? {
+            let frame = imgcodecs::imread(&path.to_string_lossy(), imgcodecs::IMREAD_GRAYSCALE)
+                .map_err(|err| error.pass_with(format!("Can't read {:?}", path), err.to_string()))?;
+            if frame.empty() {
+                log::warn!("{dbg}.calibrate | Can't read {:?}, skipped", path);
+                continue;
+            }
+            let size = frame.size().map_err(|err| error.pass(err.to_string()))?;
+            match image_size {
+                Some(image_size) if image_size != size => {
+                    return Err(error.err(format!("Image {:?} size {:?} differs from the first one {:?}", path, size, image_size)));
+                }
+                _ => image_size = Some(size),
+            }
+            let mut corners: Vector<Point2f> = Vector::new();
+            let found = calib3d::find_chessboard_corners(&frame, board, &mut corners, calib3d::CALIB_CB_ADAPTIVE_THRESH + calib3d::CALIB_CB_NORMALIZE_IMAGE)
+                .map_err(|err| error.pass_with(format!("Can't find chessboard on {:?}", path), err.to_string()))?;
+            if !found {
+                log::warn!("{dbg}.calibrate | Chessboard {}x{} not found on {:?}, skipped", columns, rows, path);
+                continue;
+            }
+            imgproc::corner_sub_pix(&frame, &mut corners, Size::new(11, 11), Size::new(-1, -1), criteria)
+                .map_err(|err| error.pass_with(format!("Can't refine corners on {:?}", path), err.to_string()))?;
+            log::debug!("{dbg}.calibrate | Chessboard found on {:?}", path);
+            object_points.push(pattern.clone());
+            image_points.push(corners);
+        }
+        let image_size = image_size.ok_or_else(|| error.err(format!("No images in {:?}", dir.as_ref())))?;
+        if image_points.len() < MIN_IMAGES {
+            return Err(error.err(format!("Chessboard found on {} images only, {} or more required", image_points.len(), MIN_IMAGES)));
+        }
+        log::info!("{dbg}.calibrate | Calibrating by {} images...", image_points.len());
+        let mut camera_matrix = Mat::default();
+        let mut distortion = Mat::default();
+        let mut rvecs: Vector<Mat> = Vector::new();
+        let mut tvecs: Vector<Mat> = Vector::new();
+        let rms = calib3d::calibrate_camera(
+            &object_points, &image_points, image_size,
+            &mut camera_matrix, &mut distortion, &mut rvecs, &mut tvecs,
+            0, criteria,
+        ).map_err(|err| error.pass_with("Calibration failed", err.to_string()))?;
+        let camera_matrix: [[f64; 3]; 3] = {
+            let mut k = [[0.0; 3]; 3];
+            for (row, values) in k.iter_mut().enumerate() {
+                for (col, value) in values.iter_mut().enumerate() {
+                    *value = *camera_matrix.at_2d::<f64>(row as i32, col as i32).map_err(|err| error.pass(err.to_string()))?;
+                }
+            }
+            k
+        };
+        let distortion = (0..distortion.total() as i32)
+            .map(|i| distortion.at::<f64>(i).copied())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| error.pass(err.to_string()))?;
+        log::info!("{dbg}.calibrate | RMS reprojection error: {:.4} px", rms);
+        Ok(Calibration {
+            image_size: (image_size.width, image_size.height),
+            camera_matrix,
+            distortion,
+            rms,
+            images: image_points.len(),
+        })
+    }
+    ///
+    /// Returns the image files of the `dir`, sorted by name
+    fn images(dir: &Path) -> Result<Vec<PathBuf>, String> {
+        let entries = std::fs::read_dir(dir).map_err(|err| format!("Can't read {:?}: {}", dir, err))?;
+        let mut images: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+            })
+            .collect();
+        images.sort();
+        Ok(images)
+    }
+}
@@ -0,0 +1,7 @@
+mod calibration_args;
+mod calibration;
+mod chessboard;
+
+pub use calibration_args::*;
+pub use calibration::*;
+pub use chessboard::*;
@@ -1,9 +1,9 @@
-use std::time::Instant;
-
 use frdm_tools::{ContextRead, ContextWrite, Eval, EvalResult, Image, ResultCtx};
-use opencv::{core::{DMatch, KeyPoint, KeyPointTraitConst, Mat, MatTraitConst, Point_, VecN, Vector}, imgproc::LineTypes, prelude::{DescriptorMatcherTraitConst, Feature2DTrait}};
+use opencv::{core::{DMatch, KeyPoint, KeyPointTrait, KeyPointTraitConst, Mat, MatTraitConst, Point2f, Point_, Rect, VecN, Vector}, imgproc::LineTypes, prelude::{DescriptorMatcherTraitConst, Feature2DTrait}};
 use sal_core::{dbg::Dbg, error::Error};
 
+use crate::modules::{Detect, Detection, Tracking};
+
 ///
 /// Brute Force Match
 pub struct BfMatch {
@@ -17,6 +17,10 @@ pub struct BfMatch {
 //
 //
 impl BfMatch {
+    /// Max iterations of the trimmed mean in the [BfMatch::center]
+    const CENTER_ITERATIONS: usize = 16;
+    /// The [BfMatch::center] is converged when it moves less than this, px
+    const CENTER_EPSILON: f32 = 0.01;
     ///
     /// Returns [BfMatch] new instance
     /// - `threshold` - ...
@@ -80,7 +84,9 @@ impl BfMatch {
     }
     ///
     /// ORB Matching
-    fn bf_match(dbg: &Dbg, template_img: &Mat, input_img: &mut Mat, match_ratio: f32, deviation_ratio: f32) -> Result<(u16, u16), Error> {
+    /// - `roi` - region of interest of the `input_img` to be searched, None - full frame
+    /// - Returns [Detection] with the number of the matched keypoints as a `score`
+    fn bf_match(dbg: &Dbg, template_img: &Mat, input_img: &mut Mat, roi: Option<Rect>, match_ratio: f32, deviation_ratio: f32) -> Result<Option<Detection>, Error> {
         let mut orb = opencv::features2d::SIFT::create(
             0,
             3,
@@ -95,119 +101,174 @@ impl BfMatch {
         let mut input_descr = Mat::default();
         orb.detect_and_compute(template_img, &mask, &mut template_keypoints, &mut template_descr, false)
             .map_err(|err| Error::new(dbg, "detect_and_compute template_img error").pass(err.to_string()))?;
-        orb.detect_and_compute(input_img, &mask, &mut input_keypoints, &mut input_descr, false)
+        let roi = roi.and_then(|roi| Tracking::clamp(&roi, input_img.cols(), input_img.rows(), template_img.cols(), template_img.rows()));
+        let roi_img = match roi {
+            Some(roi) => Some(
+                Mat::roi(input_img, roi).and_then(|roi_img| roi_img.try_clone())
+                    .map_err(|err| Error::new(dbg, "roi error").pass(err.to_string()))?
+            ),
+            None => None,
+        };
+        orb.detect_and_compute(roi_img.as_ref().unwrap_or(&*input_img), &mask, &mut input_keypoints, &mut input_descr, false)
             .map_err(|err| Error::new(dbg, "detect_and_compute input_img error").pass(err.to_string()))?;
+        // Keypoints found in the region of interest are moved to the frame coordinates
+        let input_keypoints: Vector<KeyPoint> = match roi {
+            Some(roi) => input_keypoints.iter().map(|mut kp| {
+                let pt = kp.pt();
+                kp.set_pt(Point2f::new(pt.x + roi.x as f32, pt.y + roi.y as f32));
+                kp
+            }).collect(),
+            None => input_keypoints,
+        };
         let bf = opencv::features2d::FlannBasedMatcher::create()    //opencv::core::NORM_L2 , true
             .map_err(|err| Error::new(dbg, "BFMatcher::create error").pass(err.to_string()))?;
         // log::debug!("{dbg}.bf_match | Train matches: {:?}", bf_matches);
         let mut bf_matches: Vector<Vector<DMatch>> = Vector::default();
         let mask = unsafe { Mat::new_rows_cols(0, 0, opencv::core::CV_8UC1).unwrap() };
-        bf.knn_train_match(&template_descr, &input_descr, &mut bf_matches, 3, &mask, false)
+        // Template descriptors are the query, frame descriptors are the train set,
+        // so `query_idx` addresses `template_keypoints` and `train_idx` addresses `input_keypoints`
+        bf.knn_train_match(&template_descr, &input_descr, &mut bf_matches, 2, &mask, false)
             .map_err(|err| Error::new(dbg, "knn_train_match").pass(err.to_string()))?;
         log::trace!("{dbg}.bf_match | KNN matches: {:?}", bf_matches);
-        let bf_matches: Vec<Vector<DMatch>> = bf_matches.iter().filter(|mm| {
-            let m0 = mm.get(0).unwrap();
-            let m1 = mm.get(1).unwrap();
-            log::trace!("{dbg}.bf_match | Match: {:?}", m0);
-            m0.distance < match_ratio * m1.distance
+        // Ratio test, only the nearest neighbour of the passed matches is kept
+        let bf_matches: Vec<DMatch> = bf_matches.iter().filter_map(|mm| {
+            match (mm.get(0), mm.get(1)) {
+                (Ok(m0), Ok(m1)) => {
+                    log::trace!("{dbg}.bf_match | Match: {:?}", m0);
+                    (m0.distance < match_ratio * m1.distance).then_some(m0)
+                }
+                // Without the second neighbour the match can't be verified
+                _ => None,
+            }
         }).collect();
         // log::debug!("{dbg}.bf_match | good matches: {:?}", good_matches);
-        let center = Self::center(
-            dbg,
-            deviation_ratio,
-            &bf_matches,
-            &input_keypoints,
-        );
-        if let Some((x, y)) = &center {
+        let input_points: Vec<Point2f> = input_keypoints.iter().map(|kp| kp.pt()).collect();
+        let points = Self::matched_points(dbg, &bf_matches, &input_points);
+        let center = Self::center(dbg, deviation_ratio, &points);
+        if let Some(Point2f { x, y }) = &center {
             log::debug!("{dbg}.bf_match | Center: {x}, {y}");
             Self::draw_dot(input_img, *x, *y);
             Self::draw_text(input_img, 10, input_img.rows() - 48, &format!("x: {}, y: {}", x, y));
         }
+        let bf_matches: Vector<Vector<DMatch>> = bf_matches.into_iter().map(|m| Vector::from_iter([m])).collect();
         input_img.clone_from(
-            &Self::draw_matches_knn(template_img, &template_keypoints, input_img, &input_keypoints, &bf_matches.into())
+            &Self::draw_matches_knn(template_img, &template_keypoints, input_img, &input_keypoints, &bf_matches)
         );
         match center {
-            Some((x, y)) => Ok((x.round() as u16, y.round() as u16)),
-            None => Err(Error::new(dbg, "bf_match").err(format!("Can't find center of {} keypoints", input_keypoints.len()))),
+            Some(Point2f { x, y }) => Ok(Some(Detection { x: x as f64, y: y as f64, scale: 1.0, angle: 0.0, score: points.len() as f64 })),
+            None => {
+                log::debug!("{dbg}.bf_match | Can't find center of {} keypoints", input_keypoints.len());
+                Ok(None)
+            }
         }
     }
     ///
-    /// Returns a geometrical center of the points collection
-    fn center(dbg: &Dbg, deviation_ratio: f32, matches: &Vec<Vector<DMatch>>, keypoints: &Vector<KeyPoint>) -> Option<(f32, f32)> {
-        let points: Vector<KeyPoint> = matches.iter().fold(Vector::new(), |mut acc, m| {
-            acc.push(keypoints.get(m.get(0).unwrap().train_idx as usize).unwrap());
-            acc.push(keypoints.get(m.get(1).unwrap().train_idx as usize).unwrap());
-            acc
-        });
-        let len = points.len();
-        if len >= 2 {
-            log::debug!("{dbg}.center | Total Keypoints: {}", len);
-            let (mut xa, mut ya) = (0.0, 0.0);
-            for p in &points {
-                let p = p.pt();
-                xa += p.x;
-                ya += p.y;
-                log::trace!("{dbg}.center | x: {}, y: {}", p.x, p.y);
-            }
-            xa = xa / len as f32;
-            ya = ya / len as f32;
-            let (deviation_av, deviations) = points.iter().fold((0.0, vec![]), |(acc, mut deviations), p| {
-                let p = p.pt();
-                let deviation = ((p.x - xa).powi(2) + (p.y - ya).powi(2)).sqrt();
-                log::trace!("{dbg}.center | deviation: {}", deviation);
-                deviations.push(deviation);
-                (
-                    acc + deviation,
-                    deviations
-                )
-            });
-            let deviation_av = deviation_av / len as f32;
-            let mut len = 0;
-            let filtered = points.iter().enumerate().filter(|(i, _)| {
-                if deviations[*i] <= deviation_av * deviation_ratio {
-                    log::trace!("{dbg}.center | Filtered deviation: {}", deviations[*i]);
-                    len += 1;
-                    true
-                } else {
-                    false
+    /// Returns the frame points of the matched keypoints
+    /// - `matches` - the nearest neighbour matches, `train_idx` addresses the frame `keypoints`
+    /// - `keypoints` - positions of the keypoints detected on the frame
+    ///
+    /// Each frame keypoint is returned once, even if it was matched by several template keypoints
+    pub fn matched_points(dbg: &Dbg, matches: &[DMatch], keypoints: &[Point2f]) -> Vec<Point2f> {
+        let mut used = vec![false; keypoints.len()];
+        matches.iter().filter_map(|m| {
+            let idx = usize::try_from(m.train_idx).ok()?;
+            match used.get_mut(idx) {
+                Some(used) if !*used => {
+                    *used = true;
+                    Some(keypoints[idx])
+                }
+                Some(_) => None,
+                None => {
+                    log::warn!("{dbg}.matched_points | Match train_idx {} out of {} keypoints", idx, keypoints.len());
+                    None
                 }
-            });
-            let (mut xa, mut ya) = (0.0, 0.0);
-            for (_, kp) in filtered {
-                let p = kp.pt();
-                xa += p.x;
-                ya += p.y;
-                log::trace!("{dbg}.center | x: {}, y: {}", p.x, p.y);
             }
-            log::debug!("{dbg}.center | Filtered Keypoints: {}", len);
-            xa = xa / len as f32;
-            ya = ya / len as f32;
-            Some((xa, ya))
-        } else {
-            None
+        }).collect()
+    }
+    ///
+    /// Returns a robust geometrical center of the points collection
+    /// 
+    /// Starts from the coordinate-wise median, then iteratively averages
+    /// only points closer to the current center than `deviation_ratio` x median deviation,
+    /// until the center and the set of used points stops changing
+    pub fn center(dbg: &Dbg, deviation_ratio: f32, points: &[Point2f]) -> Option<Point2f> {
+        if points.is_empty() {
+            return None;
+        }
+        log::debug!("{dbg}.center | Total Keypoints: {}", points.len());
+        let mut center = Point2f::new(
+            Self::median(points.iter().map(|p| p.x).collect()),
+            Self::median(points.iter().map(|p| p.y).collect()),
+        );
+        let mut len = points.len();
+        for _ in 0..Self::CENTER_ITERATIONS {
+            let deviations: Vec<f32> = points.iter().map(|p| Self::distance(p, &center)).collect();
+            let threshold = Self::median(deviations.clone()) * deviation_ratio;
+            let (sum, filtered) = points.iter().zip(&deviations)
+                .filter(|(_, deviation)| **deviation <= threshold)
+                .fold((Point2f::new(0.0, 0.0), 0), |(sum, len), (p, deviation)| {
+                    log::trace!("{dbg}.center | Filtered deviation: {}", deviation);
+                    (Point2f::new(sum.x + p.x, sum.y + p.y), len + 1)
+                });
+            if filtered == 0 {
+                break;
+            }
+            let next = Point2f::new(sum.x / filtered as f32, sum.y / filtered as f32);
+            let shift = Self::distance(&next, &center);
+            center = next;
+            if filtered == len && shift < Self::CENTER_EPSILON {
+                break;
+            }
+            len = filtered;
+        }
+        log::debug!("{dbg}.center | Filtered Keypoints: {}", len);
+        Some(center)
+    }
+    ///
+    /// Returns the median of the values, `values` must not be empty
+    fn median(mut values: Vec<f32>) -> f32 {
+        values.sort_by(|a, b| a.total_cmp(b));
+        let mid = values.len() / 2;
+        match values.len() % 2 {
+            0 => (values[mid - 1] + values[mid]) * 0.5,
+            _ => values[mid],
         }
     }
+    ///
+    /// Returns the euclidean distance between two points
+    fn distance(a: &Point2f, b: &Point2f) -> f32 {
+        ((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt()
+    }
 }
 //
 //
-impl Eval<Image, EvalResult> for BfMatch {
-    fn eval(&self, src: Image) -> EvalResult {
-        let error = Error::new("BfMatch", "eval");
-        match self.ctx.eval(src.clone()) {
+impl Detect for BfMatch {
+    fn detect(&self, src: Image, roi: Option<Rect>) -> (EvalResult, Option<Detection>) {
+        let error = Error::new("BfMatch", "detect");
+        match self.ctx.eval(src) {
             Ok(ctx) => {
-                let t = Instant::now();
                 let result: &ResultCtx = ctx.read();
                 let mut frame = result.frame.clone();
-                match Self::bf_match(&self.dbg, &self.template.mat, &mut frame.mat, self.match_ratio, self.deviation_ratio) {
-                    Ok(_) => {
+                match Self::bf_match(&self.dbg, &self.template.mat, &mut frame.mat, roi, self.match_ratio, self.deviation_ratio) {
+                    Ok(detection) => {
                         let result = ResultCtx { frame: frame };
-                        log::debug!("BfMatch.eval | Elapsed: {:?}", t.elapsed());
-                        ctx.write(result)
+                        (ctx.write(result), detection)
                     }
-                    Err(err) => Err(error.pass(err.to_string())),
+                    Err(err) => (Err(error.pass(err.to_string())), None),
                 }
             }
-            Err(err) => Err(error.pass(err)),
+            Err(err) => (Err(error.pass(err)), None),
+        }
+    }
+}
+//
+//
+impl Eval<Image, EvalResult> for BfMatch {
+    fn eval(&self, src: Image) -> EvalResult {
+        match self.detect(src, None) {
+            (Ok(ctx), Some(_)) => Ok(ctx),
+            (Ok(_), None) => Err(Error::new("BfMatch", "eval").err("Match not found")),
+            (Err(err), _) => Err(err),
         }
     }
 }
@@ -1,48 +1,123 @@
-use std::{sync::{atomic::{AtomicBool, Ordering}, Arc}, time::Duration};
-use frdm_tools::{camera::Camera, AutoBrightnessAndContrast, AutoBrightnessAndContrastCtx, AutoGamma, AutoGammaCtx, ContextRead, Eval, EvalResult, Image, Initial, InitialCtx, ResultCtx};
+use std::{sync::{atomic::{AtomicBool, Ordering}, Arc}, time::{Duration, Instant, SystemTime, UNIX_EPOCH}};
+use frdm_tools::{camera::Camera, AutoBrightnessAndContrastCtx, AutoGammaCtx, ContextRead, Image, ResultCtx};
+use opencv::core::MatTraitConst;
 use sal_core::{dbg::Dbg, error::Error};
 use sal_sync::{services::{entity::{Name, Object}, Service, ServiceWaiting, RECV_TIMEOUT}, sync::Handles, thread_pool::Scheduler};
 
-use crate::modules::{BfMatch, CameraServiceConf, FilterEmpty, FilterSmooth, Filter};
+use crate::modules::{BfMatch, CameraId, CameraServiceConf, Fusion, CameraState, CameraStatus, CameraTrackerConf, Detect, Detection, Diagnostics, FilterChain, FilterConf, Filter, ImageStage, Matcher, Metrics, Position, Preprocessing, RopeLength, StageRecord, Sway, SwayAnalysis, Target, TemplateMatch, Tracking, WorldPosition};
 
+/// Frames between the diagnostics reports in the log
+const DIAGNOSTICS_REPORT_FRAMES: usize = 100;
+/// Camera timestamp is considered as unsynchronized with the system clock if the latency is bigger
+const MAX_LATENCY: Duration = Duration::from_secs(10);
+/// Interval of the exit checking while waiting before the camera reconnection
+const EXIT_CHECK_INTERVAL: Duration = Duration::from_millis(100);
 /// 
 /// Dects defect on the frames coming from the camera
+/// 
+/// Each configured camera is processed in its own thread,
+/// positions are published tagged with the [CameraId]
+/// 
+/// If the `fusion` configured, the positions of the cameras are fused into the single 3D position
+/// in the separate thread before publishing
 pub struct CameraService {
     name: Name,
     conf: CameraServiceConf,
-    template: Image,
-    position: kanal::Sender<(u16, u16)>,
+    trackers: Vec<Tracker>,
+    position: kanal::Sender<Target>,
     scheduler: Scheduler,
     handles: Arc<Handles<()>>,
     exit: Arc<AtomicBool>,
+    metrics: Metrics,
+    rope: RopeLength,
     dbg: Dbg,
 }
+///
+/// The single camera of the [CameraService]
+#[derive(Clone)]
+struct Tracker {
+    conf: CameraTrackerConf,
+    template: Image,
+    diagnostics: Diagnostics,
+    status: CameraStatus,
+}
 //
 //
 impl CameraService {
     ///
     /// Crteates [CameraService] new instance
+    /// - `templates` - templates of the cameras, in the order of the `conf.cameras`
+    /// - `rope` - current length of the hoist rope, scales the `world` transform of the cameras
     pub fn new(
         parent: impl Into<String>,
         conf: CameraServiceConf,
-        template: Image,
-        position: kanal::Sender<(u16, u16)>,
+        templates: Vec<Image>,
+        position: kanal::Sender<Target>,
+        metrics: Metrics,
+        rope: RopeLength,
         scheduler: Scheduler,
     ) -> Self {
         let name = Name::new(parent, "CameraService");
         let dbg = Dbg::new(name.parent(), name.me());
+        let trackers = conf.cameras.iter().zip(templates)
+            .map(|(camera, template)| {
+                let tracker = Tracker {
+                    conf: camera.clone(),
+                    template,
+                    diagnostics: Diagnostics::new(),
+                    status: CameraStatus::new(),
+                };
+                metrics.add_diagnostics(camera.id, tracker.diagnostics.clone());
+                metrics.add_camera(camera.id, tracker.status.clone());
+                tracker
+            })
+            .collect();
         Self {
             name,
             conf,
-            template,
+            trackers,
             position,
             scheduler,
             handles: Arc::new(Handles::new(&dbg)),
             exit: Arc::new(AtomicBool::new(false)),
+            metrics,
+            rope,
             dbg,
         }
     }
     ///
+    /// Returns the diagnostics of the frame processing pipeline of the `camera`
+    pub fn diagnostics(&self, camera: CameraId) -> Option<Diagnostics> {
+        self.trackers.iter().find(|tracker| tracker.conf.id == camera).map(|tracker| tracker.diagnostics.clone())
+    }
+    ///
+    /// Returns the state of the `camera` connection
+    pub fn status(&self, camera: CameraId) -> Option<CameraStatus> {
+        self.trackers.iter().find(|tracker| tracker.conf.id == camera).map(|tracker| tracker.status.clone())
+    }
+    ///
+    /// Waits `delay` before the camera reconnection
+    /// - Returns false if exit requested while waiting
+    fn wait_reconnect(delay: Duration, exit: &AtomicBool) -> bool {
+        let until = Instant::now() + delay;
+        while Instant::now() < until {
+            if exit.load(Ordering::Acquire) {
+                return false;
+            }
+            std::thread::sleep(EXIT_CHECK_INTERVAL.min(until.saturating_duration_since(Instant::now())));
+        }
+        !exit.load(Ordering::Acquire)
+    }
+    ///
+    /// Updates the tracking [Metrics] with the result of the frame
+    fn update_metrics(metrics: &Metrics, conf: &CameraTrackerConf, detection: Option<Detection>, filter: &FilterChain<Position<f64>>) {
+        metrics.frame(conf.id, detection.is_some());
+        if let (Matcher::BfMatch, Some(detection)) = (&conf.template_match.matcher, detection) {
+            metrics.keypoints(conf.id, detection.score as u64);
+        }
+        metrics.rejected(conf.id, filter.rejected() as u64);
+    }
+    ///
     /// Create opencv Ui windows
     fn setup_opencv_windows(dbg: &Dbg, keys: Vec<impl Into<String>>) {
         for key in keys {
@@ -53,25 +128,123 @@ impl CameraService {
         // opencv::highgui::wait_key(1).unwrap();
     }
     ///
+    /// Returns the preprocessing `Eval` chain, configured in the `image` section
+    fn preprocessing(conf: &CameraTrackerConf, diagnostics: &Diagnostics) -> Preprocessing {
+        Preprocessing::new(&conf.image, diagnostics)
+    }
+    ///
+    /// Returns the offset of the processed frame relative to the source frame, px
+    fn offset(conf: &CameraTrackerConf) -> (f64, f64) {
+        match (&conf.image.cropping, conf.image.stages.contains(&ImageStage::Cropping)) {
+            (Some(cropping), true) => (cropping.x as f64, cropping.y as f64),
+            _ => (0.0, 0.0),
+        }
+    }
+    ///
+    /// Returns the [Sway] of the `world` position at the `time`, s, if the `sway` analysis configured
+    /// - `rope` - rope length read by the `HoistService`, m
+    fn sway(conf: &CameraTrackerConf, sway: Option<&mut SwayAnalysis>, world: Option<Position<f64>>, rope: Option<f64>, time: f64) -> Option<Sway> {
+        let length = conf.sway.as_ref()?.length(rope)?;
+        sway?.add(time, world?, length)
+    }
+    ///
+    /// Returns the `point` of the source frame undistorted by the `calibration` of the camera, if configured
+    fn undistort(conf: &CameraTrackerConf, point: Position<f64>) -> Position<f64> {
+        match &conf.calibration {
+            Some(calibration) => calibration.undistort(point),
+            None => point,
+        }
+    }
+    ///
+    /// Returns the matcher configured in the `template-match` section
+    fn matcher(conf: &CameraTrackerConf, template: Image, diagnostics: &Diagnostics) -> Box<dyn Detect> {
+        let tm = &conf.template_match;
+        match tm.matcher {
+            Matcher::BfMatch => Box::new(BfMatch::new(
+                tm.method,
+                tm.match_ratio,
+                tm.deviation_ratio,
+                template,
+                Self::preprocessing(conf, diagnostics),
+            )),
+            Matcher::TemplateMatch => Box::new(TemplateMatch::new(
+                tm.method,
+                tm.threshold,
+                &tm.scale,
+                &tm.angle,
+                tm.pyramid_levels,
+                template,
+                Self::preprocessing(conf, diagnostics),
+            )),
+        }
+    }
+    ///
+    /// Returns the chain of the position filters configured in the `template-match` section
+    fn filters(conf: &CameraTrackerConf) -> FilterChain<Position<f64>> {
+        FilterChain::new(
+            conf.template_match.filters.iter().map(FilterConf::filter).collect(),
+        )
+    }
+    ///
+    /// Returns the time of the `frame`, seconds, taken from the camera timestamp, ns
+    fn seconds(frame: &Image) -> f64 {
+        frame.timestamp as f64 * 1e-9
+    }
+    ///
+    /// Returns the end-to-end latency of the `frame`
+    /// - from the camera timestamp, if the camera clock is synchronized with the system one
+    /// - from the moment the frame was `received` otherwise
+    fn latency(frame: &Image, received: Instant) -> Duration {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        let timestamp = Duration::from_nanos(frame.timestamp as u64);
+        match now.checked_sub(timestamp) {
+            Some(latency) if latency < MAX_LATENCY => latency,
+            _ => received.elapsed(),
+        }
+    }
+    ///
     /// Processing an image
-    fn process(dbg: &Dbg, window: &str, window_src: &str, window_gamma: &str, window_abc: &str, templ_match: &impl Eval<Image, EvalResult>, frame: &Image) -> Option<(u16, u16)> {
+    /// - `stages` - configured preprocessing stages, their intermediate frames are displayed
+    /// - `tracking` - if configured, the frame searched in the region of interest around the last known position
+    /// - `diagnostics` - the matcher is recorded into
+    fn process(dbg: &Dbg, window: &str, window_src: &str, window_gamma: &str, window_abc: &str, stages: &[ImageStage], templ_match: &dyn Detect, tracking: &mut Option<Tracking>, diagnostics: &Diagnostics, frame: &Image) -> Option<Detection> {
         log::info!("{dbg}.process | Source frame...");
         opencv::highgui::imshow(window_src, &frame.mat).unwrap();
         opencv::highgui::wait_key(1).unwrap();
         log::info!("{dbg}.process | Calculations...");
-        match templ_match.eval(frame.clone()) {
+        let roi = tracking.as_ref().and_then(|tracking| tracking.roi());
+        log::debug!("{dbg}.process | Region of interest: {:?}", roi);
+        let t = Instant::now();
+        let (ctx, detection) = templ_match.detect(frame.clone(), roi);
+        diagnostics.record(StageRecord {
+            stage: "matcher".to_owned(),
+            elapsed: t.elapsed(),
+            output: ctx.as_ref().ok().map(|ctx| {
+                let result: &ResultCtx = ctx.read();
+                (result.frame.mat.cols(), result.frame.mat.rows())
+            }),
+            error: ctx.as_ref().err().map(|err| err.to_string()),
+        });
+        if let Some(tracking) = tracking {
+            tracking.update(detection.as_ref());
+        }
+        match ctx {
             Ok(ctx) => {
                 log::info!("{dbg}.process | Calculations - Ok");
                 
-                let gamma: &AutoGammaCtx = ctx.read();
-                log::info!("{dbg}.process | Gamma frame...");
-                opencv::highgui::imshow(window_gamma, &gamma.result.mat).unwrap();
-                opencv::highgui::wait_key(1).unwrap();
+                if stages.contains(&ImageStage::Gamma) {
+                    let gamma: &AutoGammaCtx = ctx.read();
+                    log::info!("{dbg}.process | Gamma frame...");
+                    opencv::highgui::imshow(window_gamma, &gamma.result.mat).unwrap();
+                    opencv::highgui::wait_key(1).unwrap();
+                }
 
-                let abc: &AutoBrightnessAndContrastCtx = ctx.read();
-                log::info!("{dbg}.process | ABC frame...");
-                opencv::highgui::imshow(window_abc, &abc.result.mat).unwrap();
-                opencv::highgui::wait_key(1).unwrap();
+                if stages.contains(&ImageStage::BrightnessContrast) {
+                    let abc: &AutoBrightnessAndContrastCtx = ctx.read();
+                    log::info!("{dbg}.process | ABC frame...");
+                    opencv::highgui::imshow(window_abc, &abc.result.mat).unwrap();
+                    opencv::highgui::wait_key(1).unwrap();
+                }
                 
                 let result: &ResultCtx = ctx.read();
                 log::info!("{dbg}.process | Result frame...");
@@ -80,7 +253,7 @@ impl CameraService {
             }
             Err(err) => log::info!("{dbg}.run | Template match error: {:?}", err),
         };
-        Some((0, 0))
+        detection
     }
 }
 //
@@ -106,129 +279,233 @@ impl Service for CameraService {
     //
     // 
     fn run(&self) -> Result<(), Error> {
-        let dbg = self.dbg.clone();
         let name = self.name.clone();
-        let conf = self.conf.clone();
-        let template = self.template.clone();
-        let position = self.position.clone();
-        let window = format!("Matching result");
-        let window_src = format!("Source frame");
-        let window_gamma = format!("Auto gamma frame");
-        let window_abc = format!("BrightnessContrast");
-        let exit = self.exit.clone();
-        let service_waiting = ServiceWaiting::new(&name, conf.wait_started);
-        let service_release = service_waiting.release();
-        let handles_clone = self.handles.clone();
-        log::debug!("{}.run | Preparing thread...", dbg);
-        let handle = self.scheduler.spawn(move || {
-            let dbg = &dbg;
-            Self::setup_opencv_windows(&dbg, vec![&window, &window_src, &window_gamma, &window_abc]);
-            // let mut template_gray = opencv::core::Mat::default();
-            // opencv::imgproc::cvt_color(&template.mat, &mut template_gray, opencv::imgproc::COLOR_BGR2GRAY, 0)
-            //     .map_err(|err| Error::new(dbg, "template to gray error").pass(err.to_string()))?;
-            let (mut filter_x, mut filter_y): (Box<dyn Filter<Item = u16>>, Box<dyn Filter<Item = u16>>) = match conf.template_match.smooth {
-                Some(smooth) => (Box::new(FilterSmooth::<u16>::new(None, smooth)), Box::new(FilterSmooth::<u16>::new(None, smooth))),
-                None => (Box::new(FilterEmpty::<u16>::new()), Box::new(FilterEmpty::<u16>::new())),
+        let me = name.me();
+        // Service can be started again after exit
+        self.exit.store(false, Ordering::Release);
+        let service_waiting = ServiceWaiting::new(&name, self.conf.wait_started);
+        // Positions of the cameras are sent to the fusion thread if configured
+        let views = self.conf.fusion.as_ref().map(|_| kanal::unbounded::<Target>());
+        for tracker in &self.trackers {
+            let tracker = tracker.clone();
+            let dbg = Dbg::new(&self.dbg, &tracker.conf.title);
+            let title = tracker.conf.title.clone();
+            let me = me.clone();
+            let conf = tracker.conf;
+            let template = tracker.template;
+            let diagnostics = tracker.diagnostics;
+            let status = tracker.status;
+            let position = match &views {
+                Some((send, _)) => send.clone(),
+                None => self.position.clone(),
             };
-            let templ_match = BfMatch::new(
-                conf.template_match.method,
-                conf.template_match.match_ratio,
-                conf.template_match.deviation_ratio,
-                template,
-                AutoBrightnessAndContrast::new(
-                    conf.image.brightness_contrast.hist_clip_left,
-                    conf.image.brightness_contrast.hist_clip_right,
-                    AutoGamma::new(
-                        conf.image.gamma.factor,
-                        Initial::new(
-                            InitialCtx::new(),
-                        ),
-                    ),
-                ),
-                // GrayScale::new(
-                // ),
-            );
-            let mut camera = Camera::new(conf.camera.clone());
-            match &conf.camera.from_path {
-                Some(path) => {
-                    log::info!("{dbg}.run | Starting camera from path '{path}'...");
-                    let frames = camera.from_images(path).unwrap();
-                    service_release.add(Ok(()));
-                    for frame in frames {
-                        Self::process(&dbg, &window, &window_src, &window_gamma, &window_abc, &templ_match, &frame);
-                        // match templ_match.eval(frame) {
-                        //     Err(err) => log::info!("{dbg}.run | Template match error: {:?}", err),
-                        // }
-                        std::thread::sleep(Duration::from_millis(512));
+            let window = format!("{} | Matching result", conf.title);
+            let window_src = format!("{} | Source frame", conf.title);
+            let window_gamma = format!("{} | Auto gamma frame", conf.title);
+            let window_abc = format!("{} | BrightnessContrast", conf.title);
+            let exit = self.exit.clone();
+            let metrics = self.metrics.clone();
+            let rope = self.rope.clone();
+            let service_release = service_waiting.release();
+            let handles_clone = self.handles.clone();
+            log::debug!("{}.run | Preparing thread...", dbg);
+            let handle = self.scheduler.spawn(move || {
+                let dbg = &dbg;
+                Self::setup_opencv_windows(&dbg, vec![&window, &window_src, &window_gamma, &window_abc]);
+                // let mut template_gray = opencv::core::Mat::default();
+                // opencv::imgproc::cvt_color(&template.mat, &mut template_gray, opencv::imgproc::COLOR_BGR2GRAY, 0)
+                //     .map_err(|err| Error::new(dbg, "template to gray error").pass(err.to_string()))?;
+                let mut filter = Self::filters(&conf);
+                let templ_match = Self::matcher(&conf, template, &diagnostics);
+                let mut tracking = conf.template_match.tracking.clone().map(Tracking::new);
+                let world = conf.world.as_ref().map(|world| world.transform());
+                let mut sway = conf.sway.as_ref().map(SwayAnalysis::new);
+                let (offset_x, offset_y) = Self::offset(&conf);
+                let mut camera = Camera::new(conf.camera.clone());
+                match &conf.camera.from_path {
+                    Some(path) => {
+                        log::info!("{dbg}.run | Starting camera from path '{path}'...");
+                        let frames = camera.from_images(path).unwrap();
+                        service_release.add(Ok(()));
+                        status.set(CameraState::Streaming);
+                        for frame in frames {
+                            let received = Instant::now();
+                            let detection = Self::process(&dbg, &window, &window_src, &window_gamma, &window_abc, &conf.image.stages, templ_match.as_ref(), &mut tracking, &diagnostics, &frame);
+                            Self::update_metrics(&metrics, &conf, detection, &filter);
+                            diagnostics.finish_frame(Some(received.elapsed()));
+                            // match templ_match.eval(frame) {
+                            //     Err(err) => log::info!("{dbg}.run | Template match error: {:?}", err),
+                            // }
+                            std::thread::sleep(Duration::from_millis(512));
+                            if exit.load(Ordering::Acquire) {
+                                break;
+                            }
+                        }
                     }
-                }
-                None => {
-                    let camera_stream = camera.stream();
-                    service_release.add(Ok(()));
-                    'main: loop {
-                        log::debug!("{dbg}.run | Starting camera...");
-                        match camera.read() {
-                            Ok(handle) => {
-                                log::debug!("{dbg}.run | Starting camera - Ok");
-                                handles_clone.push(handle);
-                                log::debug!("{dbg}.run | Receiving frames from camera...");
-                                'camera: loop {
-                                    match camera_stream.recv_timeout(RECV_TIMEOUT) {
-                                        Ok(frame) => {
-                                            if let Some((x, y)) = Self::process(&dbg, &window, &window_src, &window_gamma, &window_abc, &templ_match, &frame) {
-                                                let pos = (filter_x.add(x).unwrap(), filter_y.add(y).unwrap());
-                                                if let Err(err) = position.send(pos) {
-                                                    log::error!("{dbg}.run | Can't send position to ModbusService: {:?}", err);
-                                                    camera.exit();
-                                                    break 'main;
+                    None => {
+                        let camera_stream = camera.stream();
+                        service_release.add(Ok(()));
+                        // Reconnections in a row without frames received
+                        let mut failures = 0;
+                        'main: loop {
+                            log::debug!("{dbg}.run | Starting camera...");
+                            status.set(CameraState::Connecting);
+                            match camera.read() {
+                                Ok(handle) => {
+                                    log::debug!("{dbg}.run | Starting camera - Ok");
+                                    handles_clone.push(handle);
+                                    log::debug!("{dbg}.run | Receiving frames from camera...");
+                                    let mut last_frame = Instant::now();
+                                    'camera: loop {
+                                        match camera_stream.recv_timeout(RECV_TIMEOUT) {
+                                            Ok(frame) => {
+                                                let received = Instant::now();
+                                                last_frame = received;
+                                                if status.state() != CameraState::Streaming {
+                                                    log::info!("{dbg}.run | Camera streaming");
+                                                    status.set(CameraState::Streaming);
+                                                    failures = 0;
+                                                }
+                                                let detection = Self::process(&dbg, &window, &window_src, &window_gamma, &window_abc, &conf.image.stages, templ_match.as_ref(), &mut tracking, &diagnostics, &frame);
+                                                let filtered = match detection {
+                                                    Some(detection) => filter.add_at(Self::undistort(&conf, Position::new(offset_x + detection.x, offset_y + detection.y)), Self::seconds(&frame)),
+                                                    None => filter.predict_at(Self::seconds(&frame)),
+                                                };
+                                                let target = filtered.and_then(|filtered| {
+                                                    let rope = rope.get();
+                                                    // Not published if the world position is configured, but unknown, e.g. the rope length isn't read yet
+                                                    let world = match &world {
+                                                        Some(world) => Some(world.world_at(filtered, rope)?),
+                                                        None => None,
+                                                    };
+                                                    Some(Target {
+                                                        camera: conf.id,
+                                                        time: Self::seconds(&frame),
+                                                        position: filtered,
+                                                        velocity: filter.velocity(),
+                                                        world: world.map(WorldPosition::Plane),
+                                                        sway: Self::sway(&conf, sway.as_mut(), world, rope, Self::seconds(&frame)),
+                                                    })
+                                                });
+                                                Self::update_metrics(&metrics, &conf, detection, &filter);
+                                                let mut latency = None;
+                                                if let Some(target) = target {
+                                                    metrics.position(&me, target.camera, target.position);
+                                                    if let Err(err) = position.send(target) {
+                                                        log::error!("{dbg}.run | Can't send position to ModbusService: {:?}", err);
+                                                        camera.exit();
+                                                        break 'main;
+                                                    }
+                                                    latency = Some(Self::latency(&frame, received));
+                                                }
+                                                diagnostics.finish_frame(latency);
+                                                if diagnostics.frames() % DIAGNOSTICS_REPORT_FRAMES == 0 {
+                                                    log::info!("{dbg}.run | Diagnostics: {}", diagnostics.report());
                                                 }
                                             }
-                                        }
-                                        Err(err) => {
-                                            match err {
-                                                kanal::ReceiveErrorTimeout::Timeout => {}
-                                                _ => {
-                                                    log::error!("{dbg}.run | Can't receive frame from Camera: {:?}", err);
-                                                    break 'camera;
+                                            Err(err) => {
+                                                match err {
+                                                    kanal::ReceiveErrorTimeout::Timeout => {
+                                                        if last_frame.elapsed() >= conf.reconnect.frame_timeout {
+                                                            log::warn!("{dbg}.run | No frames from Camera for {:?}", last_frame.elapsed());
+                                                            break 'camera;
+                                                        }
+                                                    }
+                                                    _ => {
+                                                        log::error!("{dbg}.run | Can't receive frame from Camera: {:?}", err);
+                                                        break 'camera;
+                                                    }
                                                 }
                                             }
                                         }
+                                        if exit.load(Ordering::Acquire) {
+                                            camera.exit();
+                                            break 'main;
+                                        }
                                     }
-                                    if exit.load(Ordering::Acquire) {
-                                        camera.exit();
-                                        break 'main;
-                                    }
+                                    camera.exit();
                                 }
-                                camera.exit();
+                                Err(err) => log::info!("{dbg}.run | Camera error: {:?}", err),
+                            }
+                            status.set(CameraState::Down);
+                            failures += 1;
+                            let backoff = conf.reconnect.backoff(failures);
+                            log::info!("{dbg}.run | Camera down, reconnecting in {:?}, failures in a row: {}", backoff, failures);
+                            if !Self::wait_reconnect(backoff, &exit) {
+                                break 'main;
                             }
-                            Err(err) => log::info!("{dbg}.run | Camera error: {:?}", err),
+                            status.reconnect();
                         }
+                        camera.exit();
                     }
-                    camera.exit();
+                }
+                status.set(CameraState::Down);
+                log::info!("{dbg}.run | Exit");
+                Ok(())
+            });
+            match handle {
+                Ok(handle) => self.handles.push(handle),
+                Err(err) => {
+                    let err = Error::new(&self.dbg, "run").pass_with(format!("Start camera '{title}' failed"), err.to_string());
+                    log::warn!("{}", err);
+                    return Err(err);
                 }
             }
-            log::info!("{dbg}.run | Exit");
-            Ok(())
-        });
-        match handle {
-            Ok(handle) => {
-                self.handles.push(handle);
-                let r = match conf.wait_started {
-                    Some(_) => {
-                        log::info!("{}.run | Waiting while starting...", self.dbg);
-                        service_waiting.wait()
+        }
+        if let (Some(conf), Some((_, views))) = (self.conf.fusion.clone(), views) {
+            let dbg = Dbg::new(&self.dbg, "Fusion");
+            let models = self.trackers.iter()
+                .filter_map(|tracker| tracker.conf.model.as_ref().map(|model| (tracker.conf.id, model.model())))
+                .collect();
+            let sync = conf.sync;
+            let mut fusion = Fusion::new(conf, models);
+            let position = self.position.clone();
+            let exit = self.exit.clone();
+            let metrics = self.metrics.clone();
+            log::debug!("{}.run | Preparing thread...", dbg);
+            let handle = self.scheduler.spawn(move || {
+                loop {
+                    let mut published = match views.recv_timeout(sync) {
+                        Ok(target) => fusion.add(target, Instant::now()),
+                        Err(kanal::ReceiveErrorTimeout::Timeout) => vec![],
+                        Err(err) => {
+                            log::debug!("{dbg}.run | Cameras are closed: {:?}", err);
+                            break;
+                        }
+                    };
+                    published.extend(fusion.poll(Instant::now()));
+                    for target in published {
+                        metrics.fusion(fusion.fused() as u64, fusion.fallback() as u64);
+                        if let Err(err) = position.send(target) {
+                            log::error!("{dbg}.run | Can't send position to ModbusService: {:?}", err);
+                            return Ok(());
+                        }
                     }
-                    None => Ok(()),
-                };
-                log::info!("{}.run | Starting - ok", self.dbg);
-                r
-            }
-            Err(err) => {
-                let err = Error::new(&self.dbg, "run").pass_with("Start failed", err.to_string());
-                log::warn!("{}", err);
-                Err(err)
+                    if exit.load(Ordering::Acquire) {
+                        break;
+                    }
+                }
+                log::info!("{dbg}.run | Exit");
+                Ok(())
+            });
+            match handle {
+                Ok(handle) => self.handles.push(handle),
+                Err(err) => {
+                    let err = Error::new(&self.dbg, "run").pass_with("Start fusion failed", err.to_string());
+                    log::warn!("{}", err);
+                    return Err(err);
+                }
             }
         }
+        let r = match self.conf.wait_started {
+            Some(_) => {
+                log::info!("{}.run | Waiting while starting...", self.dbg);
+                service_waiting.wait()
+            }
+            None => Ok(()),
+        };
+        log::info!("{}.run | Starting - ok", self.dbg);
+        r
     }
     //
     //
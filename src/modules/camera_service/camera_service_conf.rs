@@ -1,9 +1,8 @@
-use std::time::Duration;
-use frdm_tools::camera::CameraConf;
+use std::{str::FromStr, time::Duration};
 use sal_core::dbg::Dbg;
-use sal_sync::services::{conf::{ConfTree, ConfTreeGet}, entity::Name};
+use sal_sync::services::{conf::{ConfCustomKeywd, ConfTree, ConfTreeGet}, entity::Name};
 
-use crate::modules::{ImageConf, TemplateMatchConf};
+use crate::modules::{CameraTrackerConf, FusionConf};
 
 ///
 /// ## The configuration parameters for the `RopeDefect`
@@ -12,13 +11,15 @@ use crate::modules::{ImageConf, TemplateMatchConf};
 /// ```yaml
 /// service CameraService:
 ///     wait-started: 10 ms         # optional, next service will wait until current completely started plus specified time
-///     camera Camera1:
+///     camera Camera1:                 # one or more cameras, each one is processed in its own thread
+///         id: 1                       # optional, tags the positions of the camera, default - index of the camera in the service
 ///         fps: Max                    # Max / Min / 30.0
 ///         resolution: 
 ///             width: 1200
 ///             height: 800
 ///         index: 0
 ///         # address: 192.168.10.12:2020
+///         # calibration: calibration.yaml   # optional, saved by the `calibrate` command, the detected positions are undistorted, `image-size` must match the `resolution`
 ///         # Mono8/10/12/16, Bayer8/10/12/16, RGB8, BGR8, YCbCr8, YCbCr411, YUV422, YUV411 | Default and fastest BayerRG8
 ///         # pixel-format:  Mono8
 ///         # pixel-format:  BayerRG8
@@ -30,35 +31,100 @@ use crate::modules::{ImageConf, TemplateMatchConf};
 ///         auto-packet-size: true          # StreamAutoNegotiatePacketSize
 ///         channel-packet-size: Max        # Maximizing packet size increases frame rate
 ///         resend-packet: true             # StreamPacketResendEnable
-///     image:
-///         cropping:
-///             x: 10           # new left edge
-///             width: 1900     # new image width
-///             y: 10           # new top edge
-///             height: 1180    # new image height
+///     # camera Camera2:
+///     #     id: 2
+///     #     address: 192.168.10.13:2020
+///     #     ...
+///     #     template-match:             # optional, own `image`, `pipeline`, `template-match` & `reconnect` of the camera,
+///     #         template: assets/template_2.png # the ones of the service are used if omitted
+///     #         ...
+///     #     calibration: calibration_2.yaml  # optional, saved by the `calibrate` command, the detected positions are undistorted, `image-size` must match the `resolution`
+///     #     intrinsics:                 # optional, required for the `fusion` if no `calibration`, px
+///     #         fx: 1400.0
+///     #         fy: 1400.0
+///     #         cx: 600.0
+///     #         cy: 400.0
+///     #     extrinsics:                 # world to camera transformation: `Xc = R * Xw + t`
+///     #         rotation: [0.0, 0.1, 0.0]       # Rodrigues vector, rad, or 3x3 matrix, row-major
+///     #         translation: [-0.5, 0.0, 0.0]   # m
+///     # fusion:                       # optional, fusion of the calibrated cameras into the single 3D position, `world` of the cameras required
+///     #     sync: 40 ms                   # optional, max time between the views of the cameras to be fused, default 40 ms
+///     #     max-error: 5.0                # optional, max reprojection error of the fused position, px, default 5.0
+///     reconnect:                      # optional, camera reconnection
+///         backoff: 500 ms                 # optional, delay before the reconnection, doubled on each failure in a row, default 500 ms
+///         max-backoff: 30 s               # optional, maximum delay before the reconnection, default 30 s
+///         frame-timeout: 2000 ms          # optional, camera is reconnected if no frames this time, default 2000 ms
+///     # pipeline:                     # optional, order of the frame processing stages, default - the order of the `image` sections
+///     #     - initial
+///     #     - gray-scale
+///     #     - gamma                     # `image.gamma`
+///     #     - brightness-contrast       # `image.brightness-contrast`
+///     #     - blur                      # `image.gausian`
+///     #     - matcher                   # `template-match`
+///     image:                          # preprocessing stages, each one is optional, applied in the listed order
 ///         gamma:
 ///             factor: 95.0            # percent of influence of [AutoGamma] algorythm bigger the value more the effect of [AutoGamma] algorythm, %
 ///         brightness-contrast:
 ///             hist-clip-left: 1.0     # optional histogram clipping from right, default = 0.0 %
 ///             hist-clip-right: 1.0    # optional histogram clipping from right, default = 0.0 %
-///         gausian:
-///             blur-size:              # blur radius
-///                 width: 3
-///                 height: 3
-///             sigma-x: 0.0
-///             sigma-y: 0.0
-///         sobel:
-///             kernel-size: 3
-///             scale: 1.0
-///             delta: 0.0
-///         overlay:
-///             src1-weight: 0.5
-///             src2-weight: 0.5
-///             gamma: 0.0
+///         # gausian:
+///         #     blur-size:              # blur radius
+///         #         width: 3
+///         #         height: 3
+///         #     sigma-x: 0.0
+///         #     sigma-y: 0.0
+///         # sobel:                      # edges of the frame, gray scale
+///         #     kernel-size: 3
+///         #     scale: 1.0
+///         #     delta: 0.0
+///         # overlay:                    # weighted sum of the source frame & the frame processed by the stages above, must precede cropping
+///         #     src1-weight: 0.5
+///         #     src2-weight: 0.5
+///         #     gamma: 0.0
+///         # cropping:                   # detected position remains relative to the source frame
+///         #     x: 10           # new left edge
+///         #     width: 1900     # new image width
+///         #     y: 10           # new top edge
+///         #     height: 1180    # new image height
 ///     template-match:
+///         matcher: BfMatch            # optional, BfMatch (default) - keypoints matching, TemplateMatch - multi-scale & rotation template matching
 ///         match-ratio: 0.8            # the lower the ratio, the less matches will used (by the match distance)
-///         deviation-ratio: 1.7        # the lower the ratio, the less key points will used (closest to the geometrical center) 
+///         deviation-ratio: 1.7        # the lower the ratio, the less key points will used (deviation from the center relative to the median deviation) 
 ///         method: TM_CCOEFF_NORMED    # TM_CCOEFF_NORMED or TM_CCORR_NORMED recomended,
+///         threshold: 0.7              # optional, TemplateMatch only, minimum score of the match (maximum for TM_SQDIFF*), default 0.8
+///         scale:                      # optional, TemplateMatch only, template scales to be searched, default 1.0
+///             min: 0.6
+///             max: 1.4
+///             step: 0.1
+///         angle:                      # optional, TemplateMatch only, template rotations to be searched, degrees, default 0.0
+///             min: -10.0
+///             max: 10.0
+///             step: 5.0
+///         pyramid-levels: 2           # optional, TemplateMatch only, coarse search on the frame downscaled 2^levels times, default 0
+///         tracking:                   # optional, search in the region of interest around the last known position, default full frame search
+///             width: 320                  # width of the region of interest, px
+///             height: 320                 # height of the region of interest, px
+///             expand: 1.5                 # optional, region of interest grows this times on each miss, default 1.5
+///             max-misses: 5               # optional, full frame search after this number of misses, default 5
+///             min-score: 12               # optional, lower scored detections are misses: matched keypoints for BfMatch, correlation for TemplateMatch, default - any detection
+///         # filters:                    # optional, chain of the position filters, applied in the listed order, used instead of `smooth-moving`
+///         #     - outlier:
+///         #         max-speed: 40               # maximum allowed jump of the position, px per `per`, bigger jumps are rejected
+///         #         per: frame                  # optional, frame (default) / second - real time between the frames is used
+///         #         relock: 3                   # optional, new location accepted after this number of consistent jumps, default 3
+///         #     - low-pass:
+///         #         cutoff: 2.0                 # cutoff frequency, Hz
+///         #         sample-time: 0.04           # time between the frames, seconds
+///         #     - kalman:
+///         #         model: velocity             # optional, velocity (default) - constant velocity / acceleration - constant acceleration
+///         #         process-noise: 0.5          # variance of the unmodeled acceleration (jerk for `acceleration` model) per frame
+///         #         measurement-noise: 4.0      # variance of the detected position, px^2
+///         #         max-predictions: 5          # optional, position predicted through this number of missed frames, default 5
+///         #         sample-time: 0.04           # optional, nominal time between the frames, seconds, if set - real time between the frames is used
+///         #     - smooth:
+///         #         factor: 2                   # the lower the setting, the lower the smoothing, 1 - no smoothing, recomended max: 256
+///         #         axis: x                     # optional, x / y - filter only single axis, default - both axes
+///         #         sample-time: 0.04           # optional, nominal time between the frames, seconds, if set - real time between the frames is used
 ///         smooth-moving: 4    # Smooth filter for X & Y changes presset, the lower the setting, the lower the smoothing, 1 (default) - no smoothing, recomended max: 256
 /// ```
 #[derive(Debug, Clone, PartialEq)]
@@ -66,9 +132,10 @@ pub struct CameraServiceConf {
     pub name: Name,
     /// Next service will wait until current completely started plus specified time, optional
     pub wait_started: Option<Duration>,
-    pub image: ImageConf,
-    pub template_match: TemplateMatchConf,
-    pub camera: CameraConf,
+    /// Cameras of the service, each one is processed in its own thread
+    pub cameras: Vec<CameraTrackerConf>,
+    /// Fusion of the cameras into the single 3D position, optional
+    pub fusion: Option<FusionConf>,
 }
 //
 // 
@@ -87,21 +154,48 @@ impl CameraServiceConf {
         log::trace!("{dbg}.new | name: {:?}", name);
         let wait_started: Option<Duration> = conf.get_duration("wait-started").ok();
         log::trace!("{}.new | wait-started: {:?}", dbg, wait_started);
-        let image = conf.get("image").expect(&format!("{dbg}.new | 'image' - not found or wrong configuration"));
-        let image = ImageConf::new(&dbg, image);
-        log::trace!("{}.new | image: {:?}", dbg, image);
-        let template_match = conf.get("template-match").expect(&format!("{dbg}.new | 'template-match' - not found or wrong configuration"));
-        let template_match = TemplateMatchConf::new(&dbg, template_match);
-        log::trace!("{}.new | template-match: {:?}", dbg, template_match);
-        let camera = conf.get("camera").expect(&format!("{dbg}.new | 'camera' - not found or wrong configuration"));
-        let camera = CameraConf::new(&name, &camera);
-        log::trace!("{dbg}.new | camera: {:#?}", camera);
+        let cameras: Vec<CameraTrackerConf> = conf.nodes()
+            .filter_map(|node| {
+                match node.key.as_str() {
+                    "camera" => Some((String::new(), node)),
+                    _ => match ConfCustomKeywd::from_str(&node.key) {
+                        Ok(keywd) if keywd.name() == "camera" => Some((keywd.title().to_string(), node)),
+                        _ => None,
+                    }
+                }
+            })
+            .enumerate()
+            .map(|(index, (title, node))| CameraTrackerConf::new(&dbg, index, &title, node, &conf))
+            .collect();
+        if cameras.is_empty() {
+            panic!("{dbg}.new | 'camera' - not found or wrong configuration");
+        }
+        for (index, camera) in cameras.iter().enumerate() {
+            if cameras[..index].iter().any(|other| other.id == camera.id) {
+                panic!("{dbg}.new | Duplicated camera id {:?} of '{}'", camera.id, camera.title);
+            }
+        }
+        log::trace!("{}.new | cameras: {:?}", dbg, cameras.iter().map(|camera| (camera.id, &camera.title)).collect::<Vec<_>>());
+        let fusion: Option<ConfTree> = conf.get("fusion");
+        let fusion = fusion.map(|fusion| FusionConf::new(&dbg, fusion));
+        log::trace!("{}.new | fusion: {:?}", dbg, fusion);
+        if fusion.is_some() {
+            if cameras.len() < 2 {
+                panic!("{dbg}.new | 'fusion' requires two or more cameras, found: {}", cameras.len());
+            }
+            if let Some(camera) = cameras.iter().find(|camera| camera.model.is_none()) {
+                panic!("{dbg}.new | 'fusion' requires 'extrinsics' & 'intrinsics' or 'calibration' of the camera '{}'", camera.title);
+            }
+            // The single camera fallback position must be written in the world units as the fused one
+            if let Some(camera) = cameras.iter().find(|camera| camera.world.is_none()) {
+                panic!("{dbg}.new | 'fusion' requires 'world' of the camera '{}'", camera.title);
+            }
+        }
         Self {
             name,
             wait_started,
-            image,
-            template_match,
-            camera
+            cameras,
+            fusion,
         }
     }
 }
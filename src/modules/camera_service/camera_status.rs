@@ -0,0 +1,69 @@
+use std::sync::{atomic::{AtomicU64, AtomicU8, Ordering}, Arc};
+
+///
+/// State of the camera connection
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CameraState {
+    /// Camera is being connected, no frames yet
+    Connecting,
+    /// Frames are coming from the camera
+    Streaming,
+    /// Camera is lost, waiting before the reconnection
+    Down,
+}
+//
+//
+impl CameraState {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => CameraState::Streaming,
+            2 => CameraState::Down,
+            _ => CameraState::Connecting,
+        }
+    }
+    fn as_u8(self) -> u8 {
+        match self {
+            CameraState::Connecting => 0,
+            CameraState::Streaming => 1,
+            CameraState::Down => 2,
+        }
+    }
+}
+///
+/// Shared state of the camera connection, published by the `CameraService` to the consumers
+/// 
+/// - Cloned instances share the same state
+#[derive(Debug, Clone, Default)]
+pub struct CameraStatus {
+    state: Arc<AtomicU8>,
+    reconnects: Arc<AtomicU64>,
+}
+//
+//
+impl CameraStatus {
+    ///
+    /// Returns [CameraStatus] new instance, [CameraState::Connecting]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    ///
+    /// Sets the current state of the camera
+    pub fn set(&self, state: CameraState) {
+        self.state.store(state.as_u8(), Ordering::Release);
+    }
+    ///
+    /// Returns the current state of the camera
+    pub fn state(&self) -> CameraState {
+        CameraState::from_u8(self.state.load(Ordering::Acquire))
+    }
+    ///
+    /// Records the reconnection of the camera
+    pub fn reconnect(&self) {
+        self.reconnects.fetch_add(1, Ordering::Relaxed);
+    }
+    ///
+    /// Returns the total number of the camera reconnections
+    pub fn reconnects(&self) -> u64 {
+        self.reconnects.load(Ordering::Relaxed)
+    }
+}
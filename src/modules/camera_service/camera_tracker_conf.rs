@@ -0,0 +1,147 @@
+use frdm_tools::camera::CameraConf;
+use sal_core::dbg::Dbg;
+use sal_sync::services::{conf::{ConfTree, ConfTreeGet}, entity::Name};
+
+use crate::modules::{Calibration, CameraId, CameraModelConf, ImageConf, PipelineConf, ReconnectConf, SwayConf, TemplateMatchConf, WorldConf};
+
+///
+/// ## The configuration parameters of the single camera of the `CameraService`
+/// 
+/// The `image`, `pipeline`, `template-match`, `reconnect` & `sway` sections are optional,
+/// the ones of the `CameraService` are used if omitted
+/// 
+/// ### Conf example
+/// ```yaml
+/// camera Camera1:
+///     id: 1                       # optional, tags the positions of the camera, default - index of the camera in the service
+///     fps: Max
+///     resolution: 
+///         width: 1200
+///         height: 800
+///     address: 192.168.10.12:2020
+///     calibration: calibration.yaml   # optional, saved by the `calibrate` command, the detected positions are undistorted, `image-size` must match the `resolution`
+///     intrinsics:                 # optional, required for the `fusion` & `world.pinhole` if no `calibration`
+///         ...
+///     extrinsics:                 # optional, position of the camera, required for the `fusion`
+///         ...
+///     world:                      # optional, pixel to world transform of the filtered position
+///         ...
+///     sway:                       # optional, sway analysis of the world position, `world` required
+///         ...
+///     template-match:             # optional, own template & matching of the camera
+///         template: assets/template_1.png
+///         ...
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CameraTrackerConf {
+    pub name: Name,
+    pub id: CameraId,
+    /// Title of the camera, `Camera1` of the `camera Camera1`
+    pub title: String,
+    pub camera: CameraConf,
+    pub image: ImageConf,
+    pub template_match: TemplateMatchConf,
+    pub reconnect: ReconnectConf,
+    /// Intrinsic calibration of the camera, the detected positions are undistorted by
+    pub calibration: Option<Calibration>,
+    /// Calibrated model of the camera, required for the fusion
+    pub model: Option<CameraModelConf>,
+    /// Pixel to world transform of the filtered position
+    pub world: Option<WorldConf>,
+    /// Sway analysis of the world position
+    pub sway: Option<SwayConf>,
+}
+//
+// 
+impl CameraTrackerConf {
+    ///
+    /// Returns [CameraTrackerConf] built from `ConfTree`:
+    /// - `index` - index of the camera in the service, used as default `id`
+    /// - `title` - title of the camera, may be empty
+    /// - `service` - conf of the `CameraService`, the sections omitted in the camera are taken from
+    pub fn new(
+        parent: impl Into<String>,
+        index: usize,
+        title: &str,
+        conf: ConfTree,
+        service: &ConfTree,
+    ) -> Self {
+        let parent = parent.into();
+        let me = "CameraTrackerConf";
+        let dbg = Dbg::new(&parent, me);
+        let name = Name::new(parent, me);
+        log::trace!("{dbg}.new | name: {:?}", name);
+        let id: Option<usize> = conf.get("id");
+        let id = CameraId(id.unwrap_or(index));
+        log::trace!("{}.new | id: {:?}", dbg, id);
+        let title = match title.is_empty() {
+            true => format!("Camera{}", *id),
+            false => title.to_owned(),
+        };
+        log::trace!("{}.new | title: {:?}", dbg, title);
+        let section = |key: &str| -> Option<ConfTree> {
+            let own: Option<ConfTree> = conf.get(key);
+            own.or_else(|| service.get(key))
+        };
+        let template_match = section("template-match").expect(&format!("{dbg}.new | 'template-match' - not found or wrong configuration"));
+        let template_match = TemplateMatchConf::new(&dbg, template_match);
+        log::trace!("{}.new | template-match: {:?}", dbg, template_match);
+        let image = section("image").expect(&format!("{dbg}.new | 'image' - not found or wrong configuration"));
+        let image = ImageConf::new(&dbg, image);
+        let image = match section("pipeline") {
+            Some(pipeline) => image.with_stages(&dbg, PipelineConf::new(&dbg, pipeline, template_match.matcher).stages),
+            None => image,
+        };
+        // The stages may come from the sections of the `image` as well
+        ImageConf::check_matcher(&dbg, &image.stages, template_match.matcher);
+        log::trace!("{}.new | image: {:?}", dbg, image);
+        let reconnect = match section("reconnect") {
+            Some(reconnect) => ReconnectConf::new(&dbg, reconnect),
+            None => ReconnectConf::default(),
+        };
+        log::trace!("{}.new | reconnect: {:?}", dbg, reconnect);
+        let calibration: Option<String> = conf.get("calibration");
+        let calibration = calibration.map(|path| {
+            let calibration = Calibration::load(&path).unwrap_or_else(|err| panic!("{dbg}.new | 'calibration' - can't be loaded: {err}"));
+            let resolution: Option<ConfTree> = conf.get("resolution");
+            if let Some(resolution) = resolution {
+                let width: i32 = resolution.get("width").expect(&format!("{dbg}.new | 'resolution.width' - not found or wrong configuration"));
+                let height: i32 = resolution.get("height").expect(&format!("{dbg}.new | 'resolution.height' - not found or wrong configuration"));
+                calibration.check_size(width, height).unwrap_or_else(|err| panic!("{dbg}.new | 'calibration' {:?} - doesn't match the camera 'resolution': {err}", path));
+            }
+            calibration
+        });
+        log::trace!("{}.new | calibration: {:?}", dbg, calibration);
+        let intrinsics = CameraModelConf::intrinsics(&dbg, conf.get("intrinsics"), calibration.as_ref());
+        log::trace!("{}.new | intrinsics: {:?}", dbg, intrinsics);
+        let extrinsics: Option<ConfTree> = conf.get("extrinsics");
+        let model = extrinsics.map(|extrinsics| {
+            let intrinsics = intrinsics.expect(&format!("{dbg}.new | 'intrinsics' or 'calibration' - not found or wrong configuration"));
+            CameraModelConf::new(&dbg, intrinsics, extrinsics)
+        });
+        log::trace!("{}.new | model: {:?}", dbg, model);
+        let world: Option<ConfTree> = conf.get("world");
+        let world = world.map(|world| WorldConf::new(&dbg, world, intrinsics));
+        log::trace!("{}.new | world: {:?}", dbg, world);
+        let sway = section("sway").map(|sway| SwayConf::new(&dbg, sway));
+        if sway.is_some() && world.is_none() {
+            panic!("{dbg}.new | 'sway' requires 'world' of the camera '{title}'");
+        }
+        log::trace!("{}.new | sway: {:?}", dbg, sway);
+        let camera = CameraConf::new(&name, &conf);
+        log::trace!("{dbg}.new | camera: {:#?}", camera);
+        Self {
+            name,
+            id,
+            title,
+            camera,
+            image,
+            template_match,
+            reconnect,
+            calibration,
+            model,
+            world,
+            sway,
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use opencv::core::{Mat, MatTraitConst, Rect};
+use sal_core::error::Error;
+use frdm_tools::{
+    ContextWrite, EvalResult,
+    ResultCtx, ContextRead,
+    Eval, Image,
+};
+///
+/// Takes source [Image]
+/// Return [Image] cropped to the configured rectangle,
+/// the rectangle is clamped to the frame
+pub struct Cropping {
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+    ctx: Box<dyn Eval<Image, EvalResult>>,
+}
+impl Cropping {
+    ///
+    /// Returns [Cropping] new instance
+    /// - `x`, `y` - new left & top edge, px
+    /// - `width`, `height` - new image size, px
+    pub fn new(x: i32, y: i32, width: i32, height: i32, ctx: impl Eval<Image, EvalResult> + 'static) -> Self {
+        Self { 
+            x,
+            y,
+            width,
+            height,
+            ctx: Box::new(ctx),
+        }
+    }
+}
+//
+//
+impl Eval<Image, EvalResult> for Cropping {
+    fn eval(&self, frame: Image) -> EvalResult {
+        let error = Error::new("Cropping", "eval");
+        match self.ctx.eval(frame) {
+            Ok(ctx) => {
+                let result: &ResultCtx = ctx.read();
+                let frame = &result.frame;
+                let (cols, rows) = (frame.mat.cols(), frame.mat.rows());
+                let x = self.x.clamp(0, cols);
+                let y = self.y.clamp(0, rows);
+                let rect = Rect::new(x, y, self.width.min(cols - x), self.height.min(rows - y));
+                if rect.width <= 0 || rect.height <= 0 {
+                    return Err(error.err(format!("Cropping {:?} is out of the frame {}x{}", rect, cols, rows)));
+                }
+                match Mat::roi(&frame.mat, rect).and_then(|roi| roi.try_clone()) {
+                    Ok(mat) => {
+                        let frame = Image {
+                            width: rect.width as _,
+                            height: rect.height as _,
+                            timestamp: frame.timestamp,
+                            mat,
+                            bytes: frame.bytes,
+                        };
+                        let result = ResultCtx { frame };
+                        ctx.write(result)
+                    }
+                    Err(err) => Err(error.pass(err.to_string())),
+                }
+            }
+            Err(err) => Err(error.pass(err)),
+        }
+    }
+}
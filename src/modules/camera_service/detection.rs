@@ -0,0 +1,29 @@
+use frdm_tools::{EvalResult, Image};
+use opencv::core::Rect;
+
+///
+/// The target found on the frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Detection {
+    /// Horizontal position of the target center on the frame, px
+    pub x: f64,
+    /// Vertical position of the target center on the frame, px
+    pub y: f64,
+    /// Scale of the template matched, 1.0 - native template size
+    pub scale: f64,
+    /// Rotation of the template matched, degrees, counter-clockwise
+    pub angle: f64,
+    /// Matching score, depends on the matching algorithm
+    pub score: f64,
+}
+///
+/// Searching the target on the frame
+/// 
+/// Implemented by the last stage of the `Eval` chain,
+/// which knows where the target is located on the frame
+pub trait Detect {
+    ///
+    /// Returns the evaluated context and the [Detection], if target is found
+    /// - `roi` - region of interest to be searched, None - full frame
+    fn detect(&self, frame: Image, roi: Option<Rect>) -> (EvalResult, Option<Detection>);
+}
@@ -0,0 +1,183 @@
+use sal_core::dbg::Dbg;
+use sal_sync::services::conf::{ConfTree, ConfTreeGet};
+
+use crate::modules::{Filter, FilterAverage, FilterAxis, FilterKalman, FilterLowPass, FilterMedian, FilterOutlier, FilterSmooth, KalmanConf, MaxSpeed, Numeric, Position};
+
+///
+/// Configuration parameters of the single stage of the position filters chain
+/// 
+/// Conf Example:
+/// ```yaml
+/// filters:
+///     - outlier:
+///         max-speed: 40           # maximum allowed jump of the position, px per `per`
+///         per: frame              # optional, frame (default) / second - frame timestamps are used
+///         relock: 3               # optional, new location accepted after this number of consistent jumps, default 3
+///     - median:
+///         window: 5               # number of the last positions the median is taken of
+///     - average:
+///         window: 3               # number of the last positions averaged
+///     - smooth:
+///         factor: 4               # the lower the setting, the lower the smoothing, 1 - no smoothing, recomended max: 256
+///         axis: x                 # optional, x / y - filter only single axis, default - both axes
+///         sample-time: 0.04       # optional, nominal time between the frames, seconds, if set - real time between the frames is used
+///     - low-pass:
+///         cutoff: 2.0             # cutoff frequency, Hz
+///         sample-time: 0.04       # time between the frames, seconds
+///     - kalman:
+///         model: velocity         # optional, velocity (default) / acceleration
+///         process-noise: 0.5
+///         measurement-noise: 4.0
+///         max-predictions: 5      # optional, default 5
+///         sample-time: 0.04       # optional, nominal time between the frames, seconds, if set - real time between the frames is used
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilterConf {
+    /// Filtered axis, 0 - x, 1 - y, None - both axes
+    pub axis: Option<usize>,
+    pub kind: FilterKind,
+}
+///
+/// Filter algorithm and its parameters
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterKind {
+    /// [FilterOutlier](crate::modules::FilterOutlier)
+    Outlier {
+        max_speed: MaxSpeed,
+        relock: usize,
+    },
+    /// [FilterMedian](crate::modules::FilterMedian)
+    Median {
+        window: usize,
+    },
+    /// [FilterAverage](crate::modules::FilterAverage)
+    Average {
+        window: usize,
+    },
+    /// [FilterSmooth](crate::modules::FilterSmooth)
+    Smooth {
+        factor: f64,
+        sample_time: Option<f64>,
+    },
+    /// [FilterLowPass](crate::modules::FilterLowPass)
+    LowPass {
+        cutoff: f64,
+        sample_time: f64,
+    },
+    /// [FilterKalman](crate::modules::FilterKalman)
+    Kalman(KalmanConf),
+}
+//
+//
+impl FilterConf {
+    ///
+    /// Returns the list of [FilterConf] built from the `filters` sequence
+    pub fn list(parent: impl Into<String>, conf: ConfTree) -> Vec<Self> {
+        let dbg = Dbg::new(parent, "FilterConf");
+        let stages = conf.conf.as_sequence().expect(&format!("{dbg}.list | 'filters' must be a list"));
+        stages.iter().enumerate().map(|(i, stage)| {
+            let (kind, params) = stage.as_mapping()
+                .filter(|stage| stage.len() == 1)
+                .and_then(|stage| stage.iter().next())
+                .expect(&format!("{dbg}.list | 'filters[{i}]' must contain single filter"));
+            let kind = kind.as_str().expect(&format!("{dbg}.list | 'filters[{i}]' wrong filter name: {:?}", kind));
+            Self::new(&dbg, kind, ConfTree::new_root(params.clone()))
+        }).collect()
+    }
+    ///
+    /// Returns [FilterConf] of the filter `kind` built from `ConfTree`:
+    pub fn new(parent: impl Into<String>, kind: &str, conf: ConfTree) -> Self {
+        let dbg = Dbg::new(parent, format!("FilterConf({kind})"));
+        let axis: Option<String> = conf.get("axis");
+        let axis = axis.map(|axis| match axis.to_lowercase().as_str() {
+            "x" => 0,
+            "y" => 1,
+            _ => panic!("{dbg}.new | Unknown 'axis' {axis}, expected x or y"),
+        });
+        log::trace!("{}.new | axis: {:?}", dbg, axis);
+        let kind = match kind.to_lowercase().as_str() {
+            "smooth" => {
+                let factor: f64 = conf.get("factor").expect(&format!("{dbg}.new | 'factor' - not found or wrong configuration"));
+                if factor < 1.0 {
+                    panic!("{dbg}.new | 'factor' must be 1.0 or more, found: {factor}");
+                }
+                let sample_time: Option<f64> = conf.get("sample-time");
+                if let Some(sample_time) = sample_time {
+                    if sample_time <= 0.0 {
+                        panic!("{dbg}.new | 'sample-time' must be positive, found: {sample_time}");
+                    }
+                }
+                FilterKind::Smooth { factor, sample_time }
+            }
+            "outlier" => {
+                let max_speed: f64 = conf.get("max-speed").expect(&format!("{dbg}.new | 'max-speed' - not found or wrong configuration"));
+                if max_speed <= 0.0 {
+                    panic!("{dbg}.new | 'max-speed' must be positive, found: {max_speed}");
+                }
+                let per: Option<String> = conf.get("per");
+                let max_speed = match per.as_deref().map(str::to_lowercase).as_deref() {
+                    None | Some("frame") => MaxSpeed::PerFrame(max_speed),
+                    Some("second") => MaxSpeed::PerSecond(max_speed),
+                    Some(per) => panic!("{dbg}.new | Unknown 'per' {per}, expected frame or second"),
+                };
+                let relock: usize = conf.get("relock").unwrap_or(3);
+                FilterKind::Outlier { max_speed, relock }
+            }
+            "median" => FilterKind::Median { window: Self::window(&dbg, &conf) },
+            "average" => FilterKind::Average { window: Self::window(&dbg, &conf) },
+            "low-pass" => {
+                let cutoff: f64 = conf.get("cutoff").expect(&format!("{dbg}.new | 'cutoff' - not found or wrong configuration"));
+                let sample_time: f64 = conf.get("sample-time").expect(&format!("{dbg}.new | 'sample-time' - not found or wrong configuration"));
+                if cutoff <= 0.0 || sample_time <= 0.0 {
+                    panic!("{dbg}.new | 'cutoff' and 'sample-time' must be positive, found: {cutoff}, {sample_time}");
+                }
+                FilterKind::LowPass { cutoff, sample_time }
+            }
+            "kalman" => FilterKind::Kalman(KalmanConf::new(&dbg, conf)),
+            _ => panic!("{dbg}.new | Unknown filter '{kind}'"),
+        };
+        log::trace!("{}.new | kind: {:?}", dbg, kind);
+        Self { axis, kind }
+    }
+    ///
+    /// Returns the `window` of the median & average filters
+    fn window(dbg: &Dbg, conf: &ConfTree) -> usize {
+        let window: usize = conf.get("window").expect(&format!("{dbg}.new | 'window' - not found or wrong configuration"));
+        if window == 0 {
+            panic!("{dbg}.new | 'window' must be 1 or more, found: {window}");
+        }
+        window
+    }
+    ///
+    /// Returns the position filter, applied to the both axes or to the single one
+    pub fn filter(&self) -> Box<dyn Filter<Item = Position<f64>>> {
+        match self.axis {
+            Some(axis) => Box::new(FilterAxis::new(axis, Self::filter_of(&self.kind))),
+            None => Self::filter_of(&self.kind),
+        }
+    }
+    ///
+    /// Returns the filter of the `kind`
+    fn filter_of<T: Numeric + 'static>(kind: &FilterKind) -> Box<dyn Filter<Item = T>> {
+        match kind {
+            FilterKind::Outlier { max_speed, relock } => Box::new(FilterOutlier::new(*max_speed, *relock)),
+            FilterKind::Median { window } => Box::new(FilterMedian::new(*window, None)),
+            FilterKind::Average { window } => Box::new(FilterAverage::new(*window, None)),
+            FilterKind::Smooth { factor, sample_time } => {
+                let filter = FilterSmooth::new(None, *factor);
+                match sample_time {
+                    Some(sample_time) => Box::new(filter.with_sample_time(*sample_time)),
+                    None => Box::new(filter),
+                }
+            }
+            FilterKind::LowPass { cutoff, sample_time } => Box::new(FilterLowPass::new(None, *cutoff, *sample_time)),
+            FilterKind::Kalman(kalman) => {
+                let filter = FilterKalman::new(kalman.model, kalman.process_noise, kalman.measurement_noise, kalman.max_predictions);
+                match kalman.sample_time {
+                    Some(sample_time) => Box::new(filter.with_sample_time(sample_time)),
+                    None => Box::new(filter),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use opencv::core::{Mat, Size};
+use sal_core::error::Error;
+use frdm_tools::{
+    ContextWrite, EvalResult,
+    ResultCtx, ContextRead,
+    Eval, Image,
+};
+///
+/// Takes source [Image]
+/// Return [Image] blured by the Gaussian filter
+pub struct Gausian {
+    blur_size: Size,
+    sigma_x: f64,
+    sigma_y: f64,
+    ctx: Box<dyn Eval<Image, EvalResult>>,
+}
+impl Gausian {
+    ///
+    /// Returns [Gausian] new instance
+    /// - `blur_size` - kernel size, width & height must be odd
+    /// - `sigma_x`, `sigma_y` - kernel standard deviation, 0.0 - calculated from the kernel size
+    pub fn new(blur_size: Size, sigma_x: f64, sigma_y: f64, ctx: impl Eval<Image, EvalResult> + 'static) -> Self {
+        Self { 
+            blur_size,
+            sigma_x,
+            sigma_y,
+            ctx: Box::new(ctx),
+        }
+    }
+}
+//
+//
+impl Eval<Image, EvalResult> for Gausian {
+    fn eval(&self, frame: Image) -> EvalResult {
+        let error = Error::new("Gausian", "eval");
+        match self.ctx.eval(frame) {
+            Ok(ctx) => {
+                let result: &ResultCtx = ctx.read();
+                let frame = &result.frame;
+                let mut blured = Mat::default();
+                match opencv::imgproc::gaussian_blur(&frame.mat, &mut blured, self.blur_size, self.sigma_x, self.sigma_y, opencv::core::BORDER_DEFAULT) {
+                    Ok(_) => {
+                        let frame = Image {
+                            width: frame.width,
+                            height: frame.height,
+                            timestamp: frame.timestamp,
+                            mat: blured,
+                            bytes: frame.bytes,
+                        };
+                        let result = ResultCtx { frame };
+                        ctx.write(result)
+                    }
+                    Err(err) => Err(error.pass(err.to_string())),
+                }
+            }
+            Err(err) => Err(error.pass(err)),
+        }
+    }
+}
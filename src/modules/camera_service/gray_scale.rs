@@ -1,4 +1,3 @@
-use std::time::Instant;
 use sal_core::error::Error;
 use frdm_tools::{
     ContextWrite, EvalResult,
@@ -29,7 +28,6 @@ impl Eval<Image, EvalResult> for GrayScale {
             Ok(ctx) => {
                 // build a lookup table mapping the pixel values [0, 255] to
                 // their adjusted gamma values
-                let t = Instant::now();
                 let result: &ResultCtx = ctx.read();
                 let frame = &result.frame;
                 let mut gray = opencv::core::Mat::default();
@@ -43,7 +41,6 @@ impl Eval<Image, EvalResult> for GrayScale {
                             bytes: frame.bytes,
                         };
                         let result = ResultCtx { frame };
-                        log::debug!("GrayScale.eval | Elapsed: {:?}", t.elapsed());
                         ctx.write(result)
                     }
                     Err(err) => Err(error.pass(err.to_string())),
@@ -2,16 +2,16 @@ use sal_core::dbg::Dbg;
 use sal_sync::services::{conf::{ConfTree, ConfTreeGet}, entity::Name};
 use frdm_tools::{conf::{BrightnessContrastConf, GammaConf, GausianConf, OverlayConf, SobelConf}, CroppingConf};
 
+use crate::modules::Matcher;
+
 ///
 /// ## Configuration for `Contour dectection` algorithm
 /// 
+/// Each section is optional, the stages are applied to the frame in the order of the sections,
+/// or in the order of the `pipeline` list, if configured
+/// 
 /// ### Example:
 /// ```yaml
-/// cropping:
-///     x: 10           # new left edge
-///     width: 1900     # new image width
-///     y: 10           # new top edge
-///     height: 1180    # new image height
 /// gamma:
 ///     factor: 95.0             # percent of influence of [AutoGamma] algorythm bigger the value more the effect of [AutoGamma] algorythm, %
 /// brightness-contrast:
@@ -23,29 +23,36 @@ use frdm_tools::{conf::{BrightnessContrastConf, GammaConf, GausianConf, OverlayC
 ///         height: 3
 ///     sigma-x: 0.0
 ///     sigma-y: 0.0
-/// sobel:
+/// sobel:                      # the frame is single channel after it, not used with the `TemplateMatch`
 ///     kernel-size: 3
 ///     scale: 1.0
 ///     delta: 0.0
-/// overlay:
+/// overlay:                    # weighted sum of the source frame & the frame processed by the stages above, must precede cropping
 ///     src1-weight: 0.5
 ///     src2-weight: 0.5
 ///     gamma: 0.0
+/// cropping:           # detected position remains relative to the source frame
+///     x: 10           # new left edge
+///     width: 1900     # new image width
+///     y: 10           # new top edge
+///     height: 1180    # new image height
 /// ```
 #[derive(Debug, Clone, PartialEq)]
 pub struct ImageConf {
     /// Configuration for `Cropping` operator
-    pub cropping: CroppingConf,
+    pub cropping: Option<CroppingConf>,
     /// Configuration for `Gamma auto correction` algorithm
-    pub gamma: GammaConf,
+    pub gamma: Option<GammaConf>,
     /// Configuration for `Brightness and contrast auto correction`
-    pub brightness_contrast: BrightnessContrastConf,
+    pub brightness_contrast: Option<BrightnessContrastConf>,
     /// Configuration for `Gaussian filter`
-    pub gausian: GausianConf,
+    pub gausian: Option<GausianConf>,
     /// Configuration for `Sobel operator`
-    pub sobel: SobelConf,
+    pub sobel: Option<SobelConf>,
     /// Configuration for `Weighted sum`
-    pub overlay: OverlayConf,
+    pub overlay: Option<OverlayConf>,
+    /// Configured stages in the order of applying
+    pub stages: Vec<ImageStage>,
 }
 //
 // 
@@ -59,24 +66,20 @@ impl ImageConf {
         log::trace!("{}.new | conf: {:?}", dbg, conf);
         let name = Name::new(parent, me);
         log::trace!("{}.new | name: {:?}", dbg, name);
-        let cropping = conf.get("cropping").expect(&format!("{dbg}.new | 'cropping' - not found or wrong configuration"));
-        let cropping = CroppingConf::new(&name, cropping);
+        let cropping = conf.get("cropping").map(|cropping| CroppingConf::new(&name, cropping));
         log::trace!("{dbg}.new | cropping: {:#?}", cropping);
-        let gamma = conf.get("gamma").expect(&format!("{dbg}.new | 'gamma' - not found or wrong configuration"));
-        let gamma = GammaConf::new(&name, gamma);
+        let gamma = conf.get("gamma").map(|gamma| GammaConf::new(&name, gamma));
         log::trace!("{dbg}.new | gamma: {:#?}", gamma);
-        let brightness_contrast = conf.get("brightness-contrast").expect(&format!("{dbg}.new | 'brightness-contrast' - not found or wrong configuration"));
-        let brightness_contrast = BrightnessContrastConf::new(&name, brightness_contrast);
+        let brightness_contrast = conf.get("brightness-contrast").map(|brightness_contrast| BrightnessContrastConf::new(&name, brightness_contrast));
         log::trace!("{dbg}.new | brightness-contrast: {:#?}", brightness_contrast);
-        let gausian = conf.get("gausian").expect(&format!("{dbg}.new | 'gausian' - not found or wrong configuration"));
-        let gausian = GausianConf::new(&name, gausian);
+        let gausian = conf.get("gausian").map(|gausian| GausianConf::new(&name, gausian));
         log::trace!("{dbg}.new | gausian: {:#?}", gausian);
-        let sobel = conf.get("sobel").expect(&format!("{dbg}.new | 'sobel' - not found or wrong configuration"));
-        let sobel = SobelConf::new(&name, sobel);
+        let sobel = conf.get("sobel").map(|sobel| SobelConf::new(&name, sobel));
         log::trace!("{dbg}.new | sobel: {:#?}", sobel);
-        let overlay = conf.get("overlay").expect(&format!("{dbg}.new | 'overlay' - not found or wrong configuration"));
-        let overlay = OverlayConf::new(&name, overlay);
+        let overlay = conf.get("overlay").map(|overlay| OverlayConf::new(&name, overlay));
         log::trace!("{dbg}.new | overlay: {:#?}", overlay);
+        let stages = Self::stages(&dbg, &conf);
+        log::trace!("{dbg}.new | stages: {:?}", stages);
         Self {
             cropping,
             gamma,
@@ -84,6 +87,96 @@ impl ImageConf {
             gausian,
             sobel,
             overlay,
+            stages,
+        }
+    }
+    ///
+    /// Returns the configured stages in the order of the sections
+    fn stages(dbg: &Dbg, conf: &ConfTree) -> Vec<ImageStage> {
+        let keys = conf.conf.as_mapping().map(|mapping| {
+            mapping.keys().filter_map(|key| key.as_str()).collect::<Vec<_>>()
+        }).unwrap_or_default();
+        let stages: Vec<ImageStage> = keys.into_iter().map(|key| match key {
+            "cropping" => ImageStage::Cropping,
+            "gamma" => ImageStage::Gamma,
+            "brightness-contrast" => ImageStage::BrightnessContrast,
+            "gausian" => ImageStage::Gausian,
+            "sobel" => ImageStage::Sobel,
+            "overlay" => ImageStage::Overlay,
+            _ => panic!("{dbg}.stages | Unknown stage '{key}'"),
+        }).collect();
+        Self::check(dbg, &stages);
+        stages
+    }
+    ///
+    /// Returns [ImageConf] with the `stages` applied instead of configured by the order of the sections,
+    /// each stage must be configured
+    pub fn with_stages(self, parent: impl Into<String>, stages: Vec<ImageStage>) -> Self {
+        let dbg = Dbg::new(parent, "ImageConf");
+        for stage in &stages {
+            let configured = match stage {
+                ImageStage::Cropping => self.cropping.is_some(),
+                ImageStage::GrayScale => true,
+                ImageStage::Gamma => self.gamma.is_some(),
+                ImageStage::BrightnessContrast => self.brightness_contrast.is_some(),
+                ImageStage::Gausian => self.gausian.is_some(),
+                ImageStage::Sobel => self.sobel.is_some(),
+                ImageStage::Overlay => self.overlay.is_some(),
+            };
+            if !configured {
+                panic!("{dbg}.with_stages | Stage {:?} is not configured in the 'image' section", stage);
+            }
+        }
+        Self::check(&dbg, &stages);
+        Self { stages, ..self }
+    }
+    ///
+    /// Checks the frame after the `stages` can be searched by the `matcher`,
+    /// the frame is single channel after `gray-scale` & `sobel`, the template of the `TemplateMatch` is colour
+    pub fn check_matcher(dbg: &Dbg, stages: &[ImageStage], matcher: Matcher) {
+        if matcher == Matcher::TemplateMatch {
+            if let Some(stage) = stages.iter().find(|stage| matches!(stage, ImageStage::GrayScale | ImageStage::Sobel)) {
+                panic!("{dbg}.new | '{}' can't be used with 'TemplateMatch', the frame is single channel after it, the template is colour", stage.name());
+            }
+        }
+    }
+    ///
+    /// Checks the order of the `stages`
+    fn check(dbg: &Dbg, stages: &[ImageStage]) {
+        let position = |stage| stages.iter().position(|s| *s == stage);
+        if let (Some(cropping), Some(overlay)) = (position(ImageStage::Cropping), position(ImageStage::Overlay)) {
+            if cropping < overlay {
+                panic!("{dbg}.check | 'overlay' must precede 'cropping', overlay needs the source frame size");
+            }
+        }
+    }
+}
+///
+/// Preprocessing stage of the frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImageStage {
+    Cropping,
+    GrayScale,
+    Gamma,
+    BrightnessContrast,
+    Gausian,
+    Sobel,
+    Overlay,
+}
+//
+//
+impl ImageStage {
+    ///
+    /// Returns the name of the stage
+    pub fn name(&self) -> &'static str {
+        match self {
+            ImageStage::Cropping => "crop",
+            ImageStage::GrayScale => "gray-scale",
+            ImageStage::Gamma => "gamma",
+            ImageStage::BrightnessContrast => "brightness-contrast",
+            ImageStage::Gausian => "blur",
+            ImageStage::Sobel => "sobel",
+            ImageStage::Overlay => "overlay",
         }
     }
 }
@@ -92,12 +185,13 @@ impl ImageConf {
 impl Default for ImageConf {
     fn default() -> Self {
         Self {
-            cropping: CroppingConf::default(),
-            gamma: GammaConf::default(),
-            brightness_contrast: BrightnessContrastConf::default(),
-            gausian: GausianConf::default(),
-            sobel: SobelConf::default(),
-            overlay: OverlayConf::default(),
+            cropping: None,
+            gamma: Some(GammaConf::default()),
+            brightness_contrast: Some(BrightnessContrastConf::default()),
+            gausian: None,
+            sobel: None,
+            overlay: None,
+            stages: vec![ImageStage::Gamma, ImageStage::BrightnessContrast],
         }
     }
 }
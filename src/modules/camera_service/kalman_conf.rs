@@ -0,0 +1,67 @@
+use sal_core::{dbg::Dbg, error::Error};
+use sal_sync::services::conf::{ConfTree, ConfTreeGet};
+
+use crate::modules::KalmanModel;
+
+///
+/// Configuration parameters of the Kalman filter of the target position
+/// 
+/// Conf Example:
+/// ```yaml
+/// kalman:
+///     model: velocity             # optional, velocity (default) - constant velocity / acceleration - constant acceleration
+///     process-noise: 0.5          # variance of the unmodeled acceleration (jerk for `acceleration` model) per frame
+///     measurement-noise: 4.0      # variance of the detected position, px^2
+///     max-predictions: 5          # optional, position predicted through this number of missed frames, default 5
+///     sample-time: 0.04           # optional, nominal time between the frames, seconds, the noises are tuned for,
+///                                 # if set - real time between the frames is used
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct KalmanConf {
+    pub model: KalmanModel,
+    pub process_noise: f64,
+    pub measurement_noise: f64,
+    pub max_predictions: usize,
+    pub sample_time: Option<f64>,
+}
+//
+//
+impl KalmanConf {
+    ///
+    /// Returns [KalmanConf] built from `ConfTree`:
+    pub fn new(parent: impl Into<String>, conf: ConfTree) -> Self {
+        let dbg = Dbg::new(parent, "KalmanConf");
+        let model: Option<String> = conf.get("model");
+        let model = match model {
+            Some(model) => Self::model_from_str(&model).expect(&format!("{dbg}.new | Unknown 'model' {model}")),
+            None => KalmanModel::Velocity,
+        };
+        log::trace!("{}.new | model: {:?}", dbg, model);
+        let process_noise: f64 = conf.get("process-noise").expect(&format!("{dbg}.new | 'process-noise' - not found or wrong configuration"));
+        log::trace!("{}.new | process-noise: {:?}", dbg, process_noise);
+        let measurement_noise: f64 = conf.get("measurement-noise").expect(&format!("{dbg}.new | 'measurement-noise' - not found or wrong configuration"));
+        log::trace!("{}.new | measurement-noise: {:?}", dbg, measurement_noise);
+        let max_predictions: usize = conf.get("max-predictions").unwrap_or(5);
+        log::trace!("{}.new | max-predictions: {:?}", dbg, max_predictions);
+        let sample_time: Option<f64> = conf.get("sample-time");
+        log::trace!("{}.new | sample-time: {:?}", dbg, sample_time);
+        if let Some(sample_time) = sample_time {
+            if sample_time <= 0.0 {
+                panic!("{dbg}.new | 'sample-time' must be positive, found: {sample_time}");
+            }
+        }
+        if process_noise <= 0.0 || measurement_noise <= 0.0 {
+            panic!("{dbg}.new | 'process-noise' and 'measurement-noise' must be positive, found: {process_noise}, {measurement_noise}");
+        }
+        Self { model, process_noise, measurement_noise, max_predictions, sample_time }
+    }
+    ///
+    /// Returns [KalmanModel] parsed from string
+    fn model_from_str(model: &str) -> Result<KalmanModel, Error> {
+        match model.to_lowercase().as_str() {
+            "velocity" => Ok(KalmanModel::Velocity),
+            "acceleration" => Ok(KalmanModel::Acceleration),
+            _ => Err(Error::new("KalmanConf", "model_from_str").err(format!("Unknown model {}", model))),
+        }
+    }
+}
@@ -36,15 +36,45 @@
 mod bf_match;
 mod camera_service_conf;
 mod camera_service;
+mod camera_status;
+mod camera_tracker_conf;
+mod cropping;
+mod detection;
+mod filter_conf;
+mod gausian;
 mod gray_scale;
 mod image_conf;
+mod kalman_conf;
+mod overlay;
+mod pipeline_conf;
+mod preprocessing;
+mod reconnect_conf;
+mod sobel;
 mod template_match_conf;
+mod target;
 mod template_match;
+mod tracking_conf;
+mod tracking;
 
 pub(crate) use bf_match::*;
 pub(crate) use camera_service_conf::*;
 pub(crate) use camera_service::*;
+pub(crate) use camera_status::*;
+pub(crate) use camera_tracker_conf::*;
+pub(crate) use cropping::*;
+pub(crate) use detection::*;
+pub(crate) use filter_conf::*;
+pub(crate) use gausian::*;
 pub(crate) use gray_scale::*;
 pub(crate) use image_conf::*;
+pub(crate) use kalman_conf::*;
+pub(crate) use overlay::*;
+pub(crate) use pipeline_conf::*;
+pub(crate) use preprocessing::*;
+pub(crate) use reconnect_conf::*;
+pub(crate) use sobel::*;
 pub(crate) use template_match_conf::*;
+pub(crate) use target::*;
 pub(crate) use template_match::*;
+pub(crate) use tracking_conf::*;
+pub(crate) use tracking::*;
@@ -0,0 +1,85 @@
+use opencv::core::{Mat, MatTraitConst};
+use sal_core::error::Error;
+use frdm_tools::{
+    ContextWrite, EvalResult,
+    ResultCtx, ContextRead,
+    Eval, Image,
+};
+///
+/// Takes source [Image]
+/// Return the weighted sum of the source [Image] (src1) and the processed one (src2):
+/// 
+/// dst = src1 * src1_weight + src2 * src2_weight + gamma
+/// 
+/// Frame size must be kept by the previous stages, so cropping can't precede the overlay
+pub struct Overlay {
+    src1_weight: f64,
+    src2_weight: f64,
+    gamma: f64,
+    ctx: Box<dyn Eval<Image, EvalResult>>,
+}
+impl Overlay {
+    ///
+    /// Returns [Overlay] new instance
+    pub fn new(src1_weight: f64, src2_weight: f64, gamma: f64, ctx: impl Eval<Image, EvalResult> + 'static) -> Self {
+        Self { 
+            src1_weight,
+            src2_weight,
+            gamma,
+            ctx: Box::new(ctx),
+        }
+    }
+    ///
+    /// Returns the weighted sum of the `src1` and `src2`,
+    /// `src2` is converted to the number of channels of the `src1`
+    fn overlay(&self, src1: &Mat, src2: &Mat) -> Result<Mat, opencv::Error> {
+        let src2 = match (src1.channels(), src2.channels()) {
+            (3, 1) => {
+                let mut bgr = Mat::default();
+                opencv::imgproc::cvt_color(src2, &mut bgr, opencv::imgproc::COLOR_GRAY2BGR, 0)?;
+                bgr
+            }
+            (1, 3) => {
+                let mut gray = Mat::default();
+                opencv::imgproc::cvt_color(src2, &mut gray, opencv::imgproc::COLOR_BGR2GRAY, 0)?;
+                gray
+            }
+            _ => src2.clone(),
+        };
+        let mut dst = Mat::default();
+        opencv::core::add_weighted(src1, self.src1_weight, &src2, self.src2_weight, self.gamma, &mut dst, src1.depth()?)?;
+        Ok(dst)
+    }
+}
+//
+//
+impl Eval<Image, EvalResult> for Overlay {
+    fn eval(&self, frame: Image) -> EvalResult {
+        let error = Error::new("Overlay", "eval");
+        let src = frame.mat.clone();
+        match self.ctx.eval(frame) {
+            Ok(ctx) => {
+                let result: &ResultCtx = ctx.read();
+                let frame = &result.frame;
+                if src.size().ok() != frame.mat.size().ok() {
+                    return Err(error.err(format!("Source frame size {:?} differs from the processed one {:?}", src.size(), frame.mat.size())));
+                }
+                match self.overlay(&src, &frame.mat) {
+                    Ok(mat) => {
+                        let frame = Image {
+                            width: frame.width,
+                            height: frame.height,
+                            timestamp: frame.timestamp,
+                            mat,
+                            bytes: frame.bytes,
+                        };
+                        let result = ResultCtx { frame };
+                        ctx.write(result)
+                    }
+                    Err(err) => Err(error.pass(err.to_string())),
+                }
+            }
+            Err(err) => Err(error.pass(err)),
+        }
+    }
+}
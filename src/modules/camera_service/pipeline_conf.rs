@@ -0,0 +1,81 @@
+use sal_core::dbg::Dbg;
+use sal_sync::services::conf::ConfTree;
+
+use crate::modules::{ImageConf, ImageStage, Matcher};
+
+///
+/// Ordered list of the frame processing stages
+/// 
+/// The parameters of the stages are taken from the `image` & `template-match` sections,
+/// so the same stage can't be listed twice
+/// 
+/// The frame is single channel after `gray-scale` & `sobel`, so `gray-scale` can't follow `sobel`
+/// and both can't be used with the `TemplateMatch` matcher, the template is matched in colour
+/// 
+/// Conf Example:
+/// ```yaml
+/// pipeline:
+///     - initial               # optional, source frame, must be first
+///     - crop                  # `image.cropping`
+///     - gray-scale            # must precede `sobel`, not used with `TemplateMatch`
+///     - gamma                 # `image.gamma`
+///     - brightness-contrast   # `image.brightness-contrast`
+///     - blur                  # `image.gausian`
+///     - sobel                 # `image.sobel`, not used with `TemplateMatch`
+///     - overlay               # `image.overlay`
+///     - matcher               # optional, `template-match`, must be last
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PipelineConf {
+    /// Preprocessing stages between `initial` & `matcher`, in the order of applying
+    pub stages: Vec<ImageStage>,
+}
+//
+//
+impl PipelineConf {
+    ///
+    /// Returns [PipelineConf] built from the `pipeline` sequence
+    /// - `matcher` - the preprocessed frame is searched by
+    pub fn new(parent: impl Into<String>, conf: ConfTree, matcher: Matcher) -> Self {
+        let dbg = Dbg::new(parent, "PipelineConf");
+        let names = conf.conf.as_sequence().expect(&format!("{dbg}.new | 'pipeline' must be a list"));
+        let names: Vec<&str> = names.iter().enumerate().map(|(i, name)| {
+            name.as_str().expect(&format!("{dbg}.new | 'pipeline[{i}]' wrong stage name: {:?}", name))
+        }).collect();
+        let last = names.len().saturating_sub(1);
+        let mut stages = vec![];
+        for (i, name) in names.into_iter().enumerate() {
+            let stage = match name.to_lowercase().as_str() {
+                "initial" if i == 0 => continue,
+                "matcher" if i == last => continue,
+                "initial" | "matcher" => panic!("{dbg}.new | '{name}' must be the {} stage", if name == "initial" { "first" } else { "last" }),
+                "crop" | "cropping" => ImageStage::Cropping,
+                "gray-scale" => ImageStage::GrayScale,
+                "gamma" => ImageStage::Gamma,
+                "brightness-contrast" => ImageStage::BrightnessContrast,
+                "blur" | "gausian" => ImageStage::Gausian,
+                "sobel" => ImageStage::Sobel,
+                "overlay" => ImageStage::Overlay,
+                _ => panic!("{dbg}.new | Unknown stage '{name}'"),
+            };
+            if stages.contains(&stage) {
+                panic!("{dbg}.new | Stage '{name}' is listed twice");
+            }
+            stages.push(stage);
+        }
+        Self::check(&dbg, &stages, matcher);
+        log::trace!("{}.new | stages: {:?}", dbg, stages);
+        Self { stages }
+    }
+    ///
+    /// Checks the `stages` produce the frame the `matcher` can search
+    fn check(dbg: &Dbg, stages: &[ImageStage], matcher: Matcher) {
+        let position = |stage| stages.iter().position(|s| *s == stage);
+        if let (Some(sobel), Some(gray_scale)) = (position(ImageStage::Sobel), position(ImageStage::GrayScale)) {
+            if sobel < gray_scale {
+                panic!("{dbg}.new | 'gray-scale' must precede 'sobel', the frame is single channel after 'sobel'");
+            }
+        }
+        ImageConf::check_matcher(dbg, stages, matcher);
+    }
+}
@@ -0,0 +1,68 @@
+use frdm_tools::{AutoBrightnessAndContrast, AutoGamma, Eval, EvalResult, Image, Initial, InitialCtx};
+use opencv::core::Size;
+
+use crate::modules::{Cropping, Diagnostics, Gausian, GrayScale, ImageConf, ImageStage, Overlay, Sobel, Timed};
+
+///
+/// Preprocessing `Eval` chain of the frame
+///
+/// Built from the [ImageConf], contains only the configured stages, in the configured order,
+/// so the stages can be reordered or disabled without recompilation
+///
+/// Each stage is recorded into the [Diagnostics]
+pub struct Preprocessing {
+    ctx: Box<dyn Eval<Image, EvalResult>>,
+}
+//
+//
+impl Preprocessing {
+    ///
+    /// Returns [Preprocessing] new instance
+    pub fn new(conf: &ImageConf, diagnostics: &Diagnostics) -> Self {
+        let initial = Self { ctx: Box::new(Timed::new("initial", diagnostics.clone(), Initial::new(InitialCtx::new()))) };
+        conf.stages.iter().fold(initial, |ctx, stage| {
+            let ctx = Self::stage(conf, *stage, ctx);
+            Self { ctx: Box::new(Timed::new(stage.name(), diagnostics.clone(), ctx)) }
+        })
+    }
+    ///
+    /// Returns the `stage` applied to the result of the `ctx`
+    fn stage(conf: &ImageConf, stage: ImageStage, ctx: Self) -> Self {
+        let ctx: Box<dyn Eval<Image, EvalResult>> = match stage {
+            ImageStage::Cropping => {
+                let cropping = conf.cropping.as_ref().expect("Preprocessing.stage | 'cropping' - not configured");
+                Box::new(Cropping::new(cropping.x as i32, cropping.y as i32, cropping.width as i32, cropping.height as i32, ctx))
+            }
+            ImageStage::GrayScale => Box::new(GrayScale::new(ctx)),
+            ImageStage::Gamma => {
+                let gamma = conf.gamma.as_ref().expect("Preprocessing.stage | 'gamma' - not configured");
+                Box::new(AutoGamma::new(gamma.factor, ctx))
+            }
+            ImageStage::BrightnessContrast => {
+                let brightness_contrast = conf.brightness_contrast.as_ref().expect("Preprocessing.stage | 'brightness-contrast' - not configured");
+                Box::new(AutoBrightnessAndContrast::new(brightness_contrast.hist_clip_left, brightness_contrast.hist_clip_right, ctx))
+            }
+            ImageStage::Gausian => {
+                let gausian = conf.gausian.as_ref().expect("Preprocessing.stage | 'gausian' - not configured");
+                let blur_size = Size::new(gausian.blur_size.width as i32, gausian.blur_size.height as i32);
+                Box::new(Gausian::new(blur_size, gausian.sigma_x, gausian.sigma_y, ctx))
+            }
+            ImageStage::Sobel => {
+                let sobel = conf.sobel.as_ref().expect("Preprocessing.stage | 'sobel' - not configured");
+                Box::new(Sobel::new(sobel.kernel_size as i32, sobel.scale, sobel.delta, ctx))
+            }
+            ImageStage::Overlay => {
+                let overlay = conf.overlay.as_ref().expect("Preprocessing.stage | 'overlay' - not configured");
+                Box::new(Overlay::new(overlay.src1_weight, overlay.src2_weight, overlay.gamma, ctx))
+            }
+        };
+        Self { ctx }
+    }
+}
+//
+//
+impl Eval<Image, EvalResult> for Preprocessing {
+    fn eval(&self, frame: Image) -> EvalResult {
+        self.ctx.eval(frame)
+    }
+}
@@ -0,0 +1,71 @@
+use std::time::Duration;
+use sal_core::dbg::Dbg;
+use sal_sync::services::{conf::ConfTree, entity::Name};
+
+use crate::modules::backoff;
+
+///
+/// ## The configuration parameters of the camera reconnection
+/// 
+/// ### Conf example
+/// ```yaml
+/// reconnect:                      # optional, camera reconnection
+///     backoff: 500 ms             # optional, delay before the reconnection, doubled on each failure in a row, default 500 ms
+///     max-backoff: 30 s           # optional, maximum delay before the reconnection, default 30 s
+///     frame-timeout: 2000 ms      # optional, camera is reconnected if no frames this time, default 2000 ms
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReconnectConf {
+    pub name: Name,
+    /// Delay before the first reconnection, doubled on each failure in a row
+    pub backoff: Duration,
+    /// Maximum delay before the reconnection
+    pub max_backoff: Duration,
+    /// Camera is reconnected if no frames this time
+    pub frame_timeout: Duration,
+}
+//
+// 
+impl ReconnectConf {
+    ///
+    /// Returns [ReconnectConf] built from `ConfTree`:
+    pub fn new(
+        parent: impl Into<String>,
+        conf: ConfTree,
+    ) -> Self {
+        let parent = parent.into();
+        let me = "ReconnectConf";
+        let dbg = Dbg::new(&parent, me);
+        let name = Name::new(parent, me);
+        log::trace!("{dbg}.new | name: {:?}", name);
+        let backoff = conf.get_duration("backoff").unwrap_or(Duration::from_millis(500));
+        log::trace!("{}.new | backoff: {:?}", dbg, backoff);
+        let max_backoff = conf.get_duration("max-backoff").unwrap_or(Duration::from_secs(30));
+        log::trace!("{}.new | max-backoff: {:?}", dbg, max_backoff);
+        let frame_timeout = conf.get_duration("frame-timeout").unwrap_or(Duration::from_millis(2000));
+        log::trace!("{}.new | frame-timeout: {:?}", dbg, frame_timeout);
+        Self {
+            name,
+            backoff,
+            max_backoff,
+            frame_timeout,
+        }
+    }
+    ///
+    /// Returns the delay before the reconnection after the `failures` in a row
+    pub fn backoff(&self, failures: usize) -> Duration {
+        backoff(self.backoff, self.max_backoff, failures)
+    }
+}
+//
+//
+impl Default for ReconnectConf {
+    fn default() -> Self {
+        Self {
+            name: Name::new("", "ReconnectConf"),
+            backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            frame_timeout: Duration::from_millis(2000),
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use opencv::core::{Mat, MatTraitConst};
+use sal_core::error::Error;
+use frdm_tools::{
+    ContextWrite, EvalResult,
+    ResultCtx, ContextRead,
+    Eval, Image,
+};
+///
+/// Takes source [Image]
+/// Return gray scale [Image] of the edges, detected by the Sobel operator,
+/// horizontal & vertical gradients are summed with equal weights
+pub struct Sobel {
+    kernel_size: i32,
+    scale: f64,
+    delta: f64,
+    ctx: Box<dyn Eval<Image, EvalResult>>,
+}
+impl Sobel {
+    ///
+    /// Returns [Sobel] new instance
+    /// - `kernel_size` - size of the extended Sobel kernel, 1, 3, 5 or 7
+    /// - `scale` - scale factor for the computed derivative values
+    /// - `delta` - value added to the results
+    pub fn new(kernel_size: i32, scale: f64, delta: f64, ctx: impl Eval<Image, EvalResult> + 'static) -> Self {
+        Self { 
+            kernel_size,
+            scale,
+            delta,
+            ctx: Box::new(ctx),
+        }
+    }
+    ///
+    /// Returns the edges of the `mat`
+    fn edges(&self, mat: &Mat) -> Result<Mat, opencv::Error> {
+        let gray = match mat.channels() {
+            1 => mat.clone(),
+            _ => {
+                let mut gray = Mat::default();
+                opencv::imgproc::cvt_color(mat, &mut gray, opencv::imgproc::COLOR_BGR2GRAY, 0)?;
+                gray
+            }
+        };
+        let mut grad = [Mat::default(), Mat::default()];
+        let mut abs = [Mat::default(), Mat::default()];
+        for (i, (dx, dy)) in [(1, 0), (0, 1)].into_iter().enumerate() {
+            opencv::imgproc::sobel(&gray, &mut grad[i], opencv::core::CV_16S, dx, dy, self.kernel_size, self.scale, self.delta, opencv::core::BORDER_DEFAULT)?;
+            opencv::core::convert_scale_abs(&grad[i], &mut abs[i], 1.0, 0.0)?;
+        }
+        let mut edges = Mat::default();
+        opencv::core::add_weighted(&abs[0], 0.5, &abs[1], 0.5, 0.0, &mut edges, -1)?;
+        Ok(edges)
+    }
+}
+//
+//
+impl Eval<Image, EvalResult> for Sobel {
+    fn eval(&self, frame: Image) -> EvalResult {
+        let error = Error::new("Sobel", "eval");
+        match self.ctx.eval(frame) {
+            Ok(ctx) => {
+                let result: &ResultCtx = ctx.read();
+                let frame = &result.frame;
+                match self.edges(&frame.mat) {
+                    Ok(edges) => {
+                        let frame = Image {
+                            width: frame.width,
+                            height: frame.height,
+                            timestamp: frame.timestamp,
+                            mat: edges,
+                            bytes: frame.bytes,
+                        };
+                        let result = ResultCtx { frame };
+                        ctx.write(result)
+                    }
+                    Err(err) => Err(error.pass(err.to_string())),
+                }
+            }
+            Err(err) => Err(error.pass(err)),
+        }
+    }
+}
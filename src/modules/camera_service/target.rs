@@ -0,0 +1,28 @@
+use crate::modules::{CameraId, Position, Position3, Sway};
+
+///
+/// The target position published by the [CameraService](crate::modules::CameraService)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Target {
+    /// The camera the target is found by
+    pub camera: CameraId,
+    /// Timestamp of the frame the target is found on, s
+    pub time: f64,
+    /// Filtered position of the target on the frame, px
+    pub position: Position<f64>,
+    /// Velocity of the target, px/frame, if estimated by the filter
+    pub velocity: Option<Position<f64>>,
+    /// Position of the target in the world, m, if fused or transformed by the `world` of the camera
+    pub world: Option<WorldPosition>,
+    /// Sway of the load, if the `sway` analysis of the camera configured & the rope length known
+    pub sway: Option<Sway>,
+}
+///
+/// Position of the [Target] in the world, m
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WorldPosition {
+    /// On the plane `z = 0`, transformed by the `world` of the camera
+    Plane(Position<f64>),
+    /// 3D, fused from the several cameras
+    Space(Position3<f64>),
+}
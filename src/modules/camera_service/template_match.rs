@@ -1,8 +1,8 @@
-use std::time::Instant;
-
 use frdm_tools::{ContextRead, ContextWrite, Eval, EvalResult, Image, ResultCtx};
-use opencv::core::Mat;
-use sal_core::error::Error;
+use opencv::{core::{Mat, MatTrait, MatTraitConst, Point, Point2f, Rect, Scalar, Size, VecN}, imgproc::{LineTypes, TemplateMatchModes}};
+use sal_core::{dbg::Dbg, error::Error};
+
+use crate::modules::{Detect, Detection, TemplateRange, Tracking};
 
 ///
 /// # Detection of the template on the input frame
@@ -124,80 +124,278 @@ use sal_core::error::Error;
 /// that your choice is not arbitrary. As usual with a data-filled field, you'll need to make sure your data is as close to real world examples as possible,
 /// and that your test data covers your edge cases as well as your typical images.
 /// 
+/// ## Scale and rotation
+/// 
+/// The template is prepared for each combination of the configured scales and angles.
+/// Rotated templates are matched with the mask, so the corners added by the rotation are ignored.
+/// If `pyramid` levels configured, all variants are searched on the frame downscaled 2^levels times,
+/// and then the best one is refined on the source frame in the small area around the coarse location.
 pub struct TemplateMatch {
-    method: opencv::imgproc::TemplateMatchModes,
+    method: TemplateMatchModes,
     threshold: f64,
-    template: Image,
+    pyramid: u32,
+    templates: Vec<TemplateVariant>,
     ctx: Box<dyn Eval<Image, EvalResult>>,
+    dbg: Dbg,
 }
 //
 //
 impl TemplateMatch {
     ///
     /// Returns [TemplateMatch] new instance
-    /// - `threshold` - ...
     /// - `method` - TM_CCOEFF_NORMED or TM_CCORR_NORMED
-    pub fn new(method: opencv::imgproc::TemplateMatchModes, threshold: f64, template: Image, ctx: impl Eval<Image, EvalResult> + 'static) -> Self {
+    /// - `threshold` - minimum score of the match, maximum for TM_SQDIFF & TM_SQDIFF_NORMED
+    /// - `scale` - template scales to be searched
+    /// - `angle` - template rotations to be searched, degrees
+    /// - `pyramid` - coarse search on the frame downscaled 2^pyramid times, 0 - search on the source frame only
+    pub fn new(method: TemplateMatchModes, threshold: f64, scale: &TemplateRange, angle: &TemplateRange, pyramid: u32, template: Image, ctx: impl Eval<Image, EvalResult> + 'static) -> Self {
+        let dbg = Dbg::new("", "TemplateMatch");
+        let mut templates = vec![];
+        for scale in scale.values() {
+            for angle in angle.values() {
+                match TemplateVariant::new(&template.mat, scale, angle, pyramid) {
+                    Ok(variant) => templates.push(variant),
+                    Err(err) => log::warn!("{dbg}.new | Template scale: {scale}, angle: {angle} skipped, error: {err}"),
+                }
+            }
+        }
+        log::debug!("{dbg}.new | Template variants: {}", templates.len());
         Self { 
             method,
             threshold,
-            template,
+            pyramid,
+            templates,
             ctx: Box::new(ctx),
+            dbg,
+        }
+    }
+    ///
+    /// Returns true if `score` is better then `other`
+    fn is_better(&self, score: f64, other: f64) -> bool {
+        match self.method {
+            TemplateMatchModes::TM_SQDIFF | TemplateMatchModes::TM_SQDIFF_NORMED => score < other,
+            _ => score > other,
+        }
+    }
+    ///
+    /// Returns the best score and location of the `template` on the `frame`,
+    /// None if `template` doesn't fit to the `frame`
+    fn match_best(&self, frame: &Mat, template: &Mat, mask: &Mat) -> Result<Option<(f64, Point)>, Error> {
+        if template.cols() > frame.cols() || template.rows() > frame.rows() {
+            return Ok(None);
+        }
+        let mut result = Mat::default();
+        opencv::imgproc::match_template(frame, template, &mut result, self.method as i32, mask)
+            .map_err(|err| Error::new(&self.dbg, "match_best").pass(err.to_string()))?;
+        let mut min_val: f64 = 0.0;
+        let mut max_val: f64 = 0.0;
+        let mut min_loc = Point::new(0, 0);
+        let mut max_loc = Point::new(0, 0);
+        opencv::core::min_max_loc(&result, Some(&mut min_val), Some(&mut max_val), Some(&mut min_loc), Some(&mut max_loc), &Mat::default())
+            .map_err(|err| Error::new(&self.dbg, "match_best").pass(err.to_string()))?;
+        match self.method {
+            TemplateMatchModes::TM_SQDIFF | TemplateMatchModes::TM_SQDIFF_NORMED => Ok(Some((min_val, min_loc))),
+            _ => Ok(Some((max_val, max_loc))),
+        }
+    }
+    ///
+    /// Returns the best template variant index, score and location on the `frame`
+    fn search(&self, frame: &Mat) -> Result<Option<(usize, f64, Point)>, Error> {
+        let coarse = match self.pyramid {
+            0 => frame.clone(),
+            levels => {
+                let mut coarse = frame.clone();
+                for _ in 0..levels {
+                    let mut down = Mat::default();
+                    opencv::imgproc::pyr_down(&coarse, &mut down, Size::default(), opencv::core::BORDER_DEFAULT)
+                        .map_err(|err| Error::new(&self.dbg, "search").pass(err.to_string()))?;
+                    coarse = down;
+                }
+                coarse
+            }
+        };
+        let mut best: Option<(usize, f64, Point)> = None;
+        for (i, variant) in self.templates.iter().enumerate() {
+            if let Some((score, loc)) = self.match_best(&coarse, &variant.coarse, &variant.coarse_mask)? {
+                log::trace!("{}.search | scale: {}, angle: {}, score: {}", self.dbg, variant.scale, variant.angle, score);
+                if best.is_none_or(|(_, best, _)| self.is_better(score, best)) {
+                    best = Some((i, score, loc));
+                }
+            }
+        }
+        match (best, self.pyramid) {
+            (Some((i, score, loc)), 0) => Ok(Some((i, score, loc))),
+            (Some((i, score, loc)), levels) => {
+                let variant = &self.templates[i];
+                let factor = 1 << levels;
+                let margin = factor * 2;
+                let x0 = (loc.x * factor - margin).max(0);
+                let y0 = (loc.y * factor - margin).max(0);
+                let x1 = (loc.x * factor + variant.mat.cols() + margin).min(frame.cols());
+                let y1 = (loc.y * factor + variant.mat.rows() + margin).min(frame.rows());
+                let roi = Mat::roi(frame, Rect::new(x0, y0, x1 - x0, y1 - y0))
+                    .and_then(|roi| roi.try_clone())
+                    .map_err(|err| Error::new(&self.dbg, "search").pass(err.to_string()))?;
+                match self.match_best(&roi, &variant.mat, &variant.mask)? {
+                    Some((score, loc)) => Ok(Some((i, score, Point::new(x0 + loc.x, y0 + loc.y)))),
+                    None => Ok(Some((i, score, Point::new(loc.x * factor, loc.y * factor)))),
+                }
+            }
+            (None, _) => Ok(None),
         }
     }
     ///
-    /// Draws a bounding box around matched image segment
-    fn draw_box(&self, mut frame: Image, rec: opencv::core::Rect) -> Result<Image, Error> {
-        match opencv::imgproc::rectangle(
-            &mut frame.mat,
-            rec,
-            opencv::core::VecN([0.0, 0.0, 255.0, 255.0]),
-            3,
-            opencv::imgproc::LineTypes::FILLED as i32,
-            0,
-        ) {
-            Ok(_) => Ok(frame),
-            Err(err) => Err(Error::new("me", "area").pass(err.to_string())),
+    /// Draws a bounding box of the matched template
+    fn draw_box(frame: &mut Mat, detection: &Detection, width: f64, height: f64) -> Result<(), Error> {
+        let (sin, cos) = detection.angle.to_radians().sin_cos();
+        let corners: Vec<Point> = [(-0.5, -0.5), (0.5, -0.5), (0.5, 0.5), (-0.5, 0.5)].iter().map(|(kx, ky)| {
+            let (dx, dy) = (kx * width, ky * height);
+            Point::new(
+                (detection.x + dx * cos + dy * sin).round() as i32,
+                (detection.y - dx * sin + dy * cos).round() as i32,
+            )
+        }).collect();
+        for i in 0..corners.len() {
+            opencv::imgproc::line(
+                frame,
+                corners[i],
+                corners[(i + 1) % corners.len()],
+                VecN([0.0, 0.0, 255.0, 255.0]),
+                3,
+                LineTypes::LINE_8 as i32,
+                0,
+            ).map_err(|err| Error::new("TemplateMatch", "draw_box").pass(err.to_string()))?;
         }
+        Ok(())
     }
 }
 //
 //
-impl Eval<Image, EvalResult> for TemplateMatch {
-    fn eval(&self, src: Image) -> EvalResult {
-        let error = Error::new("TemplateMatch", "eval");
-        match self.ctx.eval(src.clone()) {
+impl Detect for TemplateMatch {
+    fn detect(&self, src: Image, roi: Option<Rect>) -> (EvalResult, Option<Detection>) {
+        let error = Error::new("TemplateMatch", "detect");
+        match self.ctx.eval(src) {
             Ok(ctx) => {
-                let t = Instant::now();
                 let result: &ResultCtx = ctx.read();
-                let frame = &result.frame;
-                let mut result = Mat::default();
-                let mask = Mat::default();
-                match opencv::imgproc::match_template(&frame.mat, &self.template.mat, &mut result, self.method as i32, &mask) {
-                    Ok(_) => {
-                        let mut min_val: f64 = 0.0;
-                        let mut max_val: f64 = 0.0;
-                        let mut min_loc = opencv::core::Point::new(0, 0);
-                        let mut max_loc = opencv::core::Point::new(0, 0);
-                        match opencv::core::min_max_loc(&result, Some(&mut min_val), Some(&mut max_val), Some(&mut min_loc), Some(&mut max_loc), &mask) {
-                            Ok(_) => {
-                                if max_val > self.threshold {
-                                    let frame = self.draw_box(src, opencv::core::Rect::new(max_loc.x, max_loc.y, 30, 30))?;
-                                    let result = ResultCtx { frame };
-                                    log::debug!("TemplateMatch.eval | X: {}, Y: {}", max_loc.x, max_loc.y);
-                                    log::debug!("TemplateMatch.eval | Elapsed: {:?}", t.elapsed());
-                                    ctx.write(result)
-                                } else {
-                                    Err(error.err("Match not found"))
-                                }
-                            }
-                            Err(err) => Err(error.pass(err.to_string())),
+                let mut frame = result.frame.clone();
+                let min_cols = self.templates.iter().map(|t| t.mat.cols()).max().unwrap_or(1);
+                let min_rows = self.templates.iter().map(|t| t.mat.rows()).max().unwrap_or(1);
+                let roi = roi.and_then(|roi| Tracking::clamp(&roi, frame.mat.cols(), frame.mat.rows(), min_cols, min_rows));
+                let found = match roi {
+                    Some(roi) => Mat::roi(&frame.mat, roi).and_then(|roi_mat| roi_mat.try_clone())
+                        .map_err(|err| error.pass(err.to_string()))
+                        .and_then(|roi_mat| self.search(&roi_mat))
+                        .map(|found| found.map(|(i, score, loc)| (i, score, Point::new(roi.x + loc.x, roi.y + loc.y)))),
+                    None => self.search(&frame.mat),
+                };
+                match found {
+                    Ok(Some((i, score, loc))) if self.is_better(score, self.threshold) => {
+                        let variant = &self.templates[i];
+                        let detection = Detection {
+                            x: loc.x as f64 + variant.mat.cols() as f64 * 0.5,
+                            y: loc.y as f64 + variant.mat.rows() as f64 * 0.5,
+                            scale: variant.scale,
+                            angle: variant.angle,
+                            score,
+                        };
+                        log::debug!("TemplateMatch.detect | X: {}, Y: {}, scale: {}, angle: {}, score: {}", detection.x, detection.y, detection.scale, detection.angle, detection.score);
+                        if let Err(err) = Self::draw_box(&mut frame.mat, &detection, variant.width, variant.height) {
+                            log::warn!("TemplateMatch.detect | Draw error: {}", err);
                         }
+                        (ctx.write(ResultCtx { frame }), Some(detection))
+                    }
+                    Ok(_) => {
+                        log::debug!("TemplateMatch.detect | Match not found");
+                        (ctx.write(ResultCtx { frame }), None)
                     }
-                    Err(err) => Err(error.pass(err.to_string())),
+                    Err(err) => (Err(error.pass(err.to_string())), None),
                 }
             }
-            Err(err) => Err(error.pass(err)),
+            Err(err) => (Err(error.pass(err)), None),
+        }
+    }
+}
+//
+//
+impl Eval<Image, EvalResult> for TemplateMatch {
+    fn eval(&self, src: Image) -> EvalResult {
+        match self.detect(src, None) {
+            (Ok(ctx), Some(_)) => Ok(ctx),
+            (Ok(_), None) => Err(Error::new("TemplateMatch", "eval").err("Match not found")),
+            (Err(err), _) => Err(err),
         }
     }
 }
+///
+/// The template scaled and rotated to be matched
+struct TemplateVariant {
+    scale: f64,
+    angle: f64,
+    /// Width of the scaled template before rotation
+    width: f64,
+    /// Height of the scaled template before rotation
+    height: f64,
+    mat: Mat,
+    mask: Mat,
+    /// The variant downscaled for the pyramid search
+    coarse: Mat,
+    coarse_mask: Mat,
+}
+//
+//
+impl TemplateVariant {
+    ///
+    /// Returns the `template` scaled, rotated and downscaled 2^`pyramid` times for the coarse search
+    fn new(template: &Mat, scale: f64, angle: f64, pyramid: u32) -> Result<Self, Error> {
+        let error = Error::new("TemplateVariant", "new");
+        let width = template.cols() as f64 * scale;
+        let height = template.rows() as f64 * scale;
+        let (sin, cos) = angle.to_radians().sin_cos();
+        let size = Size::new(
+            (width * cos.abs() + height * sin.abs()).round() as i32,
+            (width * sin.abs() + height * cos.abs()).round() as i32,
+        );
+        if size.width < 1 || size.height < 1 {
+            return Err(error.err(format!("Template is empty, size: {:?}", size)));
+        }
+        let center = Point2f::new(template.cols() as f32 * 0.5, template.rows() as f32 * 0.5);
+        let mut transform = opencv::imgproc::get_rotation_matrix_2d(center, angle, scale)
+            .map_err(|err| error.pass(err.to_string()))?;
+        // Moving the template center to the center of the rotated template bounds
+        *transform.at_2d_mut::<f64>(0, 2).map_err(|err| error.pass(err.to_string()))? += size.width as f64 * 0.5 - center.x as f64;
+        *transform.at_2d_mut::<f64>(1, 2).map_err(|err| error.pass(err.to_string()))? += size.height as f64 * 0.5 - center.y as f64;
+        let mut mat = Mat::default();
+        opencv::imgproc::warp_affine(template, &mut mat, &transform, size, opencv::imgproc::INTER_LINEAR, opencv::core::BORDER_CONSTANT, Scalar::default())
+            .map_err(|err| error.pass(err.to_string()))?;
+        let mask = match angle == 0.0 {
+            true => Mat::default(),
+            false => {
+                let full = Mat::new_rows_cols_with_default(template.rows(), template.cols(), opencv::core::CV_8UC1, Scalar::all(255.0))
+                    .map_err(|err| error.pass(err.to_string()))?;
+                let mut mask = Mat::default();
+                opencv::imgproc::warp_affine(&full, &mut mask, &transform, size, opencv::imgproc::INTER_NEAREST, opencv::core::BORDER_CONSTANT, Scalar::default())
+                    .map_err(|err| error.pass(err.to_string()))?;
+                mask
+            }
+        };
+        let (coarse, coarse_mask) = match pyramid {
+            0 => (mat.clone(), mask.clone()),
+            levels => {
+                let factor = 1.0 / (1 << levels) as f64;
+                let mut coarse = Mat::default();
+                opencv::imgproc::resize(&mat, &mut coarse, Size::default(), factor, factor, opencv::imgproc::INTER_AREA)
+                    .map_err(|err| error.pass(err.to_string()))?;
+                if coarse.cols() < 1 || coarse.rows() < 1 {
+                    return Err(error.err(format!("Template is too small for {levels} pyramid levels, size: {:?}", size)));
+                }
+                let mut coarse_mask = Mat::default();
+                if !mask.empty() {
+                    opencv::imgproc::resize(&mask, &mut coarse_mask, coarse.size().map_err(|err| error.pass(err.to_string()))?, 0.0, 0.0, opencv::imgproc::INTER_NEAREST)
+                        .map_err(|err| error.pass(err.to_string()))?;
+                }
+                (coarse, coarse_mask)
+            }
+        };
+        Ok(Self { scale, angle, width, height, mat, mask, coarse, coarse_mask })
+    }
+}
@@ -1,24 +1,74 @@
 use sal_core::{dbg::Dbg, error::Error};
 use sal_sync::services::{conf::{ConfTree, ConfTreeGet}, entity::Name};
 
+use crate::modules::{FilterConf, TrackingConf};
+
 ///
 /// Configuration parameters for template matching algorithm
 /// 
 /// Conf Example:
 /// ```yaml
+///     matcher: BfMatch            # optional, BfMatch (default) - keypoints matching, TemplateMatch - multi-scale & rotation template matching
 ///     match-ratio: 0.8            # the lower the ratio, the less matches will used (by the match distance)
-///     deviation-ratio: 1.7        # the lower the ratio, the less key points will used (closest to the geometrical center) 
+///     deviation-ratio: 1.7        # the lower the ratio, the less key points will used (deviation from the center relative to the median deviation)
 ///     method: TM_CCOEFF_NORMED    # TM_CCOEFF_NORMED or TM_CCORR_NORMED recomended, 
+///     threshold: 0.7              # optional, TemplateMatch only, minimum score of the match (maximum for TM_SQDIFF*), default 0.8
+///     scale:                      # optional, TemplateMatch only, template scales to be searched, default 1.0
+///         min: 0.6
+///         max: 1.4
+///         step: 0.1
+///     angle:                      # optional, TemplateMatch only, template rotations to be searched, degrees, default 0.0
+///         min: -10.0
+///         max: 10.0
+///         step: 5.0
+///     pyramid-levels: 2           # optional, TemplateMatch only, coarse search on the frame downscaled 2^levels times, default 0
+///     tracking:                   # optional, search in the region of interest around the last known position, default full frame search
+///         width: 320                  # width of the region of interest, px
+///         height: 320                 # height of the region of interest, px
+///         expand: 1.5                 # optional, region of interest grows this times on each miss, default 1.5
+///         max-misses: 5               # optional, full frame search after this number of misses, default 5
+///         min-score: 12               # optional, lower scored detections are misses: matched keypoints for BfMatch, correlation for TemplateMatch, default - any detection
+///     filters:                    # optional, chain of the position filters, applied in the listed order
+///         - outlier:
+///             max-speed: 40               # maximum allowed jump of the position, px per `per`, bigger jumps are rejected
+///             per: frame                  # optional, frame (default) / second - real time between the frames is used
+///             relock: 3                   # optional, new location accepted after this number of consistent jumps, default 3
+///         - median:
+///             window: 5                   # number of the last positions the median is taken of, removes single spikes
+///         - average:
+///             window: 3                   # number of the last positions averaged
+///         - smooth:
+///             factor: 4                   # the lower the setting, the lower the smoothing, 1 - no smoothing, recomended max: 256
+///             axis: x                     # optional, x / y - filter only single axis, default - both axes
+///             sample-time: 0.04           # optional, nominal time between the frames, seconds, if set - real time between the frames is used
+///         - low-pass:
+///             cutoff: 2.0                 # cutoff frequency, Hz
+///             sample-time: 0.04           # time between the frames, seconds
+///         - kalman:
+///             model: velocity             # optional, velocity (default) - constant velocity / acceleration - constant acceleration
+///             process-noise: 0.5          # variance of the unmodeled acceleration (jerk for `acceleration` model) per frame
+///             measurement-noise: 4.0      # variance of the detected position, px^2
+///             max-predictions: 5          # optional, position predicted through this number of missed frames, default 5
+///             sample-time: 0.04           # optional, nominal time between the frames, seconds, if set - real time between the frames is used
+///     # Legacy single filter, used if `filters` is omitted
+///     # kalman: ...                 # same as `filters: [kalman: ...]`
 ///     smooth-moving: 4    # Smooth filter for X & Y changes presset, the lower the setting, the lower the smoothing, 1 (default) - no smoothing, recomended max: 256
 /// ```
 #[derive(Debug, Clone, PartialEq)]
 pub struct TemplateMatchConf {
     pub name: Name,
+    pub matcher: Matcher,
     pub template: String,
     pub method: opencv::imgproc::TemplateMatchModes,
     pub match_ratio: f64,
     pub deviation_ratio: f64,
-    pub smooth: Option<f64>,
+    pub threshold: f64,
+    pub scale: TemplateRange,
+    pub angle: TemplateRange,
+    pub pyramid_levels: u32,
+    pub tracking: Option<TrackingConf>,
+    /// Chain of the position filters
+    pub filters: Vec<FilterConf>,
 }
 //
 // 
@@ -34,6 +84,12 @@ impl TemplateMatchConf {
         let dbg = Dbg::new(&parent, me);
         let name = Name::new(parent, me);
         log::trace!("{dbg}.new | name: {:?}", name);
+        let matcher: Option<String> = conf.get("matcher");
+        let matcher = match matcher {
+            Some(matcher) => Self::matcher_from_str(&matcher).expect(&format!("{dbg}.new | Unknown 'matcher' {matcher}")),
+            None => Matcher::BfMatch,
+        };
+        log::trace!("{}.new | matcher: {:?}", dbg, matcher);
         let template: String = conf.get("template").expect(&format!("{dbg}.new | 'template' - not found or wrong configuration"));
         log::trace!("{}.new | template: {:?}", dbg, template);
         let method: String = conf.get("method").expect(&format!("{dbg}.new | 'method' - not found or wrong configuration"));
@@ -43,15 +99,55 @@ impl TemplateMatchConf {
         log::trace!("{}.new | match-ratio: {:?}", dbg, match_ratio);
         let deviation_ratio: f64 = conf.get("deviation-ratio").expect(&format!("{dbg}.new | 'deviation-ratio' - not found or wrong configuration"));
         log::trace!("{}.new | deviation-ratio: {:?}", dbg, deviation_ratio);
-        let smooth = conf.get("smooth-moving");
-        log::trace!("{}.new | smooth-moving: {:?}", dbg, smooth);
+        let threshold: f64 = conf.get("threshold").unwrap_or(0.8);
+        log::trace!("{}.new | threshold: {:?}", dbg, threshold);
+        let scale = match conf.get("scale") {
+            Some(scale) => TemplateRange::new(&dbg, scale),
+            None => TemplateRange::single(1.0),
+        };
+        log::trace!("{}.new | scale: {:?}", dbg, scale);
+        let angle = match conf.get("angle") {
+            Some(angle) => TemplateRange::new(&dbg, angle),
+            None => TemplateRange::single(0.0),
+        };
+        log::trace!("{}.new | angle: {:?}", dbg, angle);
+        let pyramid_levels: u32 = conf.get("pyramid-levels").unwrap_or(0);
+        log::trace!("{}.new | pyramid-levels: {:?}", dbg, pyramid_levels);
+        let tracking = conf.get("tracking").map(|tracking| TrackingConf::new(&dbg, tracking));
+        log::trace!("{}.new | tracking: {:?}", dbg, tracking);
+        let is_sqdiff = matches!(method, opencv::imgproc::TemplateMatchModes::TM_SQDIFF | opencv::imgproc::TemplateMatchModes::TM_SQDIFF_NORMED);
+        if matcher == Matcher::TemplateMatch && is_sqdiff && tracking.as_ref().is_some_and(|tracking| tracking.min_score.is_some()) {
+            panic!("{dbg}.new | 'tracking.min-score' - is the minimum correlation, not used with the {:?} 'method'", method);
+        }
+        let filters = match conf.get("filters") {
+            Some(filters) => FilterConf::list(&dbg, filters),
+            None => {
+                let kalman: Option<ConfTree> = conf.get("kalman");
+                let smooth: Option<f64> = conf.get("smooth-moving");
+                match (kalman, smooth) {
+                    (Some(kalman), _) => vec![FilterConf::new(&dbg, "kalman", kalman)],
+                    (None, Some(factor)) => {
+                        let smooth = serde_yaml::Mapping::from_iter([("factor".into(), factor.into())]);
+                        vec![FilterConf::new(&dbg, "smooth", ConfTree::new_root(serde_yaml::Value::Mapping(smooth)))]
+                    }
+                    (None, None) => vec![],
+                }
+            }
+        };
+        log::trace!("{}.new | filters: {:#?}", dbg, filters);
         Self {
             name,
+            matcher,
             template,
             method,
             match_ratio,
             deviation_ratio,
-            smooth,
+            threshold,
+            scale,
+            angle,
+            pyramid_levels,
+            tracking,
+            filters,
         }
     }
     ///
@@ -67,4 +163,82 @@ impl TemplateMatchConf {
             _ => Err(Error::new("TemplateMatchConf", "template_match_modes_from_str").err(format!("Unknown method {}", method))),
         }
     }
+    ///
+    /// Returns [Matcher] parsed from string
+    fn matcher_from_str(matcher: &str) -> Result<Matcher, Error> {
+        match matcher.to_lowercase().as_str() {
+            "bfmatch" => Ok(Matcher::BfMatch),
+            "templatematch" => Ok(Matcher::TemplateMatch),
+            _ => Err(Error::new("TemplateMatchConf", "matcher_from_str").err(format!("Unknown matcher {}", matcher))),
+        }
+    }
+}
+///
+/// Algorithm used to find the template on the frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Matcher {
+    /// Keypoints matching, [BfMatch](crate::modules::BfMatch)
+    BfMatch,
+    /// Multi-scale & rotation template matching, [TemplateMatch](crate::modules::TemplateMatch)
+    TemplateMatch,
+}
+///
+/// Range of the values to be searched
+/// 
+/// Conf Example:
+/// ```yaml
+/// scale:
+///     min: 0.6
+///     max: 1.4
+///     step: 0.1       # optional, if omitted only `min` and `max` are used
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemplateRange {
+    pub min: f64,
+    pub max: f64,
+    pub step: Option<f64>,
+}
+//
+//
+impl TemplateRange {
+    ///
+    /// Returns [TemplateRange] built from `ConfTree`:
+    pub fn new(parent: impl Into<String>, conf: ConfTree) -> Self {
+        let dbg = Dbg::new(parent, "TemplateRange");
+        let min: f64 = conf.get("min").expect(&format!("{dbg}.new | 'min' - not found or wrong configuration"));
+        let max: f64 = conf.get("max").expect(&format!("{dbg}.new | 'max' - not found or wrong configuration"));
+        let step: Option<f64> = conf.get("step");
+        if max < min {
+            panic!("{dbg}.new | 'max' {max} is less then 'min' {min}");
+        }
+        if let Some(step) = step {
+            if step <= 0.0 {
+                panic!("{dbg}.new | 'step' {step} must be positive");
+            }
+        }
+        Self { min, max, step }
+    }
+    ///
+    /// Returns [TemplateRange] containing single `value`
+    pub fn single(value: f64) -> Self {
+        Self { min: value, max: value, step: None }
+    }
+    ///
+    /// Returns all values of the range, from `min` to `max` inclusive
+    pub fn values(&self) -> Vec<f64> {
+        if self.max <= self.min {
+            return vec![self.min];
+        }
+        match self.step {
+            Some(step) => {
+                let steps = ((self.max - self.min) / step + 1e-9).floor() as usize;
+                let mut values: Vec<f64> = (0..=steps).map(|i| self.min + step * i as f64).collect();
+                if self.max - values[steps] > 1e-9 {
+                    values.push(self.max);
+                }
+                values
+            }
+            None => vec![self.min, self.max],
+        }
+    }
 }
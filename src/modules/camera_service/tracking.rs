@@ -0,0 +1,93 @@
+use opencv::core::Rect;
+
+use crate::modules::{Detection, TrackingConf};
+
+///
+/// Region of interest search around the last known position
+/// 
+/// - After the target is confidently found (scored `min-score` or more), next frame is searched only in the region of interest
+///   around the position predicted by the last movement of the target
+/// - Each miss expands the region of interest `expand` times
+/// - After `max-misses` misses the target is considered lost and the full frame is searched
+#[derive(Debug, Clone)]
+pub struct Tracking {
+    conf: TrackingConf,
+    last: Option<(f64, f64)>,
+    velocity: (f64, f64),
+    misses: usize,
+}
+//
+//
+impl Tracking {
+    ///
+    /// Returns [Tracking] new instance
+    pub fn new(conf: TrackingConf) -> Self {
+        Self {
+            conf,
+            last: None,
+            velocity: (0.0, 0.0),
+            misses: 0,
+        }
+    }
+    ///
+    /// Returns the region of interest to be searched on the next frame,
+    /// None - the full frame must be searched
+    pub fn roi(&self) -> Option<Rect> {
+        self.last.map(|(x, y)| {
+            let steps = (self.misses + 1) as f64;
+            let (x, y) = (x + self.velocity.0 * steps, y + self.velocity.1 * steps);
+            let expand = self.conf.expand.powi(self.misses as i32);
+            let (width, height) = (self.conf.width * expand, self.conf.height * expand);
+            Rect::new(
+                (x - width * 0.5).round() as i32,
+                (y - height * 0.5).round() as i32,
+                width.round() as i32,
+                height.round() as i32,
+            )
+        })
+    }
+    ///
+    /// Updates the state with the [Detection] on the last frame, None - if target not found
+    /// - Detection scored less then `min-score` is considered as a miss
+    pub fn update(&mut self, detection: Option<&Detection>) {
+        let detection = detection.filter(|detection| self.conf.min_score.is_none_or(|min| detection.score >= min));
+        match detection {
+            Some(&Detection { x, y, .. }) => {
+                self.velocity = match (self.last, self.misses) {
+                    (Some((last_x, last_y)), 0) => (x - last_x, y - last_y),
+                    _ => (0.0, 0.0),
+                };
+                self.last = Some((x, y));
+                self.misses = 0;
+            }
+            None => {
+                if self.last.is_some() {
+                    self.misses += 1;
+                    if self.misses > self.conf.max_misses {
+                        self.reset();
+                    }
+                }
+            }
+        }
+    }
+    ///
+    /// The target is considered lost, the full frame will be searched
+    pub fn reset(&mut self) {
+        self.last = None;
+        self.velocity = (0.0, 0.0);
+        self.misses = 0;
+    }
+    ///
+    /// Returns the part of the `roi` within the frame of `cols` x `rows`,
+    /// None if it's less then `min_cols` x `min_rows`
+    pub fn clamp(roi: &Rect, cols: i32, rows: i32, min_cols: i32, min_rows: i32) -> Option<Rect> {
+        let x0 = roi.x.max(0);
+        let y0 = roi.y.max(0);
+        let x1 = (roi.x + roi.width).min(cols);
+        let y1 = (roi.y + roi.height).min(rows);
+        match x1 - x0 >= min_cols && y1 - y0 >= min_rows {
+            true => Some(Rect::new(x0, y0, x1 - x0, y1 - y0)),
+            false => None,
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use sal_core::dbg::Dbg;
+use sal_sync::services::conf::{ConfTree, ConfTreeGet};
+
+///
+/// Configuration parameters of the search in the region of interest around the last known position
+/// 
+/// Conf Example:
+/// ```yaml
+/// tracking:
+///     width: 320          # width of the region of interest, px
+///     height: 320         # height of the region of interest, px
+///     expand: 1.5         # optional, region of interest grows this times on each miss, default 1.5
+///     max-misses: 5       # optional, full frame search after this number of misses, default 5
+///     min-score: 12       # optional, lower scored detections are misses: matched keypoints for BfMatch, correlation for TemplateMatch, default - any detection
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackingConf {
+    /// Width of the region of interest, px
+    pub width: f64,
+    /// Height of the region of interest, px
+    pub height: f64,
+    /// Region of interest grows this times on each miss
+    pub expand: f64,
+    /// Full frame search after this number of misses
+    pub max_misses: usize,
+    /// Detections with the lower score are considered as misses,
+    /// the number of the matched keypoints for the BfMatch, the correlation for the TemplateMatch
+    pub min_score: Option<f64>,
+}
+//
+//
+impl TrackingConf {
+    ///
+    /// Returns [TrackingConf] built from `ConfTree`:
+    pub fn new(parent: impl Into<String>, conf: ConfTree) -> Self {
+        let dbg = Dbg::new(parent, "TrackingConf");
+        let width: f64 = conf.get("width").expect(&format!("{dbg}.new | 'width' - not found or wrong configuration"));
+        log::trace!("{}.new | width: {:?}", dbg, width);
+        let height: f64 = conf.get("height").expect(&format!("{dbg}.new | 'height' - not found or wrong configuration"));
+        log::trace!("{}.new | height: {:?}", dbg, height);
+        let expand: f64 = conf.get("expand").unwrap_or(1.5);
+        log::trace!("{}.new | expand: {:?}", dbg, expand);
+        let max_misses: usize = conf.get("max-misses").unwrap_or(5);
+        log::trace!("{}.new | max-misses: {:?}", dbg, max_misses);
+        let min_score: Option<f64> = conf.get("min-score");
+        log::trace!("{}.new | min-score: {:?}", dbg, min_score);
+        if width <= 0.0 || height <= 0.0 {
+            panic!("{dbg}.new | 'width' and 'height' must be positive, found: {width} x {height}");
+        }
+        if expand < 1.0 {
+            panic!("{dbg}.new | 'expand' must be 1.0 or more, found: {expand}");
+        }
+        Self { width, height, expand, max_misses, min_score }
+    }
+}
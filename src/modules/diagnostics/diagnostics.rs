@@ -0,0 +1,193 @@
+use std::{sync::{Arc, Mutex}, time::Duration};
+
+use super::{rolling_stats::RollingStats, stage_record::StageRecord};
+
+/// Number of the last frames the statistics calculated over
+const WINDOW: usize = 256;
+///
+/// Shared diagnostics context of the frame processing pipeline
+///
+/// - Stages record themselves while the frame is processed, innermost first
+/// - [Diagnostics::finish_frame] aggregates the records of the frame into the rolling statistics
+/// - Cloned instances share the same state
+#[derive(Debug, Clone, Default)]
+pub struct Diagnostics {
+    state: Arc<Mutex<DiagnosticsState>>,
+}
+//
+//
+impl Diagnostics {
+    ///
+    /// Returns [Diagnostics] new instance
+    pub fn new() -> Self {
+        Self::default()
+    }
+    ///
+    /// Records the stage of the current frame
+    pub fn record(&self, record: StageRecord) {
+        match self.state.lock() {
+            Ok(mut state) => state.records.push(record),
+            Err(err) => log::warn!("Diagnostics.record | Lock error: {:?}", err),
+        }
+    }
+    ///
+    /// Aggregates the records of the current frame into the statistics
+    /// - `latency` - end-to-end latency of the frame, if known
+    pub fn finish_frame(&self, latency: Option<Duration>) {
+        let mut state = match self.state.lock() {
+            Ok(state) => state,
+            Err(err) => {
+                log::warn!("Diagnostics.finish_frame | Lock error: {:?}", err);
+                return;
+            }
+        };
+        let records = std::mem::take(&mut state.records);
+        let mut prev_elapsed = Duration::ZERO;
+        let mut prev_output = None;
+        let mut failed = false;
+        for record in records {
+            let stats = match state.stages.iter().position(|stats| stats.stage == record.stage) {
+                Some(index) => &mut state.stages[index],
+                None => {
+                    state.stages.push(StageStats::new(&record.stage));
+                    state.stages.last_mut().unwrap()
+                }
+            };
+            stats.duration.add(record.elapsed.saturating_sub(prev_elapsed).as_secs_f64() * 1000.0);
+            stats.input = prev_output.or(record.output).or(stats.input);
+            stats.output = record.output.or(stats.output);
+            // Outer stages get the error of the inner one, only the first failed stage is counted
+            if record.error.is_some() && !failed {
+                log::debug!("Diagnostics.finish_frame | Stage '{}' error: {:?}", record.stage, record.error);
+                stats.errors += 1;
+                failed = true;
+            }
+            prev_elapsed = record.elapsed;
+            prev_output = record.output;
+        }
+        if let Some(latency) = latency {
+            state.latency.add(latency.as_secs_f64() * 1000.0);
+        }
+        state.frames += 1;
+    }
+    ///
+    /// Returns the total number of the frames processed
+    pub fn frames(&self) -> usize {
+        self.state.lock().map(|state| state.frames).unwrap_or(0)
+    }
+    ///
+    /// Returns the current statistics
+    pub fn report(&self) -> DiagnosticsReport {
+        match self.state.lock() {
+            Ok(state) => DiagnosticsReport {
+                frames: state.frames,
+                stages: state.stages.iter().map(|stats| StageReport {
+                    stage: stats.stage.clone(),
+                    duration: StatsReport::new(&stats.duration),
+                    errors: stats.errors,
+                    input: stats.input,
+                    output: stats.output,
+                }).collect(),
+                latency: StatsReport::new(&state.latency),
+            },
+            Err(err) => {
+                log::warn!("Diagnostics.report | Lock error: {:?}", err);
+                DiagnosticsReport::default()
+            }
+        }
+    }
+}
+///
+/// Aggregated state of the [Diagnostics]
+#[derive(Debug, Default)]
+struct DiagnosticsState {
+    records: Vec<StageRecord>,
+    stages: Vec<StageStats>,
+    latency: RollingStats<WINDOW>,
+    frames: usize,
+}
+///
+/// Rolling statistics of the single stage
+#[derive(Debug)]
+struct StageStats {
+    stage: String,
+    duration: RollingStats<WINDOW>,
+    errors: usize,
+    input: Option<(i32, i32)>,
+    output: Option<(i32, i32)>,
+}
+//
+//
+impl StageStats {
+    fn new(stage: &str) -> Self {
+        Self { stage: stage.to_owned(), duration: RollingStats::new(), errors: 0, input: None, output: None }
+    }
+}
+///
+/// Snapshot of the [Diagnostics] statistics
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DiagnosticsReport {
+    /// Total number of the frames processed
+    pub frames: usize,
+    /// Statistics of the stages, in the pipeline order
+    pub stages: Vec<StageReport>,
+    /// End-to-end latency, ms
+    pub latency: StatsReport,
+}
+//
+//
+impl std::fmt::Display for DiagnosticsReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "frames: {}, latency: {}", self.frames, self.latency)?;
+        for stage in &self.stages {
+            writeln!(f, "    {}: {}, errors: {}, input: {:?}, output: {:?}", stage.stage, stage.duration, stage.errors, stage.input, stage.output)?;
+        }
+        Ok(())
+    }
+}
+///
+/// Statistics of the single stage
+#[derive(Debug, Clone, PartialEq)]
+pub struct StageReport {
+    pub stage: String,
+    /// Duration of the stage itself, ms
+    pub duration: StatsReport,
+    /// Number of the frames failed on this stage
+    pub errors: usize,
+    /// Size of the input frame, px
+    pub input: Option<(i32, i32)>,
+    /// Size of the output frame, px
+    pub output: Option<(i32, i32)>,
+}
+///
+/// Rolling statistics values
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StatsReport {
+    /// Total number of the values
+    pub count: usize,
+    pub mean: Option<f64>,
+    pub p95: Option<f64>,
+    pub max: Option<f64>,
+}
+//
+//
+impl StatsReport {
+    fn new<const N: usize>(stats: &RollingStats<N>) -> Self {
+        Self {
+            count: stats.count(),
+            mean: stats.mean(),
+            p95: stats.percentile(0.95),
+            max: stats.max(),
+        }
+    }
+}
+//
+//
+impl std::fmt::Display for StatsReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.mean, self.p95, self.max) {
+            (Some(mean), Some(p95), Some(max)) => write!(f, "mean {:.2} ms, p95 {:.2} ms, max {:.2} ms", mean, p95, max),
+            _ => write!(f, "-"),
+        }
+    }
+}
@@ -0,0 +1,9 @@
+mod diagnostics;
+mod rolling_stats;
+mod stage_record;
+mod timed;
+
+pub use diagnostics::*;
+pub use rolling_stats::*;
+pub use stage_record::*;
+pub use timed::*;
@@ -0,0 +1,63 @@
+use circular_buffer::CircularBuffer;
+
+///
+/// Statistics over the last `N` values
+#[derive(Debug, Clone)]
+pub struct RollingStats<const N: usize> {
+    buffer: CircularBuffer<N, f64>,
+    count: usize,
+}
+//
+//
+impl<const N: usize> RollingStats<N> {
+    ///
+    /// Creates new empty [RollingStats]
+    pub fn new() -> Self {
+        Self {
+            buffer: CircularBuffer::new(),
+            count: 0,
+        }
+    }
+    ///
+    /// Adds the `value` into the window
+    pub fn add(&mut self, value: f64) {
+        self.buffer.push_back(value);
+        self.count += 1;
+    }
+    ///
+    /// Returns the total number of the values added
+    pub fn count(&self) -> usize {
+        self.count
+    }
+    ///
+    /// Returns the average of the values in the window
+    pub fn mean(&self) -> Option<f64> {
+        match self.buffer.is_empty() {
+            true => None,
+            false => Some(self.buffer.iter().sum::<f64>() / self.buffer.len() as f64),
+        }
+    }
+    ///
+    /// Returns the `p` percentile (0.0...1.0) of the values in the window, nearest rank method
+    pub fn percentile(&self, p: f64) -> Option<f64> {
+        if self.buffer.is_empty() {
+            return None;
+        }
+        let mut values: Vec<f64> = self.buffer.iter().copied().collect();
+        values.sort_by(|a, b| a.total_cmp(b));
+        let rank = (p.clamp(0.0, 1.0) * values.len() as f64).ceil() as usize;
+        Some(values[rank.saturating_sub(1)])
+    }
+    ///
+    /// Returns the maximum of the values in the window
+    pub fn max(&self) -> Option<f64> {
+        self.buffer.iter().copied().reduce(f64::max)
+    }
+}
+//
+//
+impl<const N: usize> Default for RollingStats<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,15 @@
+use std::time::Duration;
+
+///
+/// Diagnostics of the single pipeline stage on the single frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct StageRecord {
+    /// Name of the stage
+    pub stage: String,
+    /// Time elapsed by the stage including all the stages it wraps
+    pub elapsed: Duration,
+    /// Size of the resulting frame, px, None if stage failed
+    pub output: Option<(i32, i32)>,
+    /// Error of the stage, if occurred
+    pub error: Option<String>,
+}
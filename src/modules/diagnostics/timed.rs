@@ -0,0 +1,46 @@
+use std::time::Instant;
+use frdm_tools::{ContextRead, Eval, EvalResult, Image, ResultCtx};
+use opencv::core::MatTraitConst;
+
+use super::{diagnostics::Diagnostics, stage_record::StageRecord};
+
+///
+/// Records the duration, the output frame size and the error
+/// of the wrapped pipeline stage into the [Diagnostics]
+pub struct Timed {
+    stage: String,
+    ctx: Box<dyn Eval<Image, EvalResult>>,
+    diagnostics: Diagnostics,
+}
+//
+//
+impl Timed {
+    ///
+    /// Returns [Timed] new instance
+    /// - `stage` - name of the wrapped stage
+    pub fn new(stage: impl Into<String>, diagnostics: Diagnostics, ctx: impl Eval<Image, EvalResult> + 'static) -> Self {
+        Self {
+            stage: stage.into(),
+            ctx: Box::new(ctx),
+            diagnostics,
+        }
+    }
+}
+//
+//
+impl Eval<Image, EvalResult> for Timed {
+    fn eval(&self, frame: Image) -> EvalResult {
+        let t = Instant::now();
+        let result = self.ctx.eval(frame);
+        let elapsed = t.elapsed();
+        let (output, error) = match &result {
+            Ok(ctx) => {
+                let result: &ResultCtx = ctx.read();
+                (Some((result.frame.mat.cols(), result.frame.mat.rows())), None)
+            }
+            Err(err) => (None, Some(err.to_string())),
+        };
+        self.diagnostics.record(StageRecord { stage: self.stage.clone(), elapsed, output, error });
+        result
+    }
+}
@@ -8,4 +8,32 @@ pub trait Filter: std::fmt::Debug {
     ///
     /// - Updates state with value if value != inner
     fn add(&mut self, value: Self::Item) -> Option<Self::Item>;
+    ///
+    /// - Updates state with value measured at the `time`, seconds
+    /// - Filters not depending on the time just add the value
+    fn add_at(&mut self, value: Self::Item, _time: f64) -> Option<Self::Item> {
+        self.add(value)
+    }
+    ///
+    /// - Updates state when the value is missed
+    /// - Returns the predicted value, if filter is able to predict
+    fn predict(&mut self) -> Option<Self::Item> {
+        None
+    }
+    ///
+    /// - Updates state when the value is missed at the `time`, seconds
+    /// - Filters not depending on the time just predict
+    fn predict_at(&mut self, _time: f64) -> Option<Self::Item> {
+        self.predict()
+    }
+    ///
+    /// Returns the rate of the value change per sample, if filter estimates it
+    fn velocity(&self) -> Option<Self::Item> {
+        None
+    }
+    ///
+    /// Returns the total number of the values rejected by the filter
+    fn rejected(&self) -> usize {
+        0
+    }
 }
@@ -0,0 +1,45 @@
+use std::collections::VecDeque;
+use super::{filter::Filter, numeric::Numeric};
+
+///
+/// Moving average over the last `window` values
+/// 
+/// Until the window is filled, average of the values received so far is returned
+#[derive(Debug, Clone)]
+pub struct FilterAverage<T> {
+    window: usize,
+    buffer: VecDeque<T>,
+}
+//
+// 
+impl<T: Copy> FilterAverage<T> {
+    ///
+    /// Creates new FilterAverage<T>
+    /// - `window` - Size of the window, 1 or more
+    /// - `T` - Type of the Filter Item
+    pub fn new(window: usize, initial: Option<T>) -> Self {
+        let window = window.max(1);
+        let mut buffer = VecDeque::with_capacity(window);
+        if let Some(initial) = initial {
+            buffer.push_back(initial);
+        }
+        Self { window, buffer }
+    }
+}
+//
+//
+impl<T: Numeric> Filter for FilterAverage<T> {
+    type Item = T;
+    //
+    //
+    fn add(&mut self, value: Self::Item) -> Option<Self::Item> {
+        if self.buffer.len() == self.window {
+            self.buffer.pop_front();
+        }
+        self.buffer.push_back(value);
+        let len = self.buffer.len() as f64;
+        Some(T::from_components(|i| {
+            self.buffer.iter().map(|v| v.component(i)).sum::<f64>() / len
+        }))
+    }
+}
@@ -0,0 +1,71 @@
+use super::{filter::Filter, numeric::Numeric};
+
+///
+/// Applies the filter to the single component of the value (single axis of the position),
+/// other components are passed as is, their velocity is 0.0
+#[derive(Debug)]
+pub struct FilterAxis<T> {
+    axis: usize,
+    filter: Box<dyn Filter<Item = f64>>,
+    last: Option<T>,
+}
+//
+//
+impl<T: Numeric> FilterAxis<T> {
+    ///
+    /// Creates new [FilterAxis]
+    /// - `axis` - index of the filtered component, 0 - x, 1 - y
+    pub fn new(axis: usize, filter: Box<dyn Filter<Item = f64>>) -> Self {
+        Self { axis, filter, last: None }
+    }
+    ///
+    /// Returns the `value` with the component `axis` replaced by `filtered`
+    fn with_axis(&self, value: &T, filtered: f64) -> T {
+        T::from_components(|i| match i == self.axis {
+            true => filtered,
+            false => value.component(i),
+        })
+    }
+}
+//
+//
+impl<T: Numeric> Filter for FilterAxis<T> {
+    type Item = T;
+    //
+    //
+    fn add(&mut self, value: Self::Item) -> Option<Self::Item> {
+        self.last = Some(value);
+        self.filter.add(value.component(self.axis)).map(|filtered| self.with_axis(&value, filtered))
+    }
+    //
+    //
+    fn add_at(&mut self, value: Self::Item, time: f64) -> Option<Self::Item> {
+        self.last = Some(value);
+        self.filter.add_at(value.component(self.axis), time).map(|filtered| self.with_axis(&value, filtered))
+    }
+    //
+    //
+    fn predict(&mut self) -> Option<Self::Item> {
+        let last = self.last?;
+        self.filter.predict().map(|predicted| self.with_axis(&last, predicted))
+    }
+    //
+    //
+    fn predict_at(&mut self, time: f64) -> Option<Self::Item> {
+        let last = self.last?;
+        self.filter.predict_at(time).map(|predicted| self.with_axis(&last, predicted))
+    }
+    //
+    //
+    fn velocity(&self) -> Option<Self::Item> {
+        self.filter.velocity().map(|velocity| T::from_components(|i| match i == self.axis {
+            true => velocity,
+            false => 0.0,
+        }))
+    }
+    //
+    //
+    fn rejected(&self) -> usize {
+        self.filter.rejected()
+    }
+}
@@ -0,0 +1,68 @@
+use super::filter::Filter;
+
+///
+/// Chain of the filters, each next filter takes the output of the previous one
+/// 
+/// - If a filter has no output (value rejected or missed), the next filter is asked to predict
+/// - Velocity is taken from the last filter estimating it
+#[derive(Debug)]
+pub struct FilterChain<T> {
+    filters: Vec<Box<dyn Filter<Item = T>>>,
+}
+//
+//
+impl<T> FilterChain<T> {
+    ///
+    /// Creates new [FilterChain], empty chain passes the values as is
+    pub fn new(filters: Vec<Box<dyn Filter<Item = T>>>) -> Self {
+        Self { filters }
+    }
+    ///
+    /// Passes the `value` through all the filters
+    /// - `time` - the time of the value, seconds, if known
+    fn pass(&mut self, value: Option<T>, time: Option<f64>) -> Option<T> {
+        self.filters.iter_mut().fold(value, |value, filter| {
+            match (value, time) {
+                (Some(value), Some(time)) => filter.add_at(value, time),
+                (Some(value), None) => filter.add(value),
+                (None, Some(time)) => filter.predict_at(time),
+                (None, None) => filter.predict(),
+            }
+        })
+    }
+}
+//
+//
+impl<T: std::fmt::Debug> Filter for FilterChain<T> {
+    type Item = T;
+    //
+    //
+    fn add(&mut self, value: Self::Item) -> Option<Self::Item> {
+        self.pass(Some(value), None)
+    }
+    //
+    //
+    fn add_at(&mut self, value: Self::Item, time: f64) -> Option<Self::Item> {
+        self.pass(Some(value), Some(time))
+    }
+    //
+    //
+    fn predict(&mut self) -> Option<Self::Item> {
+        self.pass(None, None)
+    }
+    //
+    //
+    fn predict_at(&mut self, time: f64) -> Option<Self::Item> {
+        self.pass(None, Some(time))
+    }
+    //
+    //
+    fn velocity(&self) -> Option<Self::Item> {
+        self.filters.iter().rev().find_map(|filter| filter.velocity())
+    }
+    //
+    //
+    fn rejected(&self) -> usize {
+        self.filters.iter().map(|filter| filter.rejected()).sum()
+    }
+}
@@ -20,14 +20,8 @@ impl<T: Clone + std::fmt::Debug> FilterEmpty<T> {
 }
 //
 //
-impl Filter for FilterEmpty<i32> {
-    type Item = i32;
-    fn add(&mut self, value: Self::Item) -> Option<Self::Item> {
-        Some(value)
-    }
-}
-impl Filter for FilterEmpty<u16> {
-    type Item = u16;
+impl<T: Clone + std::fmt::Debug> Filter for FilterEmpty<T> {
+    type Item = T;
     fn add(&mut self, value: Self::Item) -> Option<Self::Item> {
         Some(value)
     }
@@ -0,0 +1,233 @@
+use std::marker::PhantomData;
+
+use super::{filter::Filter, numeric::Numeric, sample_time::SampleTime};
+
+///
+/// Motion model of the [FilterKalman]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KalmanModel {
+    /// Constant velocity, state: position, velocity
+    Velocity,
+    /// Constant acceleration, state: position, velocity, acceleration
+    Acceleration,
+}
+///
+/// Kalman filter
+/// 
+/// Each component of the value (each axis of the position) is filtered independently with the same motion model:
+/// - `process_noise` - variance of the unmodeled change per sample
+///   (acceleration for [KalmanModel::Velocity], jerk for [KalmanModel::Acceleration]),
+///   the bigger value the faster reaction and the less smoothing
+/// - `measurement_noise` - variance of the measured position, px^2
+/// - `max_predictions` - the number of missed values the position is predicted through,
+///   after that the filter is reset and waits for the new value
+/// 
+/// If the `sample_time` is set, values added with the time are predicted over the real number of samples elapsed,
+/// so the noises & velocity remain per nominal sample
+#[derive(Debug, Clone)]
+pub struct FilterKalman<T> {
+    model: KalmanModel,
+    process_noise: f64,
+    measurement_noise: f64,
+    max_predictions: usize,
+    axes: Vec<KalmanAxis>,
+    predictions: usize,
+    sample_time: Option<SampleTime>,
+    phantom: PhantomData<T>,
+}
+//
+//
+impl<T: Numeric> FilterKalman<T> {
+    ///
+    /// Creates new FilterKalman
+    pub fn new(model: KalmanModel, process_noise: f64, measurement_noise: f64, max_predictions: usize) -> Self {
+        Self {
+            model,
+            process_noise,
+            measurement_noise,
+            max_predictions,
+            axes: vec![],
+            predictions: 0,
+            sample_time: None,
+            phantom: PhantomData,
+        }
+    }
+    ///
+    /// Returns the filter, taking into account the real time between the values
+    /// - `sample_time` - nominal time between the values, seconds, the noises are tuned for
+    pub fn with_sample_time(self, sample_time: f64) -> Self {
+        Self {
+            sample_time: Some(SampleTime::new(sample_time)),
+            ..self
+        }
+    }
+    ///
+    /// Returns the number of the samples elapsed till the `time`, seconds
+    fn samples(&mut self, time: f64) -> f64 {
+        match &mut self.sample_time {
+            Some(sample_time) => sample_time.samples(time),
+            None => 1.0,
+        }
+    }
+    ///
+    /// Updates state with the `value`, measured `dt` samples after the previous one
+    fn update(&mut self, value: T, dt: f64) -> Option<T> {
+        match self.axes.is_empty() {
+            true => {
+                self.axes = (0..T::LEN).map(|i| KalmanAxis::new(value.component(i), self.measurement_noise)).collect();
+            }
+            false => {
+                for (i, axis) in self.axes.iter_mut().enumerate() {
+                    axis.predict(self.model, self.process_noise, dt);
+                    axis.update(value.component(i), self.measurement_noise);
+                }
+            }
+        }
+        self.predictions = 0;
+        self.value()
+    }
+    ///
+    /// Predicts the state `dt` samples after the previous one
+    fn extrapolate(&mut self, dt: f64) -> Option<T> {
+        if self.predictions >= self.max_predictions {
+            self.axes.clear();
+            return None;
+        }
+        for axis in &mut self.axes {
+            axis.predict(self.model, self.process_noise, dt);
+        }
+        if !self.axes.is_empty() {
+            self.predictions += 1;
+        }
+        self.value()
+    }
+    ///
+    /// Returns the current value
+    fn value(&self) -> Option<T> {
+        match self.axes.is_empty() {
+            true => None,
+            false => Some(T::from_components(|i| self.axes[i].state[0])),
+        }
+    }
+}
+//
+//
+impl<T: Numeric> Filter for FilterKalman<T> {
+    type Item = T;
+    //
+    //
+    fn add(&mut self, value: Self::Item) -> Option<Self::Item> {
+        self.update(value, 1.0)
+    }
+    //
+    //
+    fn add_at(&mut self, value: Self::Item, time: f64) -> Option<Self::Item> {
+        let dt = self.samples(time);
+        self.update(value, dt)
+    }
+    //
+    //
+    fn predict(&mut self) -> Option<Self::Item> {
+        self.extrapolate(1.0)
+    }
+    //
+    //
+    fn predict_at(&mut self, time: f64) -> Option<Self::Item> {
+        let dt = self.samples(time);
+        self.extrapolate(dt)
+    }
+    //
+    //
+    fn velocity(&self) -> Option<Self::Item> {
+        match self.axes.is_empty() {
+            true => None,
+            false => Some(T::from_components(|i| self.axes[i].state[1])),
+        }
+    }
+}
+///
+/// Kalman filter state of the single axis: position, velocity, acceleration
+#[derive(Debug, Clone)]
+struct KalmanAxis {
+    state: [f64; 3],
+    covariance: [[f64; 3]; 3],
+}
+//
+//
+impl KalmanAxis {
+    /// Initial variance of the unknown velocity & acceleration
+    const UNKNOWN_VARIANCE: f64 = 1.0e4;
+    ///
+    /// Returns [KalmanAxis] initialized by the first measured `value`
+    fn new(value: f64, measurement_noise: f64) -> Self {
+        Self {
+            state: [value, 0.0, 0.0],
+            covariance: [
+                [measurement_noise, 0.0, 0.0],
+                [0.0, Self::UNKNOWN_VARIANCE, 0.0],
+                [0.0, 0.0, Self::UNKNOWN_VARIANCE],
+            ],
+        }
+    }
+    ///
+    /// Predicts the state after `dt`
+    fn predict(&mut self, model: KalmanModel, process_noise: f64, dt: f64) {
+        let (f, q) = match model {
+            KalmanModel::Velocity => (
+                [
+                    [1.0, dt, 0.0],
+                    [0.0, 1.0, 0.0],
+                    [0.0, 0.0, 0.0],
+                ],
+                [
+                    [dt.powi(4) / 4.0, dt.powi(3) / 2.0, 0.0],
+                    [dt.powi(3) / 2.0, dt.powi(2), 0.0],
+                    [0.0, 0.0, 0.0],
+                ],
+            ),
+            KalmanModel::Acceleration => (
+                [
+                    [1.0, dt, dt.powi(2) / 2.0],
+                    [0.0, 1.0, dt],
+                    [0.0, 0.0, 1.0],
+                ],
+                [
+                    [dt.powi(6) / 36.0, dt.powi(5) / 12.0, dt.powi(4) / 6.0],
+                    [dt.powi(5) / 12.0, dt.powi(4) / 4.0, dt.powi(3) / 2.0],
+                    [dt.powi(4) / 6.0, dt.powi(3) / 2.0, dt.powi(2)],
+                ],
+            ),
+        };
+        self.state = std::array::from_fn(|i| (0..3).map(|j| f[i][j] * self.state[j]).sum());
+        // P = F * P * Ft + Q
+        let fpft = Self::mul(&Self::mul(&f, &self.covariance), &Self::transpose(&f));
+        self.covariance = std::array::from_fn(|i| std::array::from_fn(|j| fpft[i][j] + q[i][j] * process_noise));
+    }
+    ///
+    /// Corrects the state by the measured position `value`
+    fn update(&mut self, value: f64, measurement_noise: f64) {
+        let s = self.covariance[0][0] + measurement_noise;
+        let gain = [
+            self.covariance[0][0] / s,
+            self.covariance[1][0] / s,
+            self.covariance[2][0] / s,
+        ];
+        let residual = value - self.state[0];
+        for (state, gain) in self.state.iter_mut().zip(gain) {
+            *state += gain * residual;
+        }
+        // P = (I - K * H) * P
+        let p = self.covariance;
+        self.covariance = std::array::from_fn(|i| std::array::from_fn(|j| p[i][j] - gain[i] * p[0][j]));
+    }
+    ///
+    /// Returns `a` x `b`
+    fn mul(a: &[[f64; 3]; 3], b: &[[f64; 3]; 3]) -> [[f64; 3]; 3] {
+        std::array::from_fn(|i| std::array::from_fn(|j| (0..3).map(|k| a[i][k] * b[k][j]).sum()))
+    }
+    ///
+    /// Returns transposed `a`
+    fn transpose(a: &[[f64; 3]; 3]) -> [[f64; 3]; 3] {
+        std::array::from_fn(|i| std::array::from_fn(|j| a[j][i]))
+    }
+}
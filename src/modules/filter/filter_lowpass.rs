@@ -1,45 +1,67 @@
-use circular_buffer::CircularBuffer;
-use super::filter::Filter;
+use super::{filter::Filter, numeric::Numeric, sample_time::SampleTime};
 
 ///
+/// First order IIR low-pass filter
 /// 
+/// Calculates the new value according to the following formula:
+/// 
+/// val = old_calculated_val + (val - old_calculated_val) * alpha,
+/// 
+/// where alpha = dt / (RC + dt), RC = 1 / (2 * PI * cutoff),
+/// dt - `sample_time` or the real time between the values added with the time
 #[derive(Debug, Clone)]
-pub struct FilterLowPass<const N: usize, T> {
-    buffer: CircularBuffer<N, T>,
-    factor: f64,
+pub struct FilterLowPass<T> {
+    prev: Option<T>,
+    rc: f64,
+    sample_time: SampleTime,
 }
 //
 // 
-impl<T: Copy, const N: usize> FilterLowPass<N, T> {
+impl<T: Copy> FilterLowPass<T> {
     ///
-    /// Creates new FilterLowPass<const N: usize, T>
+    /// Creates new FilterLowPass<T>
     /// - `T` - Type of the Filter Item
-    pub fn new(initial: Option<T>, factor: f64) -> Self {
-        let mut buffer = CircularBuffer::<N, T>::new();
-        initial.map(|initial| {
-            buffer.push_back(initial);
-            initial
-        });
+    /// - `cutoff` - cutoff frequency, Hz
+    /// - `sample_time` - time between the values, seconds
+    pub fn new(initial: Option<T>, cutoff: f64, sample_time: f64) -> Self {
         Self {
-            buffer,
-            factor,
+            prev: initial,
+            rc: 1.0 / (2.0 * std::f64::consts::PI * cutoff),
+            sample_time: SampleTime::new(sample_time),
         }
     }
 }
 //
 //
-impl<const N: usize> Filter for FilterLowPass<N, i32> {
-    type Item = i32;
+impl<T: Numeric> FilterLowPass<T> {
+    ///
+    /// Updates state with `value`, measured `dt` seconds after the previous one
+    fn filter(&mut self, value: T, dt: f64) -> Option<T> {
+        let alpha = dt / (self.rc + dt);
+        let value = match self.prev {
+            Some(prev) => T::from_components(|i| {
+                let prev = prev.component(i);
+                prev + (value.component(i) - prev) * alpha
+            }),
+            None => value,
+        };
+        self.prev = Some(value);
+        Some(value)
+    }
+}
+//
+//
+impl<T: Numeric> Filter for FilterLowPass<T> {
+    type Item = T;
     //
     //
     fn add(&mut self, value: Self::Item) -> Option<Self::Item> {
-        let sum = self.buffer.iter().sum::<i32>() + value;
-        // let average = ((sum as f64) / ((self.buffer.len() + 1) as f64)).round() as i32;
-        let average = sum / ((self.buffer.len() as i32) + 1);
-        self.buffer.push_back(average);
-        match self.buffer.front() {
-            Some(v) => Some(*v),
-            None => None,
-        }
+        self.filter(value, self.sample_time.sample_time())
+    }
+    //
+    //
+    fn add_at(&mut self, value: Self::Item, time: f64) -> Option<Self::Item> {
+        let dt = self.sample_time.samples(time) * self.sample_time.sample_time();
+        self.filter(value, dt)
     }
 }
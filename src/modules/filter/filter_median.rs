@@ -0,0 +1,53 @@
+use std::collections::VecDeque;
+use super::{filter::Filter, numeric::Numeric};
+
+///
+/// Moving median over the last `window` values, calculated for each component separately
+/// 
+/// - Removes single spikes without shifting the output toward them
+/// - On the even number of values the average of two middle values is returned
+#[derive(Debug, Clone)]
+pub struct FilterMedian<T> {
+    window: usize,
+    buffer: VecDeque<T>,
+}
+//
+// 
+impl<T: Copy> FilterMedian<T> {
+    ///
+    /// Creates new FilterMedian<T>
+    /// - `window` - Size of the window, 1 or more
+    /// - `T` - Type of the Filter Item
+    pub fn new(window: usize, initial: Option<T>) -> Self {
+        let window = window.max(1);
+        let mut buffer = VecDeque::with_capacity(window);
+        if let Some(initial) = initial {
+            buffer.push_back(initial);
+        }
+        Self { window, buffer }
+    }
+}
+//
+//
+impl<T: Numeric> Filter for FilterMedian<T> {
+    type Item = T;
+    //
+    //
+    fn add(&mut self, value: Self::Item) -> Option<Self::Item> {
+        if self.buffer.len() == self.window {
+            self.buffer.pop_front();
+        }
+        self.buffer.push_back(value);
+        let mut values = Vec::with_capacity(self.buffer.len());
+        Some(T::from_components(|i| {
+            values.clear();
+            values.extend(self.buffer.iter().map(|v| v.component(i)));
+            values.sort_by(|a, b| a.total_cmp(b));
+            let mid = values.len() / 2;
+            match values.len() % 2 {
+                0 => (values[mid - 1] + values[mid]) / 2.0,
+                _ => values[mid],
+            }
+        }))
+    }
+}
@@ -0,0 +1,141 @@
+use super::{filter::Filter, numeric::Numeric};
+///
+/// Maximum allowed speed of the value
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MaxSpeed {
+    /// Units per frame, missed frames (`predict`) are counted as well
+    PerFrame(f64),
+    /// Units per second, the time of the values passed to `add_at` is used,
+    /// values without the time are not checked
+    PerSecond(f64),
+}
+///
+/// Last value and the moment of its measurement
+#[derive(Debug, Clone, Copy)]
+struct Sample<T> {
+    value: T,
+    frame: usize,
+    time: Option<f64>,
+}
+///
+/// Rejects the sudden jumps of the value
+///
+/// - The value is rejected if the distance to the last accepted value exceeds `max_speed` * elapsed
+/// - Rejected values are checked for consistency with each other,
+///   after `relock` consistent values in a row the filter re-locks to the new location
+/// - Rejected value has no output, so the next filter in the chain predicts it
+#[derive(Debug, Clone)]
+pub struct FilterOutlier<T> {
+    max_speed: MaxSpeed,
+    relock: usize,
+    frame: usize,
+    last: Option<Sample<T>>,
+    candidate: Option<Sample<T>>,
+    consistent: usize,
+    rejections: usize,
+    rejected: usize,
+}
+//
+//
+impl<T: Numeric> FilterOutlier<T> {
+    ///
+    /// Creates new FilterOutlier
+    /// - `max_speed` - maximum allowed speed of the value
+    /// - `relock` - number of the consistent values in a row, after which the new location is accepted
+    pub fn new(max_speed: MaxSpeed, relock: usize) -> Self {
+        Self {
+            max_speed,
+            relock: relock.max(1),
+            frame: 0,
+            last: None,
+            candidate: None,
+            consistent: 0,
+            rejections: 0,
+            rejected: 0,
+        }
+    }
+    ///
+    /// Returns the number of the consecutive rejected values
+    pub fn rejections(&self) -> usize {
+        self.rejections
+    }
+    ///
+    /// Returns true if the `sample` is reachable from the `from` at the `max_speed`
+    fn reachable(&self, from: &Sample<T>, sample: &Sample<T>) -> bool {
+        let allowed = match (self.max_speed, from.time, sample.time) {
+            (MaxSpeed::PerFrame(speed), _, _) => speed * (sample.frame - from.frame) as f64,
+            (MaxSpeed::PerSecond(speed), Some(from), Some(time)) => speed * (time - from).abs(),
+            (MaxSpeed::PerSecond(_), _, _) => return true,
+        };
+        Self::distance(&from.value, &sample.value) <= allowed
+    }
+    ///
+    /// Returns euclidean distance between `a` and `b`
+    fn distance(a: &T, b: &T) -> f64 {
+        (0..T::LEN)
+            .map(|i| (a.component(i) - b.component(i)).powi(2))
+            .sum::<f64>()
+            .sqrt()
+    }
+    ///
+    /// Accepts the `sample` as the new location
+    fn accept(&mut self, sample: Sample<T>) -> Option<T> {
+        self.last = Some(sample);
+        self.candidate = None;
+        self.consistent = 0;
+        self.rejections = 0;
+        Some(sample.value)
+    }
+    ///
+    /// Checks and accepts or rejects the `value`
+    fn check(&mut self, value: T, time: Option<f64>) -> Option<T> {
+        self.frame += 1;
+        let sample = Sample { value, frame: self.frame, time };
+        let last = match self.last {
+            Some(last) => last,
+            None => return self.accept(sample),
+        };
+        if self.reachable(&last, &sample) {
+            return self.accept(sample);
+        }
+        self.consistent = match self.candidate {
+            Some(candidate) if self.reachable(&candidate, &sample) => self.consistent + 1,
+            _ => 1,
+        };
+        self.candidate = Some(sample);
+        if self.consistent >= self.relock {
+            log::debug!("FilterOutlier.check | Re-locked after {} rejections at {:?}", self.rejections, value);
+            return self.accept(sample);
+        }
+        self.rejections += 1;
+        self.rejected += 1;
+        log::debug!("FilterOutlier.check | Rejected {:?}, rejections in a row: {}", value, self.rejections);
+        None
+    }
+}
+//
+//
+impl<T: Numeric> Filter for FilterOutlier<T> {
+    type Item = T;
+    //
+    //
+    fn add(&mut self, value: Self::Item) -> Option<Self::Item> {
+        self.check(value, None)
+    }
+    //
+    //
+    fn add_at(&mut self, value: Self::Item, time: f64) -> Option<Self::Item> {
+        self.check(value, Some(time))
+    }
+    //
+    //
+    fn predict(&mut self) -> Option<Self::Item> {
+        self.frame += 1;
+        None
+    }
+    //
+    //
+    fn rejected(&self) -> usize {
+        self.rejected
+    }
+}
@@ -1,15 +1,20 @@
-use super::filter::Filter;
+use super::{filter::Filter, numeric::Numeric, sample_time::SampleTime};
 ///
 /// Smoothing input  
 /// 
 /// Calculates the new value according to the following formula:
 /// 
 /// val = old_calculated_val = (val - old_calculated_val) / k
+/// 
+/// If the `sample_time` is set, values added with the time are weighted by the real time elapsed:
+/// 
+/// val = old_calculated_val = (val - old_calculated_val) * (1 - (1 - 1 / k) ^ samples)
 #[derive(Debug, Clone)]
 pub struct FilterSmooth<T> {
     prev: Option<T>,
     // factor: f64,
     factor_inv: f64,
+    sample_time: Option<SampleTime>,
 }
 //
 // 
@@ -22,19 +27,31 @@ impl<T: Copy> FilterSmooth<T> {
             prev: initial,
             // factor,
             factor_inv: 1.0 / factor,
+            sample_time: None,
+        }
+    }
+    ///
+    /// Returns the filter, taking into account the real time between the values
+    /// - `sample_time` - nominal time between the values, seconds, the `factor` is tuned for
+    pub fn with_sample_time(self, sample_time: f64) -> Self {
+        Self {
+            sample_time: Some(SampleTime::new(sample_time)),
+            ..self
         }
     }
 }
 //
 //
-impl Filter for FilterSmooth<i32> {
-    type Item = i32;
-    //
-    //
-    fn add(&mut self, value: Self::Item) -> Option<Self::Item> {
+impl<T: Numeric> FilterSmooth<T> {
+    ///
+    /// Updates state with `value`, weighted by `weight`
+    fn smooth(&mut self, value: T, weight: f64) -> Option<T> {
         match self.prev {
             Some(prev) => {
-                let value = (prev as f64 + ((value as f64) - (prev as f64)) * self.factor_inv).round() as i32;
+                let value = T::from_components(|i| {
+                    let prev = prev.component(i);
+                    prev + (value.component(i) - prev) * weight
+                });
                 self.prev.replace(value);
                 Some(value)
             }
@@ -45,21 +62,25 @@ impl Filter for FilterSmooth<i32> {
         }
     }
 }
-impl Filter for FilterSmooth<u16> {
-    type Item = u16;
+//
+//
+impl<T: Numeric> Filter for FilterSmooth<T> {
+    type Item = T;
     //
     //
     fn add(&mut self, value: Self::Item) -> Option<Self::Item> {
-        match self.prev {
-            Some(prev) => {
-                let value = (prev as f64 + ((value as f64) - (prev as f64)) * self.factor_inv).round() as u16;
-                self.prev.replace(value);
-                Some(value)
-            }
-            None => {
-                self.prev.replace(value);
-                Some(value)
+        self.smooth(value, self.factor_inv)
+    }
+    //
+    //
+    fn add_at(&mut self, value: Self::Item, time: f64) -> Option<Self::Item> {
+        match &mut self.sample_time {
+            Some(sample_time) => {
+                let samples = sample_time.samples(time);
+                let weight = 1.0 - (1.0 - self.factor_inv).powf(samples);
+                self.smooth(value, weight)
             }
+            None => self.add(value),
         }
     }
 }
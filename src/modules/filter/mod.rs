@@ -1,9 +1,27 @@
+mod filter_average;
+mod filter_axis;
+mod filter_chain;
 mod filter_empty;
+mod filter_kalman;
 mod filter_lowpass;
+mod filter_median;
+mod filter_outlier;
 mod filter_smooth;
 mod filter;
+mod numeric;
+mod position;
+mod sample_time;
 
+pub use filter_average::*;
+pub use filter_axis::*;
+pub use filter_chain::*;
 pub use filter_empty::*;
+pub use filter_kalman::*;
 pub use filter_lowpass::*;
+pub use filter_median::*;
+pub use filter_outlier::*;
 pub use filter_smooth::*;
 pub use filter::*;
+pub use numeric::*;
+pub use position::*;
+pub use sample_time::*;
@@ -0,0 +1,89 @@
+use super::position::Position;
+
+///
+/// Primitive number, integer or float
+pub trait Scalar: Copy + std::fmt::Debug + PartialEq {
+    ///
+    /// Returns the value converted to f64
+    fn to_f64(self) -> f64;
+    ///
+    /// Returns the value converted from f64, integers are rounded and saturated
+    fn from_f64(value: f64) -> Self;
+}
+//
+//
+macro_rules! impl_scalar_int {
+    ($($t:ty),*) => {
+        $(
+            impl Scalar for $t {
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+                fn from_f64(value: f64) -> Self {
+                    value.round() as $t
+                }
+            }
+        )*
+    };
+}
+impl_scalar_int!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+//
+//
+impl Scalar for f32 {
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+}
+//
+//
+impl Scalar for f64 {
+    fn to_f64(self) -> f64 {
+        self
+    }
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+}
+///
+/// Value to be filtered, consists of one or more [Scalar] components,
+/// each component is filtered independently
+pub trait Numeric: Copy + std::fmt::Debug + PartialEq {
+    /// Number of the components
+    const LEN: usize;
+    ///
+    /// Returns the component `i` converted to f64
+    fn component(&self, i: usize) -> f64;
+    ///
+    /// Returns the value built from the components returned by `f`
+    fn from_components(f: impl FnMut(usize) -> f64) -> Self;
+}
+//
+//
+impl<T: Scalar> Numeric for T {
+    const LEN: usize = 1;
+    fn component(&self, _: usize) -> f64 {
+        self.to_f64()
+    }
+    fn from_components(mut f: impl FnMut(usize) -> f64) -> Self {
+        T::from_f64(f(0))
+    }
+}
+//
+//
+impl<T: Scalar> Numeric for Position<T> {
+    const LEN: usize = 2;
+    fn component(&self, i: usize) -> f64 {
+        match i {
+            0 => self.x.to_f64(),
+            _ => self.y.to_f64(),
+        }
+    }
+    fn from_components(mut f: impl FnMut(usize) -> f64) -> Self {
+        let x = T::from_f64(f(0));
+        let y = T::from_f64(f(1));
+        Position::new(x, y)
+    }
+}
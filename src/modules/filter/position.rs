@@ -0,0 +1,17 @@
+use serde::Serialize;
+///
+/// Position on the plane
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize)]
+pub struct Position<T> {
+    pub x: T,
+    pub y: T,
+}
+//
+//
+impl<T> Position<T> {
+    ///
+    /// Returns [Position] new instance
+    pub fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
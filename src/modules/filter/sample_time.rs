@@ -0,0 +1,36 @@
+///
+/// Converts the time of the values into the number of the samples elapsed
+/// 
+/// Used by the filters, tuned for the constant sample rate,
+/// to take into account jitter & dropped frames
+#[derive(Debug, Clone)]
+pub struct SampleTime {
+    sample_time: f64,
+    last: Option<f64>,
+}
+//
+//
+impl SampleTime {
+    ///
+    /// Creates new [SampleTime]
+    /// - `sample_time` - nominal time between the values, seconds
+    pub fn new(sample_time: f64) -> Self {
+        Self { sample_time, last: None }
+    }
+    ///
+    /// Returns nominal time between the values, seconds
+    pub fn sample_time(&self) -> f64 {
+        self.sample_time
+    }
+    ///
+    /// Returns the number of the samples elapsed since the previous `time`, seconds
+    /// - 1.0 on the first call, or if the time hasn't increased
+    pub fn samples(&mut self, time: f64) -> f64 {
+        let samples = match self.last {
+            Some(last) if time > last => (time - last) / self.sample_time,
+            _ => 1.0,
+        };
+        self.last = Some(time);
+        samples
+    }
+}
@@ -0,0 +1,90 @@
+use crate::modules::{Position, Position3};
+
+///
+/// Intrinsic parameters of the camera, px
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Intrinsics {
+    /// Focal length along the X axis
+    pub fx: f64,
+    /// Focal length along the Y axis
+    pub fy: f64,
+    /// Principal point X
+    pub cx: f64,
+    /// Principal point Y
+    pub cy: f64,
+}
+///
+/// Pinhole model of the calibrated camera
+///
+/// - `rotation` & `translation` transform the world point into the camera frame: `Xc = R * Xw + t`
+/// - Projection matrix of the camera: `P = K * [R | t]`
+#[derive(Debug, Clone, PartialEq)]
+pub struct CameraModel {
+    rotation: [[f64; 3]; 3],
+    translation: [f64; 3],
+    projection: [[f64; 4]; 3],
+}
+//
+//
+impl CameraModel {
+    ///
+    /// Returns [CameraModel] new instance
+    /// - `rotation` - world to camera rotation matrix, row-major
+    /// - `translation` - world to camera translation, world units
+    pub fn new(intrinsics: Intrinsics, rotation: [[f64; 3]; 3], translation: [f64; 3]) -> Self {
+        let k = [
+            [intrinsics.fx, 0.0, intrinsics.cx],
+            [0.0, intrinsics.fy, intrinsics.cy],
+            [0.0, 0.0, 1.0],
+        ];
+        let mut projection = [[0.0; 4]; 3];
+        for (row, k) in k.iter().enumerate() {
+            for col in 0..3 {
+                projection[row][col] = (0..3).map(|i| k[i] * rotation[i][col]).sum();
+            }
+            projection[row][3] = (0..3).map(|i| k[i] * translation[i]).sum();
+        }
+        Self { rotation, translation, projection }
+    }
+    ///
+    /// Returns the rotation matrix of the Rodrigues `vector`, the direction is the axis, the length is the angle, rad
+    pub fn rodrigues(vector: [f64; 3]) -> [[f64; 3]; 3] {
+        let angle = vector.iter().map(|v| v * v).sum::<f64>().sqrt();
+        if angle < f64::EPSILON {
+            return [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+        }
+        let [x, y, z] = vector.map(|v| v / angle);
+        let (sin, cos) = angle.sin_cos();
+        let c = 1.0 - cos;
+        [
+            [cos + x * x * c, x * y * c - z * sin, x * z * c + y * sin],
+            [y * x * c + z * sin, cos + y * y * c, y * z * c - x * sin],
+            [z * x * c - y * sin, z * y * c + x * sin, cos + z * z * c],
+        ]
+    }
+    ///
+    /// Returns the projection matrix `P = K * [R | t]`
+    pub fn projection(&self) -> &[[f64; 4]; 3] {
+        &self.projection
+    }
+    ///
+    /// Returns the world `point` in the camera frame
+    pub fn to_camera(&self, point: Position3<f64>) -> Position3<f64> {
+        let p = [point.x, point.y, point.z];
+        let [x, y, z] = [0, 1, 2].map(|row| {
+            (0..3).map(|i| self.rotation[row][i] * p[i]).sum::<f64>() + self.translation[row]
+        });
+        Position3::new(x, y, z)
+    }
+    ///
+    /// Returns the projection of the world `point` on the frame, px
+    /// - None if the point is behind the camera
+    pub fn project(&self, point: Position3<f64>) -> Option<Position<f64>> {
+        let p = [point.x, point.y, point.z, 1.0];
+        let [u, v, w] = self.projection.map(|row| (0..4).map(|i| row[i] * p[i]).sum::<f64>());
+        match w > f64::EPSILON {
+            true => Some(Position::new(u / w, v / w)),
+            false => None,
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use sal_core::dbg::Dbg;
+use sal_sync::services::{conf::{ConfTree, ConfTreeGet}, entity::Name};
+
+use crate::modules::{Calibration, CameraModel, Intrinsics};
+
+///
+/// ## The calibration of the camera, required for the fusion
+///
+/// ### Conf example
+/// ```yaml
+/// camera Camera1:
+///     intrinsics:                 # optional if the `calibration` file given, px
+///         fx: 1400.0
+///         fy: 1400.0
+///         cx: 600.0
+///         cy: 400.0
+///     extrinsics:                 # world to camera transformation: `Xc = R * Xw + t`
+///         rotation: [0.0, 0.1, 0.0]       # Rodrigues vector, rad, or 3x3 matrix, row-major
+///         translation: [-0.5, 0.0, 0.0]   # world units, m
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CameraModelConf {
+    pub name: Name,
+    pub intrinsics: Intrinsics,
+    /// World to camera rotation matrix
+    pub rotation: [[f64; 3]; 3],
+    /// World to camera translation, m
+    pub translation: [f64; 3],
+}
+//
+//
+impl CameraModelConf {
+    ///
+    /// Returns [CameraModelConf] built from the `extrinsics` section
+    /// - `intrinsics` - of the camera, from the `intrinsics` section or the `calibration`
+    pub fn new(
+        parent: impl Into<String>,
+        intrinsics: Intrinsics,
+        extrinsics: ConfTree,
+    ) -> Self {
+        let parent = parent.into();
+        let me = "CameraModelConf";
+        let dbg = Dbg::new(&parent, me);
+        let name = Name::new(parent, me);
+        log::trace!("{dbg}.new | name: {:?}", name);
+        log::trace!("{}.new | intrinsics: {:?}", dbg, intrinsics);
+        let rotation: ConfTree = extrinsics.get("rotation").expect(&format!("{dbg}.new | 'extrinsics.rotation' - not found or wrong configuration"));
+        let rotation = Self::values(&dbg, "rotation", &rotation);
+        let rotation = match rotation.len() {
+            3 => CameraModel::rodrigues([rotation[0], rotation[1], rotation[2]]),
+            9 => std::array::from_fn(|row| std::array::from_fn(|col| rotation[row * 3 + col])),
+            len => panic!("{dbg}.new | 'extrinsics.rotation' must be Rodrigues vector (3 values) or matrix (9 values), found {len} values"),
+        };
+        log::trace!("{}.new | rotation: {:?}", dbg, rotation);
+        let translation: ConfTree = extrinsics.get("translation").expect(&format!("{dbg}.new | 'extrinsics.translation' - not found or wrong configuration"));
+        let translation = Self::values(&dbg, "translation", &translation);
+        let translation = match translation.len() {
+            3 => [translation[0], translation[1], translation[2]],
+            len => panic!("{dbg}.new | 'extrinsics.translation' must have 3 values, found {len}"),
+        };
+        log::trace!("{}.new | translation: {:?}", dbg, translation);
+        Self {
+            name,
+            intrinsics,
+            rotation,
+            translation,
+        }
+    }
+    ///
+    /// Returns the [Intrinsics] of the camera:
+    /// - from the `intrinsics` section if given
+    /// - from the `calibration` otherwise
+    pub fn intrinsics(parent: impl Into<String>, intrinsics: Option<ConfTree>, calibration: Option<&Calibration>) -> Option<Intrinsics> {
+        let dbg = Dbg::new(parent, "CameraModelConf");
+        match (intrinsics, calibration) {
+            (Some(intrinsics), _) => {
+                let [fx, fy, cx, cy] = ["fx", "fy", "cx", "cy"].map(|key| {
+                    let value: f64 = intrinsics.get(key).expect(&format!("{dbg}.intrinsics | 'intrinsics.{key}' - not found or wrong configuration"));
+                    value
+                });
+                Some(Intrinsics { fx, fy, cx, cy })
+            }
+            (None, Some(calibration)) => Some(calibration.intrinsics()),
+            (None, None) => None,
+        }
+    }
+    ///
+    /// Returns the [CameraModel] of the configured calibration
+    pub fn model(&self) -> CameraModel {
+        CameraModel::new(self.intrinsics, self.rotation, self.translation)
+    }
+    ///
+    /// Returns the numbers of the `key` sequence
+    fn values(dbg: &Dbg, key: &str, conf: &ConfTree) -> Vec<f64> {
+        let values = conf.conf.as_sequence().expect(&format!("{dbg}.new | 'extrinsics.{key}' must be a list"));
+        values.iter().enumerate().map(|(i, value)| {
+            value.as_f64().expect(&format!("{dbg}.new | 'extrinsics.{key}[{i}]' wrong number: {:?}", value))
+        }).collect()
+    }
+}
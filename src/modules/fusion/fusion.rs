@@ -0,0 +1,117 @@
+use std::time::Instant;
+
+use crate::modules::{CameraId, CameraModel, FusionConf, Target, Triangulation, WorldPosition};
+
+///
+/// Fuses the synchronized targets of the cameras into the single 3D position
+///
+/// - The latest target of each camera is held until the targets of all the cameras are received,
+///   the frame timestamps of the targets to be fused must be within `sync`,
+///   so the clocks of the cameras must be synchronized (PTP),
+///   then the world position is triangulated and published with the 2D position of the first camera
+/// - If some camera loses the target, the views of the others are published as the single camera positions:
+///   - when the next target of the same camera received
+///   - when the target of the other camera received, its frame is later then `sync`
+///   - after waiting `sync` by [Fusion::poll]
+/// - If the triangulation fails or its reprojection error is more then `max-error`,
+///   the views of all the cameras are published as the single camera positions, in the order of the frames
+/// - The fused position has no sway, the sway is of the single camera positions only
+#[derive(Debug)]
+pub struct Fusion {
+    conf: FusionConf,
+    triangulation: Triangulation,
+    views: Vec<View>,
+    fused: usize,
+    fallback: usize,
+}
+///
+/// The target of the camera waiting for the others
+#[derive(Debug, Clone, Copy)]
+struct View {
+    target: Target,
+    received: Instant,
+}
+//
+//
+impl Fusion {
+    ///
+    /// Returns [Fusion] new instance
+    /// - `models` - calibrated cameras to be fused, the first one is the main, its 2D position is published
+    pub fn new(conf: FusionConf, models: Vec<(CameraId, CameraModel)>) -> Self {
+        Self {
+            conf,
+            triangulation: Triangulation::new(models),
+            views: vec![],
+            fused: 0,
+            fallback: 0,
+        }
+    }
+    ///
+    /// Returns the number of the fused positions published
+    pub fn fused(&self) -> usize {
+        self.fused
+    }
+    ///
+    /// Returns the number of the single camera positions published
+    pub fn fallback(&self) -> usize {
+        self.fallback
+    }
+    ///
+    /// Adds the `target` received `now`, returns the positions to be published, in the order of the frames
+    pub fn add(&mut self, target: Target, now: Instant) -> Vec<Target> {
+        let sync = self.conf.sync.as_secs_f64();
+        let (expired, views): (Vec<View>, Vec<View>) = self.views.iter()
+            .partition(|view| view.target.camera == target.camera || (target.time - view.target.time).abs() > sync);
+        self.views = views;
+        self.views.push(View { target, received: now });
+        let mut published: Vec<Target> = expired.into_iter()
+            .map(|view| {
+                log::trace!("Fusion.add | No views of the other cameras for {:?}", view.target.camera);
+                self.single(view.target)
+            })
+            .collect();
+        let complete = self.triangulation.cameras().all(|camera| self.views.iter().any(|view| view.target.camera == camera));
+        if !complete {
+            return published;
+        }
+        let held = std::mem::take(&mut self.views);
+        let views: Vec<_> = held.iter().map(|view| (view.target.camera, view.target.position)).collect();
+        let main = self.triangulation.cameras()
+            .find_map(|camera| held.iter().find(|view| view.target.camera == camera))
+            .map(|view| view.target)
+            .unwrap_or(target);
+        match self.triangulation.triangulate(&views) {
+            Some((world, error)) if error <= self.conf.max_error => {
+                self.fused += 1;
+                // Sway of the main camera is of its own world plane, not of the fused position
+                published.push(Target { world: Some(WorldPosition::Space(world)), sway: None, ..main });
+            }
+            result => {
+                log::debug!("Fusion.add | Can't be fused, triangulation: {:?}", result);
+                let mut targets: Vec<Target> = held.into_iter().map(|view| view.target).collect();
+                targets.sort_by(|a, b| a.time.total_cmp(&b.time));
+                for target in targets {
+                    published.push(self.single(target));
+                }
+            }
+        }
+        published
+    }
+    ///
+    /// Returns all the views waiting more then `sync` as the single camera positions, in the order they were received
+    pub fn poll(&mut self, now: Instant) -> Vec<Target> {
+        let sync = self.conf.sync;
+        let (expired, views): (Vec<View>, Vec<View>) = self.views.iter()
+            .partition(|view| now.saturating_duration_since(view.received) > sync);
+        self.views = views;
+        expired.into_iter()
+            .map(|view| self.single(view.target))
+            .collect()
+    }
+    ///
+    /// Returns the `target` of the single camera, keeps its own world position, if transformed
+    fn single(&mut self, target: Target) -> Target {
+        self.fallback += 1;
+        target
+    }
+}
@@ -0,0 +1,64 @@
+use std::time::Duration;
+use sal_core::dbg::Dbg;
+use sal_sync::services::{conf::{ConfTree, ConfTreeGet}, entity::Name};
+
+///
+/// ## The configuration parameters of the fusion of the cameras into the single 3D position
+///
+/// The `intrinsics` & `extrinsics` and the `world` of all the cameras of the service are required,
+/// the single camera fallback position is written in the world units as well as the fused one
+///
+/// ### Conf example
+/// ```yaml
+/// fusion:                         # optional, fusion of the cameras into the single 3D position
+///     sync: 40 ms                 # optional, max time between the views of the cameras to be fused, default 40 ms
+///     max-error: 5.0              # optional, max reprojection error of the fused position, px, default 5.0
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct FusionConf {
+    pub name: Name,
+    /// Max time between the views of the cameras to be fused,
+    /// the view is published as 2D position if the other cameras have no view this time
+    pub sync: Duration,
+    /// Max reprojection error of the fused position, px
+    pub max_error: f64,
+}
+//
+//
+impl FusionConf {
+    ///
+    /// Returns [FusionConf] built from `ConfTree`:
+    pub fn new(
+        parent: impl Into<String>,
+        conf: ConfTree,
+    ) -> Self {
+        let parent = parent.into();
+        let me = "FusionConf";
+        let dbg = Dbg::new(&parent, me);
+        let name = Name::new(parent, me);
+        log::trace!("{dbg}.new | name: {:?}", name);
+        let sync = conf.get_duration("sync").unwrap_or(Duration::from_millis(40));
+        log::trace!("{}.new | sync: {:?}", dbg, sync);
+        let max_error: f64 = conf.get("max-error").unwrap_or(5.0);
+        log::trace!("{}.new | max-error: {:?}", dbg, max_error);
+        if max_error <= 0.0 {
+            panic!("{dbg}.new | 'max-error' must be positive, found: {max_error}");
+        }
+        Self {
+            name,
+            sync,
+            max_error,
+        }
+    }
+}
+//
+//
+impl Default for FusionConf {
+    fn default() -> Self {
+        Self {
+            name: Name::new("", "FusionConf"),
+            sync: Duration::from_millis(40),
+            max_error: 5.0,
+        }
+    }
+}
@@ -0,0 +1,13 @@
+mod camera_model_conf;
+mod camera_model;
+mod fusion_conf;
+mod fusion;
+mod position3;
+mod triangulation;
+
+pub use camera_model_conf::*;
+pub use camera_model::*;
+pub use fusion_conf::*;
+pub use fusion::*;
+pub use position3::*;
+pub use triangulation::*;
@@ -0,0 +1,18 @@
+use serde::Serialize;
+///
+/// Position in the space
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize)]
+pub struct Position3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+//
+//
+impl<T> Position3<T> {
+    ///
+    /// Returns [Position3] new instance
+    pub fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
@@ -0,0 +1,65 @@
+use crate::modules::{solve_linear, CameraId, CameraModel, Position, Position3};
+
+///
+/// Triangulates the 3D point from its projections on the calibrated cameras
+///
+/// - Linear least squares (DLT) over all the views, two rows per view: `u * P3 - P1`, `v * P3 - P2`
+/// - The point must be in front of all the cameras
+#[derive(Debug, Clone)]
+pub struct Triangulation {
+    models: Vec<(CameraId, CameraModel)>,
+}
+//
+//
+impl Triangulation {
+    ///
+    /// Returns [Triangulation] new instance
+    pub fn new(models: Vec<(CameraId, CameraModel)>) -> Self {
+        Self { models }
+    }
+    ///
+    /// Returns the cameras the point is triangulated by
+    pub fn cameras(&self) -> impl Iterator<Item = CameraId> + '_ {
+        self.models.iter().map(|(camera, _)| *camera)
+    }
+    ///
+    /// Returns the world point & the max reprojection error of the `views`, px
+    /// - `views` - projections of the point on the frames of the cameras, px
+    /// - None if less then two known views or the views are degenerate
+    pub fn triangulate(&self, views: &[(CameraId, Position<f64>)]) -> Option<(Position3<f64>, f64)> {
+        let views: Vec<_> = views.iter()
+            .filter_map(|(camera, position)| {
+                self.models.iter().find(|(id, _)| id == camera).map(|(_, model)| (model, *position))
+            })
+            .collect();
+        if views.len() < 2 {
+            return None;
+        }
+        // Normal equations `A^T A x = A^T b` of the rows `a0 x + a1 y + a2 z = -a3`
+        let mut ata = [[0.0; 3]; 3];
+        let mut atb = [0.0; 3];
+        for (model, position) in &views {
+            let p = model.projection();
+            for (value, row) in [(position.x, 0), (position.y, 1)] {
+                let a: [f64; 4] = std::array::from_fn(|i| value * p[2][i] - p[row][i]);
+                for i in 0..3 {
+                    for j in 0..3 {
+                        ata[i][j] += a[i] * a[j];
+                    }
+                    atb[i] -= a[i] * a[3];
+                }
+            }
+        }
+        let [x, y, z] = solve_linear(ata, atb)?;
+        let point = Position3::new(x, y, z);
+        let mut error: f64 = 0.0;
+        for (model, position) in &views {
+            if model.to_camera(point).z <= 0.0 {
+                return None;
+            }
+            let projected = model.project(point)?;
+            error = error.max(((projected.x - position.x).powi(2) + (projected.y - position.y).powi(2)).sqrt());
+        }
+        Some((point, error))
+    }
+}
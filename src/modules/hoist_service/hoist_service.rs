@@ -0,0 +1,212 @@
+use std::{io, net::{TcpStream, ToSocketAddrs}, sync::{atomic::{AtomicBool, Ordering}, Arc}, time::{Duration, Instant}};
+use sal_core::{dbg::Dbg, error::Error};
+use sal_sync::{services::{entity::{Name, Object}, Service, ServiceWaiting}, sync::Handles, thread_pool::Scheduler};
+
+use crate::modules::{FunctionCode, HoistServiceConf, Metrics, ReadRegisters, Register, RopeLength};
+
+/// Delay before the reconnection to the PLC
+const RECONNECT_DELAY: Duration = Duration::from_millis(3000);
+
+///
+/// Reads the current length of the hoist rope from the PLC over Modbus TCP/IP
+///
+/// - The rope length register is polled with the `period`
+/// - The length is published into the shared [RopeLength] timestamped by the middle of the request,
+///   it becomes unknown if the PLC is not responding
+pub struct HoistService {
+    name: Name,
+    conf: HoistServiceConf,
+    rope: RopeLength,
+    metrics: Metrics,
+    scheduler: Scheduler,
+    handles: Arc<Handles<()>>,
+    exit: Arc<AtomicBool>,
+    dbg: Dbg,
+}
+//
+//
+impl HoistService {
+    ///
+    /// Crteates [HoistService] new instance
+    /// - `rope` - the rope length read is published into
+    pub fn new(
+        parent: impl Into<String>,
+        conf: HoistServiceConf,
+        rope: RopeLength,
+        metrics: Metrics,
+        scheduler: Scheduler,
+    ) -> Self {
+        let name = Name::new(parent, "HoistService");
+        let dbg = Dbg::new(name.parent(), name.me());
+        Self {
+            name,
+            conf,
+            rope,
+            metrics,
+            scheduler,
+            handles: Arc::new(Handles::new(&dbg)),
+            exit: Arc::new(AtomicBool::new(false)),
+            dbg,
+        }
+    }
+    ///
+    /// Returns the flag the exit of the service is requested by, for the owner to stop the service from its own thread
+    pub fn exit_flag(&self) -> Arc<AtomicBool> {
+        self.exit.clone()
+    }
+    ///
+    /// Connects to the PLC at `addr` waiting up to `timeout` for each resolved address,
+    /// so the exit request is seen while the PLC is unreachable
+    fn connect(addr: &str, timeout: Duration) -> io::Result<TcpStream> {
+        let mut result = Err(io::Error::new(io::ErrorKind::InvalidInput, format!("Address '{addr}' - not resolved")));
+        for addr in addr.to_socket_addrs()? {
+            result = TcpStream::connect_timeout(&addr, timeout);
+            if result.is_ok() {
+                break;
+            }
+        }
+        result
+    }
+    ///
+    /// Sleeps the `duration` or until the exit requested
+    fn sleep(exit: &AtomicBool, duration: Duration) {
+        let until = Instant::now() + duration;
+        while !exit.load(Ordering::Acquire) {
+            let left = until.saturating_duration_since(Instant::now());
+            if left.is_zero() {
+                break;
+            }
+            std::thread::sleep(left.min(Duration::from_millis(100)));
+        }
+    }
+}
+//
+//
+impl Object for HoistService {
+    fn name(&self) -> Name {
+        self.name.clone()
+    }
+}
+//
+//
+impl std::fmt::Debug for HoistService {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_struct("HoistService")
+            .field("dbg", &self.dbg)
+            .finish()
+    }
+}
+//
+//
+impl Service for HoistService {
+    //
+    //
+    fn run(&self) -> Result<(), Error> {
+        let dbg = self.dbg.clone();
+        let name = self.name.clone();
+        let conf = self.conf.clone();
+        let (FunctionCode(function_code), Register(register)) = conf.register_rope.clone();
+        let request = ReadRegisters::new(conf.unit, function_code, register, conf.register_type.count());
+        let rope = self.rope.clone();
+        let metrics = self.metrics.clone();
+        // Label of the metrics, several hoists may be read
+        let hoist = name.join();
+        // Service can be started again after exit
+        self.exit.store(false, Ordering::Release);
+        let exit = self.exit.clone();
+        let service_waiting = ServiceWaiting::new(&name, conf.wait_started);
+        let service_release = service_waiting.release();
+        log::debug!("{dbg}.run | Preparing thread...");
+        let handle = self.scheduler.spawn(move || {
+            let dbg = &dbg;
+            service_release.add(Ok(()));
+            let mut tr_id: u16 = 0;
+            'main: loop {
+                match Self::connect(&conf.addr, conf.timeout) {
+                    Ok(mut stream) => {
+                        log::debug!("{dbg}.run | Connected to {:?}", conf.addr);
+                        if let Err(err) = stream.set_read_timeout(Some(conf.timeout)).and_then(|_| stream.set_write_timeout(Some(conf.timeout))) {
+                            log::warn!("{dbg}.run | TcpStream set timeout error {:?}", err);
+                        }
+                        loop {
+                            let started = Instant::now();
+                            tr_id = tr_id.wrapping_add(1);
+                            match request.read(&mut stream, tr_id) {
+                                Ok(registers) => match conf.length(&registers) {
+                                    Some(length) => {
+                                        log::trace!("{dbg}.run | Rope length: {} m", length);
+                                        // The register is read somewhere between the request & the response
+                                        rope.set_at(RopeLength::now() - started.elapsed().as_secs_f64() / 2.0, Some(length));
+                                        metrics.rope_length(&hoist, Some(length));
+                                    }
+                                    None => {
+                                        log::debug!("{dbg}.run | Wrong rope length registers {:?}", registers);
+                                        metrics.hoist_read_error(&hoist);
+                                    }
+                                },
+                                Err(err) => {
+                                    log::debug!("{dbg}.run | Read rope length error {:?}", err);
+                                    metrics.hoist_read_error(&hoist);
+                                    // The stream may hold the rest of the failed response, reconnecting
+                                    break;
+                                }
+                            }
+                            Self::sleep(&exit, conf.period.saturating_sub(started.elapsed()));
+                            if exit.load(Ordering::Acquire) {
+                                break 'main;
+                            }
+                        }
+                        rope.set(None);
+                        metrics.rope_length(&hoist, None);
+                    }
+                    Err(err) => {
+                        log::debug!("{dbg}.run | Can't connected to {:?}, \n\terror: {:?}", conf.addr, err);
+                    }
+                }
+                Self::sleep(&exit, RECONNECT_DELAY);
+                if exit.load(Ordering::Acquire) {
+                    break;
+                }
+            }
+            rope.set(None);
+            metrics.rope_length(&hoist, None);
+            log::info!("{dbg}.run | Exit");
+            Ok(())
+        });
+        match handle {
+            Ok(handle) => {
+                self.handles.push(handle);
+                let r = match self.conf.wait_started {
+                    Some(_) => {
+                        log::info!("{}.run | Waiting while starting...", self.dbg);
+                        service_waiting.wait()
+                    }
+                    None => Ok(()),
+                };
+                log::info!("{}.run | Starting - ok", self.dbg);
+                r
+            }
+            Err(err) => {
+                let err = Error::new(&self.dbg, "run").pass_with("Start failed", err.to_string());
+                log::warn!("{}", err);
+                Err(err)
+            }
+        }
+    }
+    //
+    //
+    fn wait(&self) -> Result<(), Error> {
+        self.handles.wait()
+    }
+    //
+    //
+    fn is_finished(&self) -> bool {
+        self.handles.is_finished()
+    }
+    //
+    //
+    fn exit(&self) {
+        self.exit.store(true, Ordering::Release);
+    }
+}
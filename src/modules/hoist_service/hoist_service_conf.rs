@@ -0,0 +1,128 @@
+use std::{str::FromStr, time::Duration};
+use sal_core::dbg::Dbg;
+use sal_sync::services::{conf::{ConfCustomKeywd, ConfTree, ConfTreeGet}, entity::Name};
+
+use crate::modules::{FunctionCode, Register, RegisterType, Units};
+
+///
+/// ## The configuration parameters of the `HoistService`
+///
+/// Reads the current length of the hoist rope from the PLC,
+/// used by the `world.pinhole` transform of the cameras with the `rope-offset`;
+/// the `RopeDefectService` polls its own rope position by the `hoist` of the same parameters
+///
+/// ### Conf example
+/// ```yaml
+/// service HoistService:
+///     wait-started: 10 ms         # optional, next service will wait until current completely started plus specified time
+///     period: 100 ms              # optional, rope length polling period, default 100 ms
+///     timeout: 500 ms             # optional, connection & response timeout, default 500 ms
+///     type: u16                   # optional, u16 / i16 / u32 / f32 (32-bit - two registers, high word first), default u16
+///     scale: 1.0                  # optional, rope length = register value * scale + offset, default 1.0
+///     offset: 0.0                 # optional, default 0.0
+///     units: mm                   # optional, units of the rope length, m / cm / mm, default mm
+///     unit 01:
+///         address: 192.168.100.1:502
+///         function-rope 03: 111   # rope length or hoist position register, 03 - holding, 04 - input
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct HoistServiceConf {
+    pub name: Name,
+    /// Next service will wait until current completely started plus specified time, optional
+    pub wait_started: Option<Duration>,
+    /// Rope length polling period
+    pub period: Duration,
+    /// Connection & response timeout
+    pub timeout: Duration,
+    pub register_type: RegisterType,
+    /// Rope length = register value * scale + offset
+    pub scale: f64,
+    /// Rope length = register value * scale + offset
+    pub offset: f64,
+    /// Units of the rope length
+    pub units: Units,
+    pub unit: u8,
+    pub addr: String,
+    pub register_rope: (FunctionCode, Register),
+}
+//
+//
+impl HoistServiceConf {
+    ///
+    /// Returns [HoistServiceConf] built from `ConfTree`:
+    pub fn new(
+        parent: impl Into<String>,
+        conf: ConfTree,
+    ) -> Self {
+        let parent = parent.into();
+        let me = "HoistServiceConf";
+        let dbg = Dbg::new(&parent, me);
+        let name = Name::new(parent, me);
+        log::trace!("{dbg}.new | name: {:?}", name);
+        let wait_started: Option<Duration> = conf.get_duration("wait-started").ok();
+        log::trace!("{}.new | wait-started: {:?}", dbg, wait_started);
+        let period = conf.get_duration("period").unwrap_or(Duration::from_millis(100));
+        log::trace!("{}.new | period: {:?}", dbg, period);
+        let timeout = conf.get_duration("timeout").unwrap_or(Duration::from_millis(500));
+        log::trace!("{}.new | timeout: {:?}", dbg, timeout);
+        let register_type: Option<String> = conf.get("type");
+        let register_type: RegisterType = register_type
+            .map(|value| value.parse().unwrap_or_else(|err| panic!("{dbg}.new | 'type' - wrong configuration: {err}")))
+            .unwrap_or_default();
+        log::trace!("{}.new | type: {:?}", dbg, register_type);
+        let scale: f64 = conf.get("scale").unwrap_or(1.0);
+        log::trace!("{}.new | scale: {:?}", dbg, scale);
+        let offset: f64 = conf.get("offset").unwrap_or(0.0);
+        log::trace!("{}.new | offset: {:?}", dbg, offset);
+        let units: Option<String> = conf.get("units");
+        let units: Units = units
+            .map(|units| units.parse().unwrap_or_else(|err| panic!("{dbg}.new | 'units' - wrong configuration: {err}")))
+            .unwrap_or_default();
+        log::trace!("{}.new | units: {:?}", dbg, units);
+        let (unit, addr, register_rope) = conf.nodes()
+            .filter_map(|node| {
+                match ConfCustomKeywd::from_str(&node.key) {
+                    Ok(keywd) => {
+                        if keywd.name() == "unit" {
+                            let unit = keywd.title().parse().expect(&format!("{dbg}.new | 'unit number' - not found or wrong configuration"));
+                            let addr: String = node.get("address").expect(&format!("{dbg}.new | 'unit {}: address' - not found or wrong configuration", keywd.title()));
+                            let rope = node.get_by_custom_keywd("", "function-rope").map(|(keywd, node)| {
+                                (
+                                    FunctionCode(keywd.title().parse().expect(&format!("{dbg}.new | 'unit {}: function-rope code' - not found or wrong configuration", keywd.title()))),
+                                    Register(node.conf.as_u64().expect(&format!("{dbg}.new | 'unit {}: function-rope register' - not found or wrong configuration", keywd.title())) as u16)
+                                )
+                            }).expect(&format!("{dbg}.new | 'unit {}: function-rope' - not found or wrong configuration", keywd.title()));
+                            Some((unit, addr, rope))
+                        } else {
+                            None
+                        }
+                    }
+                    Err(_) => None,
+                }
+            })
+            .next()
+            .expect(&format!("{dbg}.new | 'unit' - not found or wrong configuration"));
+        log::debug!("{}.new | unit: {:?}", dbg, unit);
+        log::debug!("{}.new | \taddr: {:?}", dbg, addr);
+        log::debug!("{}.new | \tregister_rope: {:?}", dbg, register_rope);
+        Self {
+            name,
+            wait_started,
+            period,
+            timeout,
+            register_type,
+            scale,
+            offset,
+            units,
+            unit,
+            addr,
+            register_rope,
+        }
+    }
+    ///
+    /// Returns the rope length, m, of the values of the rope length `registers`
+    pub fn length(&self, registers: &[u16]) -> Option<f64> {
+        self.register_type.value(registers)
+            .map(|value| self.units.to_meters(value * self.scale + self.offset))
+    }
+}
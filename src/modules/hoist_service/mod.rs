@@ -0,0 +1,11 @@
+mod hoist_service_conf;
+mod hoist_service;
+mod read_registers;
+mod register_type;
+mod rope_length;
+
+pub(crate) use hoist_service_conf::*;
+pub(crate) use hoist_service::*;
+pub(crate) use read_registers::*;
+pub(crate) use register_type::*;
+pub(crate) use rope_length::*;
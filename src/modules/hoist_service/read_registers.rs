@@ -0,0 +1,104 @@
+use std::io::{Read, Write};
+use sal_core::error::Error;
+
+/// Size of the Modbus TCP header (MBAP): transaction id, protocol id, length, unit id
+const HEADER_LEN: usize = 7;
+/// Function code bit of the exception response
+const EXCEPTION: u8 = 0x80;
+
+///
+/// Request reading the sequence of the registers of the Modbus device over TCP/IP
+///
+/// ## Request
+///
+/// ```ignore
+///  Transaction ID | Protocol ID | Length Field |  Unit ID | Function Code | Register | Count
+///  ---            | ---         | ---          | ---      | ---           | ---      | ---
+///   2 Bytes       | 2 Bytes     | 2 Bytes (6)  | 1 Byte   | 1 Byte        | 2 Bytes  | 2 Bytes
+/// ```
+///
+/// ## Response
+///
+/// ```ignore
+///  Transaction ID | Protocol ID | Length Field |  Unit ID | Function Code | Byte Count | Values
+///  ---            | ---         | ---          | ---      | ---           | ---        | ---
+///   2 Bytes       | 2 Bytes     | 2 Bytes      | 1 Byte   | 1 Byte        | 1 Byte     | 2 Bytes * Count
+/// ```
+///
+/// Exception response has the function code `function | 0x80` followed by the exception code
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReadRegisters {
+    pub unit: u8,
+    /// Function code, 03 - holding registers, 04 - input registers
+    pub function: u8,
+    /// Address of the first register
+    pub register: u16,
+    /// Number of the registers to be read
+    pub count: u16,
+}
+//
+//
+impl ReadRegisters {
+    ///
+    /// Returns [ReadRegisters] new instance
+    pub fn new(unit: u8, function: u8, register: u16, count: u16) -> Self {
+        Self { unit, function, register, count }
+    }
+    ///
+    /// Returns the request message of the transaction `tr_id`
+    pub fn request(&self, tr_id: u16) -> Vec<u8> {
+        let mut bytes = vec![];
+        bytes.extend(tr_id.to_be_bytes());
+        bytes.extend(0u16.to_be_bytes());
+        bytes.extend(6u16.to_be_bytes());
+        bytes.push(self.unit);
+        bytes.push(self.function);
+        bytes.extend(self.register.to_be_bytes());
+        bytes.extend(self.count.to_be_bytes());
+        bytes
+    }
+    ///
+    /// Returns the values of the registers parsed from the `response` message of the transaction `tr_id`
+    pub fn response(&self, tr_id: u16, response: &[u8]) -> Result<Vec<u16>, Error> {
+        let error = Error::new("ReadRegisters", "response");
+        if response.len() < HEADER_LEN + 2 {
+            return Err(error.err(format!("Response too short: {} bytes", response.len())));
+        }
+        let word = |i: usize| u16::from_be_bytes([response[i], response[i + 1]]);
+        if word(0) != tr_id {
+            return Err(error.err(format!("Wrong transaction id: {}, expected {}", word(0), tr_id)));
+        }
+        let len = word(4) as usize;
+        if response.len() != HEADER_LEN - 1 + len {
+            return Err(error.err(format!("Wrong length field: {}, received {} bytes", len, response.len())));
+        }
+        let function = response[HEADER_LEN];
+        if function == self.function | EXCEPTION {
+            return Err(error.err(format!("Exception code {:#04x}, function {:#04x}, register {}", response[HEADER_LEN + 1], self.function, self.register)));
+        }
+        if function != self.function {
+            return Err(error.err(format!("Wrong function code: {:#04x}, expected {:#04x}", function, self.function)));
+        }
+        let count = response[HEADER_LEN + 1] as usize;
+        let values = &response[HEADER_LEN + 2..];
+        if count != self.count as usize * 2 || values.len() != count {
+            return Err(error.err(format!("Wrong byte count: {}, expected {}", count, self.count * 2)));
+        }
+        Ok(values.chunks_exact(2).map(|value| u16::from_be_bytes([value[0], value[1]])).collect())
+    }
+    ///
+    /// Sends the request of the transaction `tr_id` into the `stream` and returns the values of the registers
+    pub fn read<S: Read + Write>(&self, stream: &mut S, tr_id: u16) -> Result<Vec<u16>, Error> {
+        let error = Error::new("ReadRegisters", "read");
+        stream.write_all(&self.request(tr_id)).map_err(|err| error.pass_with("Write error", err.to_string()))?;
+        let mut response = vec![0; HEADER_LEN];
+        stream.read_exact(&mut response).map_err(|err| error.pass_with("Read header error", err.to_string()))?;
+        let len = u16::from_be_bytes([response[4], response[5]]) as usize;
+        if len < 2 {
+            return Err(error.err(format!("Wrong length field: {}", len)));
+        }
+        response.resize(HEADER_LEN - 1 + len, 0);
+        stream.read_exact(&mut response[HEADER_LEN..]).map_err(|err| error.pass_with("Read data error", err.to_string()))?;
+        self.response(tr_id, &response)
+    }
+}
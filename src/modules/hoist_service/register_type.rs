@@ -0,0 +1,51 @@
+use std::str::FromStr;
+use sal_core::error::Error;
+
+///
+/// Type of the value of the rope length register
+/// - 32-bit values take two registers, high word first
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum RegisterType {
+    #[default]
+    U16,
+    I16,
+    U32,
+    F32,
+}
+//
+//
+impl RegisterType {
+    ///
+    /// Returns the number of the registers the value takes
+    pub fn count(self) -> u16 {
+        match self {
+            RegisterType::U16 | RegisterType::I16 => 1,
+            RegisterType::U32 | RegisterType::F32 => 2,
+        }
+    }
+    ///
+    /// Returns the value of the `registers`
+    pub fn value(self, registers: &[u16]) -> Option<f64> {
+        match (self, registers) {
+            (RegisterType::U16, &[value]) => Some(value as f64),
+            (RegisterType::I16, &[value]) => Some(value as i16 as f64),
+            (RegisterType::U32, &[high, low]) => Some((((high as u32) << 16) | low as u32) as f64),
+            (RegisterType::F32, &[high, low]) => Some(f32::from_bits(((high as u32) << 16) | low as u32) as f64).filter(|value| value.is_finite()),
+            _ => None,
+        }
+    }
+}
+//
+//
+impl FromStr for RegisterType {
+    type Err = Error;
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "u16" => Ok(RegisterType::U16),
+            "i16" => Ok(RegisterType::I16),
+            "u32" => Ok(RegisterType::U32),
+            "f32" => Ok(RegisterType::F32),
+            _ => Err(Error::new("RegisterType", "from_str").err(format!("Unknown register type '{}', u16 / i16 / u32 / f32 expected", value))),
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use std::{sync::{Arc, Mutex}, time::{Duration, SystemTime, UNIX_EPOCH}};
+
+/// Default age of the last sample the length is estimated up to
+const MAX_AGE: Duration = Duration::from_secs(1);
+
+///
+/// Shared current length of the hoist rope, published by the `HoistService` to the consumers
+///
+/// - Cloned instances share the same state
+/// - Unknown until the first value is read from the PLC and after the connection is lost
+/// - The values are timestamped by the system clock, s since the UNIX epoch,
+///   so the length can be estimated at the moment of the frame between the sparse readings
+#[derive(Debug, Clone)]
+pub struct RopeLength {
+    /// The last two samples, time, s & length, m, the latest last
+    samples: Arc<Mutex<[Option<(f64, f64)>; 2]>>,
+    /// The length is unknown by [RopeLength::at] the time later then the last sample plus this age, s
+    max_age: f64,
+}
+//
+//
+impl RopeLength {
+    ///
+    /// Returns [RopeLength] new instance, the length is unknown
+    pub fn new() -> Self {
+        Self { samples: Arc::new(Mutex::new([None, None])), max_age: MAX_AGE.as_secs_f64() }
+    }
+    ///
+    /// Returns [RopeLength] estimated by [RopeLength::at] up to the `max_age` of the last sample, default 1 s,
+    /// so the stale samples of the hoist not responding aren't extrapolated
+    pub fn with_max_age(self, max_age: Duration) -> Self {
+        Self { max_age: max_age.as_secs_f64(), ..self }
+    }
+    ///
+    /// Returns the current system time, s since the UNIX epoch, the samples are timestamped by
+    pub fn now() -> f64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs_f64()
+    }
+    ///
+    /// Sets the current `length` of the rope, m, None - unknown
+    pub fn set(&self, length: Option<f64>) {
+        self.set_at(Self::now(), length);
+    }
+    ///
+    /// Sets the `length` of the rope, m, read at the `time`, s since the UNIX epoch, None - unknown
+    pub fn set_at(&self, time: f64, length: Option<f64>) {
+        if let Ok(mut samples) = self.samples.lock() {
+            *samples = match length.filter(|length| length.is_finite()) {
+                // The stale sample doesn't give the speed
+                Some(length) => [samples[1].filter(|(last, _)| time - last <= self.max_age), Some((time, length))],
+                None => [None, None],
+            };
+        }
+    }
+    ///
+    /// Returns the current length of the rope, m, if known
+    pub fn get(&self) -> Option<f64> {
+        self.samples.lock().ok().and_then(|samples| samples[1]).map(|(_, length)| length)
+    }
+    ///
+    /// Returns the length of the rope, m, at the `time`, s since the UNIX epoch, if known
+    ///
+    /// Linearly interpolated / extrapolated by the last two samples,
+    /// the last sample is returned if the only one is read,
+    /// unknown if the last sample is older then the `max_age` at the `time`
+    pub fn at(&self, time: f64) -> Option<f64> {
+        let samples = *self.samples.lock().ok()?;
+        if samples[1].is_none_or(|(last, _)| time - last > self.max_age) {
+            return None;
+        }
+        match samples {
+            [Some((time0, length0)), Some((time1, length1))] if time1 > time0 => {
+                Some(length1 + (length1 - length0) / (time1 - time0) * (time - time1))
+            }
+            [_, Some((_, length))] => Some(length),
+            _ => None,
+        }
+    }
+}
+//
+//
+impl Default for RopeLength {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,249 @@
+use std::{io::{BufRead, BufReader, Write}, net::{TcpListener, TcpStream}, sync::{atomic::{AtomicBool, Ordering}, Arc}, time::Duration};
+use sal_core::{dbg::Dbg, error::Error};
+use serde::Serialize;
+use sal_sync::{services::{entity::{Name, Object}, Service, ServiceWaiting}, sync::Handles, thread_pool::Scheduler};
+
+use crate::modules::{HttpServiceConf, Metrics, ServiceRegistry};
+
+/// Interval of the exit checking while no connections
+const ACCEPT_INTERVAL: Duration = Duration::from_millis(100);
+/// Timeout of the request reading / response writing
+const IO_TIMEOUT: Duration = Duration::from_millis(1000);
+///
+/// Monitoring HTTP server
+/// 
+/// ## Routes
+/// 
+/// - `GET /metrics` - [Metrics] in the Prometheus text exposition format
+/// - `GET /status` - state of the all services & the recent rope defects, JSON
+/// - `POST /services/{name}/stop` - stops the service
+/// - `POST /services/{name}/restart` - stops the service & starts it again
+/// - `POST /services/{name}/run` - starts the stopped service
+///
+/// The service is controlled in the background, so the monitoring isn't blocked while the service is stopping,
+/// the control requests are responded by `202 Accepted` with the state of the all services before the action,
+/// the result is seen by the next `GET /status`
+pub struct HttpService {
+    name: Name,
+    conf: HttpServiceConf,
+    metrics: Metrics,
+    registry: ServiceRegistry,
+    scheduler: Scheduler,
+    handles: Arc<Handles<()>>,
+    exit: Arc<AtomicBool>,
+    dbg: Dbg,
+}
+//
+//
+impl HttpService {
+    ///
+    /// Crteates [HttpService] new instance
+    pub fn new(
+        parent: impl Into<String>,
+        conf: HttpServiceConf,
+        metrics: Metrics,
+        registry: ServiceRegistry,
+        scheduler: Scheduler,
+    ) -> Self {
+        let name = Name::new(parent, "HttpService");
+        let dbg = Dbg::new(name.parent(), name.me());
+        Self {
+            name,
+            conf,
+            metrics,
+            registry,
+            scheduler,
+            handles: Arc::new(Handles::new(&dbg)),
+            exit: Arc::new(AtomicBool::new(false)),
+            dbg,
+        }
+    }
+    ///
+    /// Reads the request from the `stream` and writes the response
+    fn handle(dbg: &Dbg, me: &str, mut stream: TcpStream, metrics: &Metrics, registry: &ServiceRegistry) -> Result<(), Error> {
+        let error = Error::new(dbg, "handle");
+        stream.set_nonblocking(false).map_err(|err| error.pass(err.to_string()))?;
+        stream.set_read_timeout(Some(IO_TIMEOUT)).map_err(|err| error.pass(err.to_string()))?;
+        stream.set_write_timeout(Some(IO_TIMEOUT)).map_err(|err| error.pass(err.to_string()))?;
+        let mut reader = BufReader::new(&stream);
+        let mut request = String::new();
+        reader.read_line(&mut request).map_err(|err| error.pass(err.to_string()))?;
+        // Headers & body are not used, but headers have to be read out before the response
+        let mut header = String::new();
+        while reader.read_line(&mut header).map_err(|err| error.pass(err.to_string()))? > 2 {
+            header.clear();
+        }
+        let mut parts = request.split_whitespace();
+        let (method, path) = (parts.next().unwrap_or_default(), parts.next().unwrap_or_default());
+        log::trace!("{dbg}.handle | Request: {} {}", method, path);
+        let response = Self::route(dbg, me, method, path, metrics, registry);
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            response.status, response.content_type, response.body.len(), response.body,
+        );
+        stream.write_all(response.as_bytes()).map_err(|err| error.pass(err.to_string()))
+    }
+    ///
+    /// Returns the response to the request `method` `path`
+    /// - `me` - name of the current service, it can't be controlled over itself
+    fn route(dbg: &Dbg, me: &str, method: &str, path: &str, metrics: &Metrics, registry: &ServiceRegistry) -> Response {
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+        match (method, segments.as_slice()) {
+            ("GET", ["metrics"]) => Response::new("200 OK", "text/plain; version=0.0.4; charset=utf-8", metrics.render()),
+            ("GET", ["status"]) => Response::json("200 OK", &registry.status()),
+            ("POST", ["services", name, action]) => {
+                if !registry.contains(name) {
+                    return Response::error("404 Not Found", format!("Service '{name}' - not found"));
+                }
+                if *name == me {
+                    return Response::error("409 Conflict", format!("Service '{name}' can't be controlled over itself"));
+                }
+                let action: fn(&ServiceRegistry, &str) -> Result<(), Error> = match *action {
+                    "stop" => ServiceRegistry::stop,
+                    "restart" => ServiceRegistry::restart,
+                    "run" => ServiceRegistry::run,
+                    _ => return Response::error("404 Not Found", format!("Unknown action '{action}'")),
+                };
+                let (dbg, name, path, registry_clone) = (dbg.clone(), name.to_string(), path.to_owned(), registry.clone());
+                let spawned = std::thread::Builder::new().name(format!("HttpService {name}")).spawn(move || {
+                    if let Err(err) = action(&registry_clone, &name) {
+                        log::warn!("{dbg}.route | POST {} error: {}", path, err);
+                    }
+                });
+                match spawned {
+                    Ok(_) => Response::json("202 Accepted", &registry.status()),
+                    Err(err) => Response::error("500 Internal Server Error", err.to_string()),
+                }
+            }
+            ("GET", _) | ("POST", _) => Response::error("404 Not Found", format!("Unknown path '{path}'")),
+            _ => Response::error("405 Method Not Allowed", format!("Unknown method '{method}'")),
+        }
+    }
+}
+///
+/// HTTP response
+struct Response {
+    status: &'static str,
+    content_type: &'static str,
+    body: String,
+}
+//
+//
+impl Response {
+    fn new(status: &'static str, content_type: &'static str, body: String) -> Self {
+        Self { status, content_type, body }
+    }
+    ///
+    /// Returns the response with the `value` serialized into JSON
+    fn json(status: &'static str, value: &impl Serialize) -> Self {
+        match serde_json::to_string(value) {
+            Ok(body) => Self::new(status, "application/json", body),
+            Err(err) => Self::error("500 Internal Server Error", err.to_string()),
+        }
+    }
+    ///
+    /// Returns the response with the JSON `{"error": "..."}` body
+    fn error(status: &'static str, error: String) -> Self {
+        Self::new(status, "application/json", serde_json::json!({ "error": error }).to_string())
+    }
+}
+//
+//
+impl Object for HttpService {
+    fn name(&self) -> Name {
+        self.name.clone()
+    }
+}
+//
+// 
+impl std::fmt::Debug for HttpService {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_struct("HttpService")
+            .field("dbg", &self.dbg)
+            .finish()
+    }
+}
+//
+// 
+impl Service for HttpService {
+    //
+    // 
+    fn run(&self) -> Result<(), Error> {
+        let dbg = self.dbg.clone();
+        let name = self.name.clone();
+        let conf = self.conf.clone();
+        let metrics = self.metrics.clone();
+        let registry = self.registry.clone();
+        let me = name.me();
+        // Service can be started again after exit
+        self.exit.store(false, Ordering::Release);
+        let exit = self.exit.clone();
+        let listener = TcpListener::bind(&conf.addr)
+            .and_then(|listener| listener.set_nonblocking(true).map(|_| listener))
+            .map_err(|err| Error::new(&self.dbg, "run").pass_with(format!("Can't bind to {:?}", conf.addr), err.to_string()))?;
+        let service_waiting = ServiceWaiting::new(&name, conf.wait_started);
+        let service_release = service_waiting.release();
+        log::debug!("{dbg}.run | Preparing thread...");
+        let handle = self.scheduler.spawn(move || {
+            let dbg = &dbg;
+            service_release.add(Ok(()));
+            log::debug!("{dbg}.run | Listening on {:?}", conf.addr);
+            loop {
+                match listener.accept() {
+                    Ok((stream, addr)) => {
+                        if let Err(err) = Self::handle(dbg, &me, stream, &metrics, &registry) {
+                            log::debug!("{dbg}.run | Request from {:?} error: {:?}", addr, err);
+                        }
+                    }
+                    Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => {
+                        std::thread::sleep(ACCEPT_INTERVAL);
+                    }
+                    Err(err) => {
+                        log::debug!("{dbg}.run | Accept error: {:?}", err);
+                        std::thread::sleep(ACCEPT_INTERVAL);
+                    }
+                }
+                if exit.load(Ordering::Acquire) {
+                    break;
+                }
+            }
+            log::info!("{dbg}.run | Exit");
+            Ok(())
+        });
+        match handle {
+            Ok(handle) => {
+                self.handles.push(handle);
+                let r = match conf.wait_started {
+                    Some(_) => {
+                        log::info!("{}.run | Waiting while starting...", self.dbg);
+                        service_waiting.wait()
+                    }
+                    None => Ok(()),
+                };
+                log::info!("{}.run | Starting - ok", self.dbg);
+                r
+            }
+            Err(err) => {
+                let err = Error::new(&self.dbg, "run").pass_with("Start failed", err.to_string());
+                log::warn!("{}", err);
+                Err(err)
+            }
+        }
+    }
+    //
+    //
+    fn wait(&self) -> Result<(), Error> {
+        self.handles.wait()
+    }
+    //
+    //
+    fn is_finished(&self) -> bool {
+        self.handles.is_finished()
+    }
+    //
+    //
+    fn exit(&self) {
+        self.exit.store(true, Ordering::Release);
+    }
+}
@@ -0,0 +1,45 @@
+use std::time::Duration;
+use sal_core::dbg::Dbg;
+use sal_sync::services::{conf::{ConfTree, ConfTreeGet}, entity::Name};
+
+///
+/// ## The configuration parameters for the `HttpService`
+/// 
+/// ### Conf example
+/// ```yaml
+/// service HttpService:
+///     wait-started: 10 ms         # optional, next service will wait until current completely started plus specified time
+///     address: 0.0.0.0:9100       # the address to listen on, `GET /metrics` - Prometheus metrics, `GET /status` - services state, `POST /services/{name}/stop|restart|run`
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpServiceConf {
+    pub name: Name,
+    /// Next service will wait until current completely started plus specified time, optional
+    pub wait_started: Option<Duration>,
+    pub addr: String,
+}
+//
+// 
+impl HttpServiceConf {
+    ///
+    /// Returns [HttpServiceConf] built from `ConfTree`:
+    pub fn new(
+        parent: impl Into<String>,
+        conf: ConfTree,
+    ) -> Self {
+        let parent = parent.into();
+        let me = "HttpServiceConf";
+        let dbg = Dbg::new(&parent, me);
+        let name = Name::new(parent, me);
+        log::trace!("{dbg}.new | name: {:?}", name);
+        let wait_started: Option<Duration> = conf.get_duration("wait-started").ok();
+        log::trace!("{}.new | wait-started: {:?}", dbg, wait_started);
+        let addr: String = conf.get("address").expect(&format!("{dbg}.new | 'address' - not found or wrong configuration"));
+        log::trace!("{}.new | address: {:?}", dbg, addr);
+        Self {
+            name,
+            wait_started,
+            addr,
+        }
+    }
+}
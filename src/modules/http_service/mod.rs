@@ -0,0 +1,5 @@
+mod http_service_conf;
+mod http_service;
+
+pub(crate) use http_service_conf::*;
+pub(crate) use http_service::*;
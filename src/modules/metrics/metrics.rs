@@ -0,0 +1,336 @@
+use std::{collections::VecDeque, fmt::Write, sync::{atomic::{AtomicBool, AtomicU64, Ordering}, Arc, Mutex}, time::Instant};
+
+use crate::modules::{CameraId, CameraState, CameraStatus, Diagnostics, Position, RopeDefect, RopeDefectKind, StatsReport};
+
+/// Prefix of the all metric names
+const PREFIX: &str = "position_tracking";
+/// Smoothing factor of the fps
+const FPS_SMOOTHING: f64 = 0.1;
+/// Number of the recent rope defects kept for the `GET /status`
+const RECENT_DEFECTS: usize = 16;
+///
+/// Shared metrics of the services, rendered in the Prometheus text exposition format
+/// 
+/// - Cloned instances share the same state
+/// - Counters & gauges are updated by the services while working
+#[derive(Debug, Clone, Default)]
+pub struct Metrics {
+    state: Arc<MetricsState>,
+}
+///
+/// State of the [Metrics]
+#[derive(Debug, Default)]
+struct MetricsState {
+    trackers: Mutex<Vec<(CameraId, TrackerStats)>>,
+    modbus_connected: AtomicBool,
+    modbus_write_errors: AtomicU64,
+    modbus_reconnects: AtomicU64,
+    modbus_overflows: AtomicU64,
+    backlog: AtomicU64,
+    fused: AtomicU64,
+    fallback: AtomicU64,
+    hoists: Mutex<Vec<(String, HoistStats)>>,
+    rope_segments: AtomicU64,
+    rope_segments_missed: AtomicU64,
+    rope_defects: [AtomicU64; 4],
+    recent_defects: Mutex<VecDeque<RopeDefect>>,
+    diagnostics: Mutex<Vec<(CameraId, Diagnostics)>>,
+    cameras: Mutex<Vec<(CameraId, CameraStatus)>>,
+    positions: Mutex<Vec<(String, CameraId, Position<f64>)>>,
+}
+///
+/// Frame processing state of the single camera
+#[derive(Debug, Clone, Default)]
+struct TrackerStats {
+    frames: u64,
+    matched: u64,
+    keypoints: u64,
+    rejected: u64,
+    fps: Fps,
+}
+///
+/// Rope reading state of the single hoist
+#[derive(Debug, Clone, Default)]
+struct HoistStats {
+    length: Option<f64>,
+    read_errors: u64,
+}
+///
+/// Frame rate, smoothed
+#[derive(Debug, Clone, Default)]
+struct Fps {
+    last: Option<Instant>,
+    value: f64,
+}
+//
+//
+impl Metrics {
+    ///
+    /// Returns [Metrics] new instance
+    pub fn new() -> Self {
+        Self::default()
+    }
+    ///
+    /// Adds the pipeline [Diagnostics] of the `camera`, rendered as stage latencies
+    pub fn add_diagnostics(&self, camera: CameraId, diagnostics: Diagnostics) {
+        if let Ok(mut list) = self.state.diagnostics.lock() {
+            list.push((camera, diagnostics));
+        }
+    }
+    ///
+    /// Adds the [CameraStatus] of the `camera`, rendered as camera state & reconnections
+    pub fn add_camera(&self, camera: CameraId, status: CameraStatus) {
+        if let Ok(mut list) = self.state.cameras.lock() {
+            list.push((camera, status));
+        }
+    }
+    ///
+    /// Returns true if some camera is not streaming
+    pub fn camera_down(&self) -> bool {
+        self.state.cameras.lock()
+            .map(|list| list.iter().any(|(_, status)| status.state() != CameraState::Streaming))
+            .unwrap_or(false)
+    }
+    ///
+    /// Records the frame processed by the `camera`
+    /// - `matched` - the target is found on the frame
+    pub fn frame(&self, camera: CameraId, matched: bool) {
+        self.tracker(camera, |stats| {
+            stats.frames += 1;
+            if matched {
+                stats.matched += 1;
+            }
+            let fps = &mut stats.fps;
+            let now = Instant::now();
+            if let Some(last) = fps.last {
+                let elapsed = now.duration_since(last).as_secs_f64();
+                if elapsed > 0.0 {
+                    fps.value = match fps.value > 0.0 {
+                        true => fps.value + (1.0 / elapsed - fps.value) * FPS_SMOOTHING,
+                        false => 1.0 / elapsed,
+                    };
+                }
+            }
+            fps.last = Some(now);
+        });
+    }
+    ///
+    /// Sets the number of the matched keypoints on the last frame of the `camera`
+    pub fn keypoints(&self, camera: CameraId, keypoints: u64) {
+        self.tracker(camera, |stats| stats.keypoints = keypoints);
+    }
+    ///
+    /// Sets the total number of the values rejected by the position filters of the `camera`
+    pub fn rejected(&self, camera: CameraId, rejected: u64) {
+        self.tracker(camera, |stats| stats.rejected = rejected);
+    }
+    ///
+    /// Sets the Modbus connection state
+    pub fn modbus_connected(&self, connected: bool) {
+        self.state.modbus_connected.store(connected, Ordering::Relaxed);
+    }
+    ///
+    /// Records the Modbus write error
+    pub fn modbus_write_error(&self) {
+        self.state.modbus_write_errors.fetch_add(1, Ordering::Relaxed);
+    }
+    ///
+    /// Records the Modbus reconnection
+    pub fn modbus_reconnect(&self) {
+        self.state.modbus_reconnects.fetch_add(1, Ordering::Relaxed);
+    }
+    ///
+    /// Counts the world position out of the register range, not written
+    pub fn modbus_overflow(&self) {
+        self.state.modbus_overflows.fetch_add(1, Ordering::Relaxed);
+    }
+    ///
+    /// Sets the number of the positions waiting in the channel to be sent
+    pub fn backlog(&self, backlog: u64) {
+        self.state.backlog.store(backlog, Ordering::Relaxed);
+    }
+    ///
+    /// Sets the total numbers of the `fused` 3D positions & the single camera `fallback` 2D positions
+    pub fn fusion(&self, fused: u64, fallback: u64) {
+        self.state.fused.store(fused, Ordering::Relaxed);
+        self.state.fallback.store(fallback, Ordering::Relaxed);
+    }
+    ///
+    /// Sets the current rope length read from the PLC by the `hoist` service, m, None - unknown
+    pub fn rope_length(&self, hoist: &str, length: Option<f64>) {
+        self.hoist(hoist, |stats| stats.length = length);
+    }
+    ///
+    /// Counts the failed reading of the rope length from the PLC by the `hoist` service
+    pub fn hoist_read_error(&self, hoist: &str) {
+        self.hoist(hoist, |stats| stats.read_errors += 1);
+    }
+    ///
+    /// Counts the inspected rope segment
+    pub fn rope_segment(&self) {
+        self.state.rope_segments.fetch_add(1, Ordering::Relaxed);
+    }
+    ///
+    /// Sets the total number of the segment boundaries passed without the frame taken
+    pub fn rope_segments_missed(&self, missed: u64) {
+        self.state.rope_segments_missed.store(missed, Ordering::Relaxed);
+    }
+    ///
+    /// Counts the rope `defect` found, the recent ones are kept for the `GET /status`
+    pub fn rope_defect(&self, defect: RopeDefect) {
+        self.state.rope_defects[defect.kind as usize].fetch_add(1, Ordering::Relaxed);
+        if let Ok(mut recent) = self.state.recent_defects.lock() {
+            if recent.len() >= RECENT_DEFECTS {
+                recent.pop_front();
+            }
+            recent.push_back(defect);
+        }
+    }
+    ///
+    /// Returns the recent rope defects found, the oldest first
+    pub fn rope_defects(&self) -> Vec<RopeDefect> {
+        self.state.recent_defects.lock()
+            .map(|recent| recent.iter().copied().collect())
+            .unwrap_or_default()
+    }
+    ///
+    /// Sets the last position of the `camera` handled by the `service`
+    pub fn position(&self, service: &str, camera: CameraId, position: Position<f64>) {
+        if let Ok(mut positions) = self.state.positions.lock() {
+            match positions.iter_mut().find(|(name, id, _)| name == service && *id == camera) {
+                Some((_, _, last)) => *last = position,
+                None => positions.push((service.to_owned(), camera, position)),
+            }
+        }
+    }
+    ///
+    /// Returns the last positions handled by the `service`, one per camera, ordered by the camera
+    pub fn last_positions(&self, service: &str) -> Vec<(CameraId, Position<f64>)> {
+        let mut last: Vec<_> = self.state.positions.lock()
+            .map(|positions| positions.iter()
+                .filter(|(name, _, _)| name == service)
+                .map(|(_, camera, position)| (*camera, *position))
+                .collect())
+            .unwrap_or_default();
+        last.sort_by_key(|(camera, _)| camera.0);
+        last
+    }
+    ///
+    /// Returns the metrics in the Prometheus text exposition format
+    pub fn render(&self) -> String {
+        let state = &self.state;
+        let mut out = String::new();
+        let trackers = state.trackers.lock()
+            .map(|list| list.iter().map(|(camera, stats)| (format!("camera=\"{}\"", camera.0), stats.clone())).collect::<Vec<_>>())
+            .unwrap_or_default();
+        let samples = |value: fn(&TrackerStats) -> f64| -> Vec<(&str, f64)> {
+            trackers.iter().map(|(labels, stats)| (labels.as_str(), value(stats))).collect()
+        };
+        Self::metric(&mut out, "frames_total", "counter", "Frames processed", &samples(|stats| stats.frames as f64));
+        Self::metric(&mut out, "matched_total", "counter", "Frames the target is found on", &samples(|stats| stats.matched as f64));
+        Self::metric(&mut out, "match_success_ratio", "gauge", "Ratio of the frames the target is found on", &samples(|stats| match stats.frames {
+            0 => 0.0,
+            _ => stats.matched as f64 / stats.frames as f64,
+        }));
+        Self::metric(&mut out, "fps", "gauge", "Frames processed per second", &samples(|stats| stats.fps.value));
+        Self::metric(&mut out, "keypoints", "gauge", "Matched keypoints on the last frame", &samples(|stats| stats.keypoints as f64));
+        Self::metric(&mut out, "filter_rejected_total", "counter", "Positions rejected by the filters", &samples(|stats| stats.rejected as f64));
+        let connected = state.modbus_connected.load(Ordering::Relaxed);
+        Self::metric(&mut out, "modbus_connected", "gauge", "Modbus connection state, 1 - connected", &[("", if connected { 1.0 } else { 0.0 })]);
+        Self::metric(&mut out, "modbus_write_errors_total", "counter", "Modbus write errors", &[("", state.modbus_write_errors.load(Ordering::Relaxed) as f64)]);
+        Self::metric(&mut out, "modbus_reconnects_total", "counter", "Modbus reconnections", &[("", state.modbus_reconnects.load(Ordering::Relaxed) as f64)]);
+        Self::metric(&mut out, "modbus_overflows_total", "counter", "World positions out of the register range, not written", &[("", state.modbus_overflows.load(Ordering::Relaxed) as f64)]);
+        Self::metric(&mut out, "channel_backlog", "gauge", "Positions waiting in the channel to be sent", &[("", state.backlog.load(Ordering::Relaxed) as f64)]);
+        Self::metric(&mut out, "fusion_fused_total", "counter", "Positions fused from the several cameras", &[("", state.fused.load(Ordering::Relaxed) as f64)]);
+        Self::metric(&mut out, "fusion_fallback_total", "counter", "Single camera positions published while fusion", &[("", state.fallback.load(Ordering::Relaxed) as f64)]);
+        let hoists = state.hoists.lock()
+            .map(|list| list.iter().map(|(hoist, stats)| (format!("hoist=\"{hoist}\""), stats.clone())).collect::<Vec<_>>())
+            .unwrap_or_default();
+        let rope_length: Vec<_> = hoists.iter()
+            .filter_map(|(labels, stats)| stats.length.map(|length| (labels.as_str(), length)))
+            .collect();
+        Self::metric(&mut out, "rope_length_meters", "gauge", "Rope length read from the PLC, absent if unknown", &rope_length);
+        let read_errors: Vec<_> = hoists.iter()
+            .map(|(labels, stats)| (labels.as_str(), stats.read_errors as f64))
+            .collect();
+        Self::metric(&mut out, "hoist_read_errors_total", "counter", "Rope length reading errors", &read_errors);
+        Self::metric(&mut out, "rope_segments_total", "counter", "Rope segments inspected for the defects", &[("", state.rope_segments.load(Ordering::Relaxed) as f64)]);
+        Self::metric(&mut out, "rope_segments_missed_total", "counter", "Rope segment boundaries passed without the frame taken", &[("", state.rope_segments_missed.load(Ordering::Relaxed) as f64)]);
+        let rope_defects: Vec<_> = RopeDefectKind::ALL.iter()
+            .map(|kind| (format!("kind=\"{}\"", kind.name()), state.rope_defects[*kind as usize].load(Ordering::Relaxed) as f64))
+            .collect();
+        Self::metric(&mut out, "rope_defects_total", "counter", "Rope defects found", &rope_defects);
+        let cameras = state.cameras.lock().map(|list| list.clone()).unwrap_or_default();
+        let camera_up: Vec<_> = cameras.iter()
+            .map(|(camera, status)| (format!("camera=\"{}\"", camera.0), if status.state() == CameraState::Streaming { 1.0 } else { 0.0 }))
+            .collect();
+        let camera_reconnects: Vec<_> = cameras.iter()
+            .map(|(camera, status)| (format!("camera=\"{}\"", camera.0), status.reconnects() as f64))
+            .collect();
+        Self::metric(&mut out, "camera_up", "gauge", "Camera state, 1 - frames are coming", &camera_up);
+        Self::metric(&mut out, "camera_reconnects_total", "counter", "Camera reconnections", &camera_reconnects);
+        let reports = state.diagnostics.lock()
+            .map(|list| list.iter().map(|(camera, diagnostics)| (camera.0, diagnostics.report())).collect::<Vec<_>>())
+            .unwrap_or_default();
+        let mut stages = vec![];
+        let mut latency = vec![];
+        for (camera, report) in &reports {
+            for stage in &report.stages {
+                stages.extend(Self::summary(&format!("camera=\"{camera}\",stage=\"{}\"", stage.stage), &stage.duration));
+            }
+            latency.extend(Self::summary(&format!("camera=\"{camera}\""), &report.latency));
+        }
+        Self::metric(&mut out, "stage_duration_milliseconds", "gauge", "Duration of the pipeline stage over the last frames", &stages);
+        Self::metric(&mut out, "latency_milliseconds", "gauge", "End-to-end latency from the frame to the position sent over the last frames", &latency);
+        out
+    }
+    ///
+    /// Applies `op` to the frame processing state of the `camera`, the state is added on the first call
+    fn tracker(&self, camera: CameraId, op: impl FnOnce(&mut TrackerStats)) {
+        if let Ok(mut list) = self.state.trackers.lock() {
+            match list.iter_mut().find(|(id, _)| *id == camera) {
+                Some((_, stats)) => op(stats),
+                None => {
+                    let mut stats = TrackerStats::default();
+                    op(&mut stats);
+                    list.push((camera, stats));
+                }
+            }
+        }
+    }
+    ///
+    /// Applies `op` to the rope reading state of the `hoist` service, the state is added on the first call
+    fn hoist(&self, hoist: &str, op: impl FnOnce(&mut HoistStats)) {
+        if let Ok(mut list) = self.state.hoists.lock() {
+            match list.iter_mut().find(|(name, _)| name == hoist) {
+                Some((_, stats)) => op(stats),
+                None => {
+                    let mut stats = HoistStats::default();
+                    op(&mut stats);
+                    list.push((hoist.to_owned(), stats));
+                }
+            }
+        }
+    }
+    ///
+    /// Returns the samples of the rolling statistics labeled by `labels`
+    fn summary(labels: &str, stats: &StatsReport) -> Vec<(String, f64)> {
+        [("mean", stats.mean), ("p95", stats.p95), ("max", stats.max)].into_iter()
+            .filter_map(|(stat, value)| value.map(|value| (format!("{labels},stat=\"{stat}\""), value)))
+            .collect()
+    }
+    ///
+    /// Writes the metric `name` with the `samples` into `out`
+    /// - `samples` - labels (without braces, may be empty) & value
+    fn metric<L: AsRef<str>>(out: &mut String, name: &str, kind: &str, help: &str, samples: &[(L, f64)]) {
+        let _ = writeln!(out, "# HELP {PREFIX}_{name} {help}");
+        let _ = writeln!(out, "# TYPE {PREFIX}_{name} {kind}");
+        for (labels, value) in samples {
+            let labels = labels.as_ref();
+            let _ = match labels.is_empty() {
+                true => writeln!(out, "{PREFIX}_{name} {value}"),
+                false => writeln!(out, "{PREFIX}_{name}{{{labels}}} {value}"),
+            };
+        }
+    }
+}
@@ -1,7 +1,29 @@
+mod calibration;
 mod camera_service;
+mod diagnostics;
 mod filter;
+mod fusion;
+mod hoist_service;
+mod http_service;
+mod metrics;
 mod modbus_service;
+mod rope_defect_service;
+mod service_registry;
+mod supervisor;
+mod sway;
+mod world;
 
+pub(crate) use calibration::*;
 pub(crate) use camera_service::*;
+pub(crate) use diagnostics::*;
 pub(crate) use filter::*;
-pub(crate) use modbus_service::*;
\ No newline at end of file
+pub(crate) use fusion::*;
+pub(crate) use hoist_service::*;
+pub(crate) use http_service::*;
+pub(crate) use metrics::*;
+pub(crate) use modbus_service::*;
+pub(crate) use rope_defect_service::*;
+pub(crate) use service_registry::*;
+pub(crate) use supervisor::*;
+pub(crate) use sway::*;
+pub(crate) use world::*;
\ No newline at end of file
@@ -1,8 +1,15 @@
 use std::{io::Write, net::TcpStream, sync::{atomic::{AtomicBool, Ordering}, Arc}, time::Duration};
 use sal_core::{dbg::Dbg, error::Error};
-use sal_sync::{services::{entity::{Name, Object}, Service, ServiceWaiting, RECV_TIMEOUT}, sync::{Handles, Owner}, thread_pool::Scheduler};
+use sal_sync::{services::{entity::{Name, Object}, Service, ServiceWaiting, RECV_TIMEOUT}, sync::Handles, thread_pool::Scheduler};
 
-use crate::modules::{FunctionCode, ModbusServiceConf, Register};
+use crate::modules::{CameraId, FunctionCode, Metrics, ModbusServiceConf, Register, Sway, SwayRegister, Target, Units, WorldPosition};
+
+/// Tracker status written into the `function-status` register while running
+const STATUS_RUNNING: u16 = 1;
+/// Tracker status written into the `function-status` register on exit
+const STATUS_STOPPED: u16 = 0;
+/// Tracker status written into the `function-status` register while some camera is down
+const STATUS_CAMERA_DOWN: u16 = 2;
 
 /// 
 /// Communication with the Modbus device over TCP/IP
@@ -39,7 +46,8 @@ use crate::modules::{FunctionCode, ModbusServiceConf, Register};
 pub struct ModbusService {
     name: Name,
     conf: ModbusServiceConf,
-    position: Owner<kanal::Receiver<(u16, u16)>>,
+    position: kanal::Receiver<Target>,
+    metrics: Metrics,
     scheduler: Scheduler,
     handles: Arc<Handles<()>>,
     exit: Arc<AtomicBool>,
@@ -53,7 +61,8 @@ impl ModbusService {
     pub fn new(
         parent: impl Into<String>,
         conf: ModbusServiceConf,
-        position: kanal::Receiver<(u16, u16)>,
+        position: kanal::Receiver<Target>,
+        metrics: Metrics,
         scheduler: Scheduler,
     ) -> Self {
         let name = Name::new(parent, "ModbusService");
@@ -61,7 +70,8 @@ impl ModbusService {
         Self {
             name,
             conf,
-            position: Owner::new(position),
+            position,
+            metrics,
             scheduler,
             handles: Arc::new(Handles::new(&dbg)),
             exit: Arc::new(AtomicBool::new(false)),
@@ -81,6 +91,97 @@ impl ModbusService {
         bytes.extend(value.to_be_bytes());
         bytes
     }
+    ///
+    /// Writes the tracker `status` into the `function-status` register, if configured
+    fn write_status(stream: &mut TcpStream, conf: &ModbusServiceConf, status: u16) -> std::io::Result<()> {
+        match &conf.register_status {
+            Some((FunctionCode(function_code), Register(register))) => {
+                let buf = Self::build_modbus_cmd_message(0, 0, conf.unit, *function_code, *register, status);
+                stream.write_all(&buf)
+            }
+            None => Ok(()),
+        }
+    }
+    ///
+    /// Writes the Z of the 3D world position, if the Z register configured
+    fn write_z(stream: &mut TcpStream, conf: &ModbusServiceConf, z: Option<u16>) -> std::io::Result<()> {
+        match (&conf.register_z, z) {
+            (Some((FunctionCode(function_code), Register(register))), Some(z)) => {
+                let buf = Self::build_modbus_cmd_message(0, 0, conf.unit, *function_code, *register, z);
+                stream.write_all(&buf)
+            }
+            _ => Ok(()),
+        }
+    }
+    ///
+    /// Returns the X, Y & Z register values of the `target`:
+    /// - the world position in the `units`, signed, Z only if the position is 3D
+    /// - the pixel position, if the target has no world position
+    /// - None if the world position is out of the register range
+    fn values(units: Units, target: &Target) -> Option<(u16, u16, Option<u16>)> {
+        match target.world {
+            Some(WorldPosition::Plane(world)) => Some((
+                Self::world_value(units, world.x)?,
+                Self::world_value(units, world.y)?,
+                None,
+            )),
+            Some(WorldPosition::Space(world)) => Some((
+                Self::world_value(units, world.x)?,
+                Self::world_value(units, world.y)?,
+                Some(Self::world_value(units, world.z)?),
+            )),
+            None => Some((target.position.x.round() as u16, target.position.y.round() as u16, None)),
+        }
+    }
+    ///
+    /// Returns the world coordinate, m, as the register value in the `units`, signed
+    /// - None if the value is out of the `i16` range
+    fn world_value(units: Units, value: f64) -> Option<u16> {
+        let value = units.of_meters(value).round();
+        match (i16::MIN as f64..=i16::MAX as f64).contains(&value) {
+            true => Some((value as i16) as u16),
+            false => None,
+        }
+    }
+    ///
+    /// Writes the `sway` into the configured sway registers, zeros if the sway of the position is unknown,
+    /// so the sway of the previous position isn't kept
+    fn write_sway(stream: &mut TcpStream, conf: &ModbusServiceConf, sway: Option<Sway>) -> std::io::Result<()> {
+        for (register, (FunctionCode(function_code), Register(address))) in &conf.register_sway {
+            let value = sway.as_ref().map(|sway| Self::sway_value(*register, sway)).unwrap_or(0);
+            let buf = Self::build_modbus_cmd_message(0, 0, conf.unit, *function_code, *address, value);
+            stream.write_all(&buf)?;
+        }
+        Ok(())
+    }
+    ///
+    /// Returns the value of the sway `register`:
+    /// - angle, mrad, signed
+    /// - angular velocity, mrad/s, signed
+    /// - period, ms, 0 - unknown
+    fn sway_value(register: SwayRegister, sway: &Sway) -> u16 {
+        let milli = |value: f64| ((value * 1000.0).round() as i16) as u16;
+        let period = |value: Option<f64>| value.map(|value| (value * 1000.0).round() as u16).unwrap_or(0);
+        match register {
+            SwayRegister::AngleX => milli(sway.x.angle),
+            SwayRegister::AngleY => milli(sway.y.angle),
+            SwayRegister::RateX => milli(sway.x.rate),
+            SwayRegister::RateY => milli(sway.y.rate),
+            SwayRegister::PeriodX => period(sway.x.period),
+            SwayRegister::PeriodY => period(sway.y.period),
+        }
+    }
+    ///
+    /// Writes the id of the `camera` the next position belongs to, if the camera register configured
+    fn write_camera(stream: &mut TcpStream, conf: &ModbusServiceConf, camera: CameraId) -> std::io::Result<()> {
+        match &conf.register_camera {
+            Some((FunctionCode(function_code), Register(register))) => {
+                let buf = Self::build_modbus_cmd_message(0, 0, conf.unit, *function_code, *register, camera.0 as u16);
+                stream.write_all(&buf)
+            }
+            None => Ok(()),
+        }
+    }
 }
 //
 //
@@ -107,10 +208,14 @@ impl Service for ModbusService {
     fn run(&self) -> Result<(), Error> {
         let dbg = self.dbg.clone();
         let name = self.name.clone();
+        let me = name.me();
         let conf = self.conf.clone();
         let (FunctionCode(function_code_x), Register(register_x)) = (conf.register_x.0, conf.register_x.1);
         let (FunctionCode(function_code_y), Register(register_y)) = (conf.register_y.0, conf.register_y.1);
-        let position = self.position.take().unwrap();
+        let position = self.position.clone();
+        let metrics = self.metrics.clone();
+        // Service can be started again after exit
+        self.exit.store(false, Ordering::Release);
         let exit = self.exit.clone();
         let service_waiting = ServiceWaiting::new(&name, conf.wait_started);
         let service_release = service_waiting.release();
@@ -118,26 +223,83 @@ impl Service for ModbusService {
         let handle = self.scheduler.spawn(move || {
             let dbg = &dbg;
             service_release.add(Ok(()));
+            let mut connected_once = false;
             loop {
                 match TcpStream::connect(&conf.addr) {
                     Ok(mut stream) => {
                         log::debug!("{dbg}.run | Connected to {:?}", conf.addr);
+                        if connected_once {
+                            metrics.modbus_reconnect();
+                        }
+                        connected_once = true;
+                        metrics.modbus_connected(true);
+                        let mut status = None;
                         let mut buf = vec![];
-                        loop {
+                        'write: loop {
+                            let current = match metrics.camera_down() {
+                                true => STATUS_CAMERA_DOWN,
+                                false => STATUS_RUNNING,
+                            };
+                            if status != Some(current) {
+                                log::info!("{dbg}.run | Writing tracker status {}...", current);
+                                if let Err(err) = Self::write_status(&mut stream, &conf, current) {
+                                    log::debug!("{dbg}.run | TcpStream write status error {:?}", err);
+                                    metrics.modbus_write_error();
+                                    break 'write;
+                                }
+                                status = Some(current);
+                            }
                             match position.recv_timeout(RECV_TIMEOUT) {
-                                Ok((pos_x, pos_y)) => {
-                                    buf = Self::build_modbus_cmd_message(0, 0, conf.unit, function_code_x, register_x, pos_x);
-                                    if let Err(err) = stream.write_all(&mut buf) {
-                                        log::debug!("{dbg}.run | TcpStream write error {:?}", err);
-                                    }
-                                    buf = Self::build_modbus_cmd_message(0, 0, conf.unit, function_code_y, register_y, pos_y);
-                                    if let Err(err) = stream.write_all(&mut buf) {
-                                        log::debug!("{dbg}.run | TcpStream write error {:?}", err);
+                                Ok(target) => {
+                                    metrics.backlog(position.len() as u64);
+                                    match Self::values(conf.units, &target) {
+                                        Some((pos_x, pos_y, pos_z)) => {
+                                            if let Err(err) = Self::write_camera(&mut stream, &conf, target.camera) {
+                                                log::debug!("{dbg}.run | TcpStream write camera error {:?}", err);
+                                                metrics.modbus_write_error();
+                                                break 'write;
+                                            }
+                                            buf = Self::build_modbus_cmd_message(0, 0, conf.unit, function_code_x, register_x, pos_x);
+                                            if let Err(err) = stream.write_all(&mut buf) {
+                                                log::debug!("{dbg}.run | TcpStream write error {:?}", err);
+                                                metrics.modbus_write_error();
+                                                break 'write;
+                                            }
+                                            buf = Self::build_modbus_cmd_message(0, 0, conf.unit, function_code_y, register_y, pos_y);
+                                            if let Err(err) = stream.write_all(&mut buf) {
+                                                log::debug!("{dbg}.run | TcpStream write error {:?}", err);
+                                                metrics.modbus_write_error();
+                                                break 'write;
+                                            }
+                                            if let Err(err) = Self::write_z(&mut stream, &conf, pos_z) {
+                                                log::debug!("{dbg}.run | TcpStream write error {:?}", err);
+                                                metrics.modbus_write_error();
+                                                break 'write;
+                                            }
+                                            if let Err(err) = Self::write_sway(&mut stream, &conf, target.sway) {
+                                                log::debug!("{dbg}.run | TcpStream write sway error {:?}", err);
+                                                metrics.modbus_write_error();
+                                                break 'write;
+                                            }
+                                            metrics.position(&me, target.camera, target.position);
+                                        }
+                                        None => {
+                                            log::warn!("{dbg}.run | World position {:?} out of the register range in {:?}, not written", target.world, conf.units);
+                                            metrics.modbus_overflow();
+                                        }
                                     }
                                 }
                                 Err(_) => {},
                             }
+                            if exit.load(Ordering::Acquire) {
+                                log::info!("{dbg}.run | Writing tracker stopped status...");
+                                if let Err(err) = Self::write_status(&mut stream, &conf, STATUS_STOPPED).and_then(|_| stream.flush()) {
+                                    log::warn!("{dbg}.run | TcpStream write status error {:?}", err);
+                                }
+                                break 'write;
+                            }
                         }
+                        metrics.modbus_connected(false);
                     }
                     Err(err) => {
                         log::debug!("{dbg}.run | Can't connected to {:?}, \n\terror: {:?}", conf.addr, err);
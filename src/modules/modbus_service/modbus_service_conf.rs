@@ -3,7 +3,7 @@ use frdm_tools::camera::CameraConf;
 use sal_core::dbg::Dbg;
 use sal_sync::services::{conf::{ConfCustomKeywd, ConfTree, ConfTreeGet}, entity::Name};
 
-use crate::modules::TemplateMatchConf;
+use crate::modules::{TemplateMatchConf, Units};
 
 ///
 /// Modbus Function Code u8
@@ -30,26 +30,90 @@ impl Register {
     }
 }
 ///
+/// Sway value written into the optional register
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SwayRegister {
+    /// Angle of the rope along X, mrad, signed
+    AngleX,
+    /// Angle of the rope along Y, mrad, signed
+    AngleY,
+    /// Angular velocity along X, mrad/s, signed
+    RateX,
+    /// Angular velocity along Y, mrad/s, signed
+    RateY,
+    /// Swing period along X, ms, 0 - unknown
+    PeriodX,
+    /// Swing period along Y, ms, 0 - unknown
+    PeriodY,
+}
+//
+//
+impl SwayRegister {
+    /// All the sway registers, in the order of writing
+    pub const ALL: [SwayRegister; 6] = [
+        SwayRegister::AngleX,
+        SwayRegister::AngleY,
+        SwayRegister::RateX,
+        SwayRegister::RateY,
+        SwayRegister::PeriodX,
+        SwayRegister::PeriodY,
+    ];
+    ///
+    /// Returns the configuration keyword of the register
+    pub fn key(&self) -> &'static str {
+        match self {
+            SwayRegister::AngleX => "function-angle-x",
+            SwayRegister::AngleY => "function-angle-y",
+            SwayRegister::RateX => "function-rate-x",
+            SwayRegister::RateY => "function-rate-y",
+            SwayRegister::PeriodX => "function-period-x",
+            SwayRegister::PeriodY => "function-period-y",
+        }
+    }
+}
+///
 /// ## The configuration parameters for the `RopeDefect`
 /// 
 /// ### Conf example
 /// ```yaml
 /// service ModbusService:
 ///     wait-started: 10 ms         # optional, next service will wait until current completely started plus specified time
+///     units: mm                   # optional, units of the world position written, m / cm / mm, default mm, limits the range to ±32767 units
 ///     unit 01:
 ///         address: 192.168.100.1:502
 ///         function-x 03: 101
 ///         function-y 03: 103
+///         function-z 03: 109          # optional, Z of the fused 3D world position; X, Y & Z of the world position are written in `units`, signed 16 bit
+///         function-status 06: 105     # optional, tracker status, 1 - running, 2 - camera down, 0 - stopped, written on change & on exit
+///         function-camera 06: 107     # optional, id of the camera the position belongs to, written before the position
+///         function-angle-x 03: 113    # optional, sway angle, mrad, signed; `sway` of the camera required, all the sway registers are 0 if the sway is unknown
+///         function-angle-y 03: 115
+///         function-rate-x 03: 117     # optional, sway angular velocity, mrad/s, signed
+///         function-rate-y 03: 119
+///         function-period-x 03: 121   # optional, swing period, ms, 0 - unknown
+///         function-period-y 03: 123
 /// ```
 #[derive(Debug, Clone, PartialEq)]
 pub struct ModbusServiceConf {
     pub name: Name,
     /// Next service will wait until current completely started plus specified time, optional
     pub wait_started: Option<Duration>,
+    /// Units of the world position written, the pixel position is written if the target has no world position
+    /// - the registers are signed 16 bit, so the range is ±32.767 m in mm, ±327.67 m in cm & ±32767 m in m
+    /// - the position out of the range isn't written, counted by the `modbus_overflows_total` metric
+    pub units: Units,
     pub unit: u8,
     pub addr: String,
     pub register_x: (FunctionCode, Register),
     pub register_y: (FunctionCode, Register),
+    /// Z of the fused 3D world position register, optional, isn't written for the single camera position
+    pub register_z: Option<(FunctionCode, Register)>,
+    /// Tracker status register, optional
+    pub register_status: Option<(FunctionCode, Register)>,
+    /// Camera id register, optional
+    pub register_camera: Option<(FunctionCode, Register)>,
+    /// Sway registers, optional
+    pub register_sway: Vec<(SwayRegister, (FunctionCode, Register))>,
 }
 //
 // 
@@ -68,7 +132,12 @@ impl ModbusServiceConf {
         log::trace!("{dbg}.new | name: {:?}", name);
         let wait_started: Option<Duration> = conf.get_duration("wait-started").ok();
         log::trace!("{}.new | wait-started: {:?}", dbg, wait_started);
-        let (unit, addr, register_x, register_y) = conf.nodes()
+        let units: Option<String> = conf.get("units");
+        let units: Units = units
+            .map(|units| units.parse().unwrap_or_else(|err| panic!("{dbg}.new | 'units' - wrong configuration: {err}")))
+            .unwrap_or_default();
+        log::trace!("{}.new | units: {:?}", dbg, units);
+        let (unit, addr, register_x, register_y, register_z, register_status, register_camera, register_sway) = conf.nodes()
             .filter_map(|node| {
                 log::debug!("{dbg}.new | node: {:#?}", node);
                 match ConfCustomKeywd::from_str(&node.key) {
@@ -88,7 +157,37 @@ impl ModbusServiceConf {
                                     Register(node.conf.as_u64().expect(&format!("{dbg}.new | 'unit {}: function-y register' - not found or wrong configuration", keywd.title())) as u16)
                                 )
                             }).expect(&format!("{dbg}.new | 'unit {}: function-y' - not found or wrong configuration", keywd.title()));
-                            Some((unit, addr, (x_function, x_register), (y_function, y_register)))
+                            let z = node.get_by_custom_keywd("", "function-z").map(|(keywd, node)| {
+                                (
+                                    FunctionCode(keywd.title().parse().expect(&format!("{dbg}.new | 'unit {}: function-z code' - not found or wrong configuration", keywd.title()))),
+                                    Register(node.conf.as_u64().expect(&format!("{dbg}.new | 'unit {}: function-z register' - not found or wrong configuration", keywd.title())) as u16)
+                                )
+                            });
+                            let status = node.get_by_custom_keywd("", "function-status").map(|(keywd, node)| {
+                                (
+                                    FunctionCode(keywd.title().parse().expect(&format!("{dbg}.new | 'unit {}: function-status code' - not found or wrong configuration", keywd.title()))),
+                                    Register(node.conf.as_u64().expect(&format!("{dbg}.new | 'unit {}: function-status register' - not found or wrong configuration", keywd.title())) as u16)
+                                )
+                            });
+                            let camera = node.get_by_custom_keywd("", "function-camera").map(|(keywd, node)| {
+                                (
+                                    FunctionCode(keywd.title().parse().expect(&format!("{dbg}.new | 'unit {}: function-camera code' - not found or wrong configuration", keywd.title()))),
+                                    Register(node.conf.as_u64().expect(&format!("{dbg}.new | 'unit {}: function-camera register' - not found or wrong configuration", keywd.title())) as u16)
+                                )
+                            });
+                            let sway = SwayRegister::ALL.into_iter().filter_map(|register| {
+                                let key = register.key();
+                                node.get_by_custom_keywd("", key).map(|(keywd, node)| {
+                                    (
+                                        register,
+                                        (
+                                            FunctionCode(keywd.title().parse().expect(&format!("{dbg}.new | 'unit {}: {key} code' - not found or wrong configuration", keywd.title()))),
+                                            Register(node.conf.as_u64().expect(&format!("{dbg}.new | 'unit {}: {key} register' - not found or wrong configuration", keywd.title())) as u16)
+                                        )
+                                    )
+                                })
+                            }).collect::<Vec<_>>();
+                            Some((unit, addr, (x_function, x_register), (y_function, y_register), z, status, camera, sway))
                         } else {
                             None
                         }
@@ -102,40 +201,22 @@ impl ModbusServiceConf {
         log::debug!("{}.new | \taddr: {:?}", dbg, addr);
         log::debug!("{}.new | \tregister_x: {:?}", dbg, register_x);
         log::debug!("{}.new | \tregister_y: {:?}", dbg, register_y);
+        log::debug!("{}.new | \tregister_z: {:?}", dbg, register_z);
+        log::debug!("{}.new | \tregister_status: {:?}", dbg, register_status);
+        log::debug!("{}.new | \tregister_camera: {:?}", dbg, register_camera);
+        log::debug!("{}.new | \tregister_sway: {:?}", dbg, register_sway);
         Self {
             name,
             wait_started,
+            units,
             unit,
             addr,
             register_x,
             register_y,
+            register_z,
+            register_status,
+            register_camera,
+            register_sway,
         }
     }
 }
-///
-/// Camera unique identifier to be used in the sql database and folder name
-#[repr(transparent)]
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub struct CameraId(pub usize);
-// Implement the Default trait to provide a default value
-impl Default for CameraId {
-    fn default() -> Self {
-        CameraId(0) // Default value for the wrapped usize
-    }
-}
-
-// Implement Deref to allow immutable dereferencing to usize
-impl std::ops::Deref for CameraId {
-    type Target = usize;
-
-    fn deref(&self) -> &Self::Target {
-        &self.0 // Dereference to the inner usize
-    }
-}
-
-// Implement DerefMut to allow mutable dereferencing to usize
-impl std::ops::DerefMut for CameraId {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0 // Mutably dereference to the inner usize
-    }
-}
@@ -0,0 +1,15 @@
+mod rope_conf;
+mod rope_defect_analysis;
+mod rope_defect_service_conf;
+mod rope_defect_service;
+mod rope_defect;
+mod rope_profile;
+mod segment_tracker;
+
+pub(crate) use rope_conf::*;
+pub(crate) use rope_defect_analysis::*;
+pub(crate) use rope_defect_service_conf::*;
+pub(crate) use rope_defect_service::*;
+pub(crate) use rope_defect::*;
+pub(crate) use rope_profile::*;
+pub(crate) use segment_tracker::*;
@@ -0,0 +1,120 @@
+use sal_core::dbg::Dbg;
+use sal_sync::services::{conf::{ConfTree, ConfTreeGet}, entity::Name};
+
+use crate::modules::Units;
+
+///
+/// ## The configuration parameters of the rope inspected by the `RopeDefectService`
+///
+/// ### Conf example
+/// ```yaml
+/// rope:
+///     units: mm                   # optional, units of the lengths below, m / cm / mm, default mm
+///     width: 35                   # nominal diameter of the rope
+///     segment: 100                # the rope is inspected by the segments of this length, 85..95% of the frame size along the rope
+///     segment-threshold: 5        # optional, the frame is taken within this distance from the segment boundary, default 5% of the `segment`
+///     camera-offset: 0            # optional, rope point under the camera = rope position + camera-offset, default 0
+///     max-speed: 400              # maximum speed of the rope, `units` per second
+///     width-tolerance: 2          # optional, widening / narrowing if the diameter differs more, default 5% of the `width`
+///     edge-tolerance: 1           # optional, bump / groove if the single edge deviates more, default 3% of the `width`
+///     min-length: 3               # optional, shorter deviations are ignored, default 0
+///     pixel-size: 0.05            # optional, size of the pixel on the rope, default - the `width` over the median diameter on the frame,
+///                                 # widening & narrowing longer than the half of the frame are detected only if configured
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RopeConf {
+    pub name: Name,
+    /// Nominal diameter of the rope, m
+    pub width: f64,
+    /// Length of the segment, m, the rope is inspected by
+    pub segment: f64,
+    /// The frame is taken within this distance from the segment boundary, m
+    pub segment_threshold: f64,
+    /// Rope point under the camera = rope position + camera offset, m
+    pub camera_offset: f64,
+    /// Maximum speed of the rope, m/s
+    pub max_speed: f64,
+    /// Widening / narrowing if the diameter differs from the `width` more, m
+    pub width_tolerance: f64,
+    /// Bump / groove if the single edge deviates more, m
+    pub edge_tolerance: f64,
+    /// Deviations shorter along the rope are ignored, m
+    pub min_length: f64,
+    /// Size of the pixel on the rope, m, derived from the median diameter on the frame if None
+    pub pixel_size: Option<f64>,
+}
+//
+//
+impl RopeConf {
+    ///
+    /// Returns [RopeConf] built from `ConfTree`:
+    pub fn new(
+        parent: impl Into<String>,
+        conf: ConfTree,
+    ) -> Self {
+        let parent = parent.into();
+        let me = "RopeConf";
+        let dbg = Dbg::new(&parent, me);
+        let name = Name::new(parent, me);
+        log::trace!("{dbg}.new | name: {:?}", name);
+        let units: Option<String> = conf.get("units");
+        let units: Units = units
+            .map(|units| units.parse().unwrap_or_else(|err| panic!("{dbg}.new | 'units' - wrong configuration: {err}")))
+            .unwrap_or_default();
+        log::trace!("{}.new | units: {:?}", dbg, units);
+        let width: f64 = conf.get("width").expect(&format!("{dbg}.new | 'width' - not found or wrong configuration"));
+        let width = units.to_meters(width);
+        log::trace!("{}.new | width: {:?}", dbg, width);
+        if width <= 0.0 {
+            panic!("{dbg}.new | 'width' must be positive, found: {width}");
+        }
+        let segment: f64 = conf.get("segment").expect(&format!("{dbg}.new | 'segment' - not found or wrong configuration"));
+        let segment = units.to_meters(segment);
+        log::trace!("{}.new | segment: {:?}", dbg, segment);
+        if segment <= 0.0 {
+            panic!("{dbg}.new | 'segment' must be positive, found: {segment}");
+        }
+        let segment_threshold: Option<f64> = conf.get("segment-threshold");
+        let segment_threshold = segment_threshold.map(|threshold| units.to_meters(threshold)).unwrap_or(segment * 0.05);
+        log::trace!("{}.new | segment-threshold: {:?}", dbg, segment_threshold);
+        if segment_threshold <= 0.0 || segment_threshold >= segment / 4.0 {
+            panic!("{dbg}.new | 'segment-threshold' must be positive & less than quarter of the 'segment', found: {segment_threshold}");
+        }
+        let camera_offset: f64 = conf.get("camera-offset").unwrap_or(0.0);
+        let camera_offset = units.to_meters(camera_offset);
+        log::trace!("{}.new | camera-offset: {:?}", dbg, camera_offset);
+        let max_speed: f64 = conf.get("max-speed").expect(&format!("{dbg}.new | 'max-speed' - not found or wrong configuration"));
+        let max_speed = units.to_meters(max_speed);
+        log::trace!("{}.new | max-speed: {:?}", dbg, max_speed);
+        if max_speed <= 0.0 {
+            panic!("{dbg}.new | 'max-speed' must be positive, found: {max_speed}");
+        }
+        let width_tolerance: Option<f64> = conf.get("width-tolerance");
+        let width_tolerance = width_tolerance.map(|tolerance| units.to_meters(tolerance)).unwrap_or(width * 0.05);
+        log::trace!("{}.new | width-tolerance: {:?}", dbg, width_tolerance);
+        let edge_tolerance: Option<f64> = conf.get("edge-tolerance");
+        let edge_tolerance = edge_tolerance.map(|tolerance| units.to_meters(tolerance)).unwrap_or(width * 0.03);
+        log::trace!("{}.new | edge-tolerance: {:?}", dbg, edge_tolerance);
+        let min_length: f64 = conf.get("min-length").unwrap_or(0.0);
+        let min_length = units.to_meters(min_length);
+        log::trace!("{}.new | min-length: {:?}", dbg, min_length);
+        let pixel_size: Option<f64> = conf.get("pixel-size");
+        let pixel_size = pixel_size.map(|size| units.to_meters(size));
+        log::trace!("{}.new | pixel-size: {:?}", dbg, pixel_size);
+        if pixel_size.is_some_and(|size| size <= 0.0) {
+            panic!("{dbg}.new | 'pixel-size' must be positive, found: {:?}", pixel_size);
+        }
+        Self {
+            name,
+            width,
+            segment,
+            segment_threshold,
+            camera_offset,
+            max_speed,
+            width_tolerance,
+            edge_tolerance,
+            min_length,
+            pixel_size,
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use serde::Serialize;
+
+///
+/// Kind of the rope defect
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RopeDefectKind {
+    /// Symmetric deformation with the increased diameter
+    Widening,
+    /// Symmetric deformation with the decreased diameter
+    Narrowing,
+    /// Bulge on the one side of the rope
+    Bump,
+    /// Hollow on the one side of the rope
+    Groove,
+}
+//
+//
+impl RopeDefectKind {
+    /// All the defect kinds
+    pub const ALL: [RopeDefectKind; 4] = [
+        RopeDefectKind::Widening,
+        RopeDefectKind::Narrowing,
+        RopeDefectKind::Bump,
+        RopeDefectKind::Groove,
+    ];
+    ///
+    /// Returns the name of the kind, used in the logs & metrics
+    pub fn name(&self) -> &'static str {
+        match self {
+            RopeDefectKind::Widening => "widening",
+            RopeDefectKind::Narrowing => "narrowing",
+            RopeDefectKind::Bump => "bump",
+            RopeDefectKind::Groove => "groove",
+        }
+    }
+}
+///
+/// Defect found on the rope
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct RopeDefect {
+    pub kind: RopeDefectKind,
+    /// Rope position of the center of the defect, m
+    pub position: f64,
+    /// Length of the defect along the rope, m
+    pub length: f64,
+    /// Maximum deviation from the nominal rope, m;
+    /// change of the diameter for the widening & narrowing, height / depth of the edge for the bump & groove
+    pub deviation: f64,
+}
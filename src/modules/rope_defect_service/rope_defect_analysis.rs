@@ -0,0 +1,95 @@
+use crate::modules::{RopeConf, RopeDefect, RopeDefectKind, RopeProfile};
+
+///
+/// Classifies the deviations of the [RopeProfile] into the [RopeDefect]s
+///
+/// The nominal edges of the rope are the median edges of the frame, so the rope may be shifted on the frame;
+/// each column is classified:
+/// - bump / groove - the single edge deviates outward / inward more than the `edge-tolerance`
+/// - widening / narrowing - the diameter is bigger / smaller than the `width` more than the `width-tolerance`
+///
+/// The neighbour columns of the same kind are merged into the single defect
+#[derive(Debug, Clone)]
+pub struct RopeDefectAnalysis {
+    conf: RopeConf,
+}
+//
+//
+impl RopeDefectAnalysis {
+    ///
+    /// Returns [RopeDefectAnalysis] new instance
+    pub fn new(conf: RopeConf) -> Self {
+        Self { conf }
+    }
+    ///
+    /// Returns the defects of the `profile`
+    /// - `position` - rope position of the center of the frame, m, the rope position is expected to grow along the X axis of the frame
+    pub fn defects(&self, profile: &RopeProfile, position: f64) -> Vec<RopeDefect> {
+        let Some((top, bottom, diameter)) = profile.median() else {
+            return vec![];
+        };
+        let pixel = match self.conf.pixel_size {
+            Some(pixel) => pixel,
+            None if diameter > 0.0 => self.conf.width / diameter,
+            None => return vec![],
+        };
+        let center = (profile.len() as f64 - 1.0) / 2.0;
+        let columns: Vec<Option<(RopeDefectKind, f64)>> = profile.edges().iter()
+            .map(|edges| edges.and_then(|edges| self.classify(edges, (top, bottom), pixel)))
+            .collect();
+        let mut defects = vec![];
+        let mut start = 0;
+        while start < columns.len() {
+            let Some((kind, _)) = columns[start] else {
+                start += 1;
+                continue;
+            };
+            let end = columns[start..].iter()
+                .position(|column| column.is_none_or(|(k, _)| k != kind))
+                .map_or(columns.len(), |len| start + len);
+            let length = (end - start) as f64 * pixel;
+            if length >= self.conf.min_length {
+                let deviation = columns[start..end].iter().flatten().map(|(_, deviation)| *deviation).fold(0.0, f64::max);
+                defects.push(RopeDefect {
+                    kind,
+                    position: position + ((start + end - 1) as f64 / 2.0 - center) * pixel,
+                    length,
+                    deviation,
+                });
+            }
+            start = end;
+        }
+        defects
+    }
+    ///
+    /// Returns the defect kind & the deviation, m, of the column `edges`, px
+    /// - `nominal` - nominal top & bottom edges, px
+    /// - `pixel` - size of the pixel, m
+    fn classify(&self, edges: (f64, f64), nominal: (f64, f64), pixel: f64) -> Option<(RopeDefectKind, f64)> {
+        // Outward deviations of the edges, m
+        let top = (nominal.0 - edges.0) * pixel;
+        let bottom = (edges.1 - nominal.1) * pixel;
+        let tolerance = self.conf.edge_tolerance;
+        let edge = match (top.abs() > tolerance, bottom.abs() > tolerance) {
+            (true, false) => Some(top),
+            (false, true) => Some(bottom),
+            _ => None,
+        };
+        if let Some(edge) = edge {
+            return match edge > 0.0 {
+                true => Some((RopeDefectKind::Bump, edge)),
+                false => Some((RopeDefectKind::Groove, -edge)),
+            };
+        }
+        // Both edges deviated in the opposite directions is the shift of the rope, not the defect
+        if top * bottom < 0.0 && top.abs() > tolerance {
+            return None;
+        }
+        let width = (edges.1 - edges.0) * pixel - self.conf.width;
+        match width {
+            _ if width > self.conf.width_tolerance => Some((RopeDefectKind::Widening, width)),
+            _ if width < -self.conf.width_tolerance => Some((RopeDefectKind::Narrowing, -width)),
+            _ => None,
+        }
+    }
+}
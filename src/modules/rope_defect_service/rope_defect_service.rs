@@ -0,0 +1,299 @@
+use std::{sync::{atomic::{AtomicBool, Ordering}, Arc}, time::{Duration, Instant}};
+use frdm_tools::{camera::Camera, ContextRead, Eval, Image, ResultCtx};
+use opencv::core::{Mat, MatTraitConst, Point, Scalar, Vector};
+use sal_core::{dbg::Dbg, error::Error};
+use sal_sync::{services::{entity::{Name, Object}, Service, ServiceWaiting, RECV_TIMEOUT}, sync::Handles, thread_pool::Scheduler};
+
+use crate::modules::{Diagnostics, HoistService, Metrics, Preprocessing, RopeDefect, RopeDefectAnalysis, RopeDefectServiceConf, RopeLength, RopeProfile, SegmentTracker};
+
+/// The camera clock is considered synchronized with the system one, if the frame timestamp differs less
+const MAX_CLOCK_OFFSET: Duration = Duration::from_secs(1);
+/// The rope position is unknown if not read for this number of the `hoist.period`
+const STALE_PERIODS: u32 = 3;
+
+///
+/// Detects the defects of the hoist rope on the frames of the camera
+///
+/// - The rope is inspected by the segments of the `rope.segment` length,
+///   the frame is taken when the camera passes the boundary of the segments by the rope position,
+///   see [SegmentTracker]
+/// - The rope position is polled from the PLC by the own [HoistService] of the `hoist` configured,
+///   it's started & stopped with the service;
+///   the rope position is estimated at the timestamp of each frame between the sparse readings
+/// - The frame is preprocessed, binarized, the rope is the largest contour on it
+/// - The diameter profile of the rope is classified into widening, narrowing, bump & groove
+/// - Each defect found is logged with its rope position & published into the [Metrics],
+///   the recent defects are reported by the `GET /status`
+pub struct RopeDefectService {
+    name: Name,
+    conf: RopeDefectServiceConf,
+    rope: RopeLength,
+    hoist: HoistService,
+    metrics: Metrics,
+    scheduler: Scheduler,
+    handles: Arc<Handles<()>>,
+    exit: Arc<AtomicBool>,
+    dbg: Dbg,
+}
+//
+//
+impl RopeDefectService {
+    ///
+    /// Crteates [RopeDefectService] new instance
+    /// - `hoist_scheduler` - the rope position polling is spawned on
+    pub fn new(
+        parent: impl Into<String>,
+        conf: RopeDefectServiceConf,
+        metrics: Metrics,
+        scheduler: Scheduler,
+        hoist_scheduler: Scheduler,
+    ) -> Self {
+        let name = Name::new(parent, "RopeDefectService");
+        let dbg = Dbg::new(name.parent(), name.me());
+        let rope = RopeLength::new().with_max_age(conf.hoist.period * STALE_PERIODS);
+        let hoist = HoistService::new(name.join(), conf.hoist.clone(), rope.clone(), metrics.clone(), hoist_scheduler);
+        Self {
+            name,
+            conf,
+            rope,
+            hoist,
+            metrics,
+            scheduler,
+            handles: Arc::new(Handles::new(&dbg)),
+            exit: Arc::new(AtomicBool::new(false)),
+            dbg,
+        }
+    }
+    ///
+    /// Returns the time of the `frame`, s since the UNIX epoch
+    /// - the camera timestamp, if the camera clock is synchronized with the system one
+    /// - the moment the frame was `received` otherwise
+    fn frame_time(frame: &Image, received: f64) -> f64 {
+        let timestamp = frame.timestamp as f64 * 1e-9;
+        match (received - timestamp).abs() < MAX_CLOCK_OFFSET.as_secs_f64() {
+            true => timestamp,
+            false => received,
+        }
+    }
+    ///
+    /// Returns the diameter profile of the rope on the preprocessed frame `mat`
+    fn profile(conf: &RopeDefectServiceConf, mat: &Mat) -> Result<RopeProfile, opencv::Error> {
+        let gray = match mat.channels() {
+            1 => mat.clone(),
+            _ => {
+                let mut gray = Mat::default();
+                opencv::imgproc::cvt_color(mat, &mut gray, opencv::imgproc::COLOR_BGR2GRAY, 0)?;
+                gray
+            }
+        };
+        let kind = match conf.invert {
+            true => opencv::imgproc::THRESH_BINARY_INV,
+            false => opencv::imgproc::THRESH_BINARY,
+        };
+        let mut binary = Mat::default();
+        match conf.threshold {
+            Some(threshold) => opencv::imgproc::threshold(&gray, &mut binary, threshold, 255.0, kind)?,
+            None => opencv::imgproc::threshold(&gray, &mut binary, 0.0, 255.0, kind | opencv::imgproc::THRESH_OTSU)?,
+        };
+        let mut contours = Vector::<Vector<Point>>::new();
+        opencv::imgproc::find_contours(&binary, &mut contours, opencv::imgproc::RETR_EXTERNAL, opencv::imgproc::CHAIN_APPROX_SIMPLE, Point::default())?;
+        // The rope is the largest contour, the rest are the spots of the background
+        let mut largest = None;
+        for (index, contour) in contours.iter().enumerate() {
+            let area = opencv::imgproc::contour_area(&contour, false)?;
+            if largest.is_none_or(|(_, largest)| area > largest) {
+                largest = Some((index, area));
+            }
+        }
+        let mut mask = Mat::new_rows_cols_with_default(binary.rows(), binary.cols(), opencv::core::CV_8UC1, Scalar::all(0.0))?;
+        if let Some((index, _)) = largest {
+            opencv::imgproc::draw_contours(&mut mask, &contours, index as i32, Scalar::all(255.0), opencv::imgproc::FILLED, opencv::imgproc::LINE_8, &opencv::core::no_array(), i32::MAX, Point::default())?;
+        }
+        Ok(RopeProfile::from_mask(mask.data_bytes()?, mask.cols() as usize, mask.rows() as usize))
+    }
+    ///
+    /// Returns the defects of the rope on the `frame`
+    /// - `position` - rope point under the camera, the center of the frame, m
+    fn inspect(conf: &RopeDefectServiceConf, preprocessing: &Preprocessing, analysis: &RopeDefectAnalysis, frame: Image, position: f64) -> Result<Vec<RopeDefect>, Error> {
+        let error = Error::new("RopeDefectService", "inspect");
+        let ctx = preprocessing.eval(frame).map_err(|err| error.pass(err))?;
+        let result: &ResultCtx = ctx.read();
+        let profile = Self::profile(conf, &result.frame.mat).map_err(|err| error.pass(err.to_string()))?;
+        if profile.is_empty() {
+            return Err(error.err("The rope is not found on the frame"));
+        }
+        Ok(analysis.defects(&profile, position))
+    }
+}
+///
+/// Requests the own [HoistService] to exit when the service thread ends, even if it panicked
+struct HoistExit(Arc<AtomicBool>);
+//
+//
+impl Drop for HoistExit {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Release);
+    }
+}
+//
+//
+impl Object for RopeDefectService {
+    fn name(&self) -> Name {
+        self.name.clone()
+    }
+}
+//
+//
+impl std::fmt::Debug for RopeDefectService {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_struct("RopeDefectService")
+            .field("dbg", &self.dbg)
+            .finish()
+    }
+}
+//
+//
+impl Service for RopeDefectService {
+    //
+    //
+    fn run(&self) -> Result<(), Error> {
+        self.hoist.run()?;
+        let dbg = self.dbg.clone();
+        let name = self.name.clone();
+        let conf = self.conf.clone();
+        let rope = self.rope.clone();
+        let metrics = self.metrics.clone();
+        // Service can be started again after exit
+        self.exit.store(false, Ordering::Release);
+        let exit = self.exit.clone();
+        let service_waiting = ServiceWaiting::new(&name, conf.wait_started);
+        let service_release = service_waiting.release();
+        let handles_clone = self.handles.clone();
+        let hoist_exit = HoistExit(self.hoist.exit_flag());
+        log::debug!("{dbg}.run | Preparing thread...");
+        let handle = self.scheduler.spawn(move || {
+            let _hoist_exit = hoist_exit;
+            let dbg = &dbg;
+            let preprocessing = Preprocessing::new(&conf.image, &Diagnostics::new());
+            let analysis = RopeDefectAnalysis::new(conf.rope.clone());
+            let mut camera = Camera::new(conf.camera.clone());
+            let camera_stream = camera.stream();
+            service_release.add(Ok(()));
+            let mut segments = SegmentTracker::new(conf.rope.segment, conf.rope.segment_threshold, conf.rope.camera_offset);
+            // Reconnections in a row without frames received
+            let mut failures = 0;
+            'main: loop {
+                log::debug!("{dbg}.run | Starting camera...");
+                match camera.read() {
+                    Ok(handle) => {
+                        handles_clone.push(handle);
+                        let mut last_frame = Instant::now();
+                        'camera: loop {
+                            match camera_stream.recv_timeout(RECV_TIMEOUT) {
+                                Ok(frame) => {
+                                    last_frame = Instant::now();
+                                    failures = 0;
+                                    let time = Self::frame_time(&frame, RopeLength::now());
+                                    match rope.at(time) {
+                                        Some(position) => {
+                                            let boundary = segments.add(position);
+                                            metrics.rope_segments_missed(segments.missed() as u64);
+                                            if let Some(boundary) = boundary {
+                                                match Self::inspect(&conf, &preprocessing, &analysis, frame, boundary.position) {
+                                                    Ok(defects) => {
+                                                        log::debug!("{dbg}.run | Boundary {} inspected, defects: {}", boundary.index, defects.len());
+                                                        metrics.rope_segment();
+                                                        for defect in defects {
+                                                            log::warn!(
+                                                                "{dbg}.run | Defect '{}' at {:.3} m, length: {:.1} mm, deviation: {:.1} mm",
+                                                                defect.kind.name(), defect.position, defect.length * 1000.0, defect.deviation * 1000.0,
+                                                            );
+                                                            metrics.rope_defect(defect);
+                                                        }
+                                                    }
+                                                    Err(err) => log::warn!("{dbg}.run | Boundary {} inspection error: {}", boundary.index, err),
+                                                }
+                                            }
+                                        }
+                                        None => segments.reset(),
+                                    }
+                                }
+                                Err(err) => {
+                                    match err {
+                                        kanal::ReceiveErrorTimeout::Timeout => {
+                                            if last_frame.elapsed() >= conf.reconnect.frame_timeout {
+                                                log::warn!("{dbg}.run | No frames from Camera for {:?}", last_frame.elapsed());
+                                                break 'camera;
+                                            }
+                                        }
+                                        _ => {
+                                            log::error!("{dbg}.run | Can't receive frame from Camera: {:?}", err);
+                                            break 'camera;
+                                        }
+                                    }
+                                }
+                            }
+                            if exit.load(Ordering::Acquire) {
+                                camera.exit();
+                                break 'main;
+                            }
+                        }
+                        camera.exit();
+                    }
+                    Err(err) => log::info!("{dbg}.run | Camera error: {:?}", err),
+                }
+                failures += 1;
+                let backoff = conf.reconnect.backoff(failures);
+                log::info!("{dbg}.run | Camera down, reconnecting in {:?}, failures in a row: {}", backoff, failures);
+                let until = Instant::now() + backoff;
+                while Instant::now() < until {
+                    if exit.load(Ordering::Acquire) {
+                        break 'main;
+                    }
+                    std::thread::sleep(until.saturating_duration_since(Instant::now()).min(RECV_TIMEOUT));
+                }
+            }
+            log::info!("{dbg}.run | Exit");
+            Ok(())
+        });
+        match handle {
+            Ok(handle) => {
+                self.handles.push(handle);
+                let r = match self.conf.wait_started {
+                    Some(_) => {
+                        log::info!("{}.run | Waiting while starting...", self.dbg);
+                        service_waiting.wait()
+                    }
+                    None => Ok(()),
+                };
+                log::info!("{}.run | Starting - ok", self.dbg);
+                r
+            }
+            Err(err) => {
+                let err = Error::new(&self.dbg, "run").pass_with("Start failed", err.to_string());
+                log::warn!("{}", err);
+                self.hoist.exit();
+                Err(err)
+            }
+        }
+    }
+    //
+    //
+    fn wait(&self) -> Result<(), Error> {
+        let result = self.handles.wait();
+        result.and(self.hoist.wait())
+    }
+    //
+    //
+    fn is_finished(&self) -> bool {
+        // The hoist is requested to exit by the service thread ended
+        self.handles.is_finished() && self.hoist.is_finished()
+    }
+    //
+    //
+    fn exit(&self) {
+        self.exit.store(true, Ordering::Release);
+        self.hoist.exit();
+    }
+}
@@ -0,0 +1,126 @@
+use std::time::Duration;
+use frdm_tools::camera::CameraConf;
+use sal_core::dbg::Dbg;
+use sal_sync::services::{conf::{ConfTree, ConfTreeGet}, entity::Name};
+
+use crate::modules::{HoistServiceConf, ImageConf, ReconnectConf, RopeConf};
+
+///
+/// ## The configuration parameters of the `RopeDefectService`
+///
+/// The rope position is read from the PLC by the own `hoist`, not scaled or offset for the cameras,
+/// the rope is expected to go along the X axis of the frame, its position growing to the right
+///
+/// ### Conf example
+/// ```yaml
+/// service RopeDefectService:
+///     wait-started: 10 ms         # optional, next service will wait until current completely started plus specified time
+///     camera:
+///         fps: Max
+///         resolution:
+///             width: 1200
+///             height: 800
+///         address: 192.168.10.14:2020
+///         pixel-format:  Mono8
+///     image:                      # preprocessing stages before the binarization, applied in the listed order
+///         gamma:
+///             factor: 95.0
+///         brightness-contrast:
+///             hist-clip-left: 1.0
+///             hist-clip-right: 1.0
+///     threshold: 120              # optional, binarization threshold 0..255, default - by the Otsu's method
+///     invert: false               # optional, true if the rope is darker than the background, default false
+///     rope:
+///         units: mm
+///         width: 35
+///         segment: 100
+///         max-speed: 400
+///         ...
+///     hoist:                      # rope position polling, the same parameters as the `HoistService`
+///         period: 20 ms           # the rope passes less than the doubled `rope.segment-threshold` in the period at the `rope.max-speed`
+///         type: u32
+///         units: mm
+///         unit 01:
+///             address: 192.168.100.1:502
+///             function-rope 03: 131   # rope position register, the hoist encoder
+///     reconnect:                  # optional, camera reconnection
+///         ...
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RopeDefectServiceConf {
+    pub name: Name,
+    /// Next service will wait until current completely started plus specified time, optional
+    pub wait_started: Option<Duration>,
+    pub camera: CameraConf,
+    pub image: ImageConf,
+    /// Binarization threshold 0..255, by the Otsu's method if None
+    pub threshold: Option<f64>,
+    /// The rope is darker than the background
+    pub invert: bool,
+    pub rope: RopeConf,
+    /// Polling of the rope position
+    pub hoist: HoistServiceConf,
+    pub reconnect: ReconnectConf,
+}
+//
+//
+impl RopeDefectServiceConf {
+    ///
+    /// Returns [RopeDefectServiceConf] built from `ConfTree`:
+    pub fn new(
+        parent: impl Into<String>,
+        conf: ConfTree,
+    ) -> Self {
+        let parent = parent.into();
+        let me = "RopeDefectServiceConf";
+        let dbg = Dbg::new(&parent, me);
+        let name = Name::new(parent, me);
+        log::trace!("{dbg}.new | name: {:?}", name);
+        let wait_started: Option<Duration> = conf.get_duration("wait-started").ok();
+        log::trace!("{}.new | wait-started: {:?}", dbg, wait_started);
+        let camera: ConfTree = conf.get("camera").expect(&format!("{dbg}.new | 'camera' - not found or wrong configuration"));
+        let camera = CameraConf::new(&name, &camera);
+        log::trace!("{dbg}.new | camera: {:#?}", camera);
+        let image = conf.get("image").expect(&format!("{dbg}.new | 'image' - not found or wrong configuration"));
+        let image = ImageConf::new(&dbg, image);
+        log::trace!("{}.new | image: {:?}", dbg, image);
+        let threshold: Option<f64> = conf.get("threshold");
+        log::trace!("{}.new | threshold: {:?}", dbg, threshold);
+        if threshold.is_some_and(|threshold| !(0.0..=255.0).contains(&threshold)) {
+            panic!("{dbg}.new | 'threshold' must be in 0..255, found: {:?}", threshold);
+        }
+        let invert: bool = conf.get("invert").unwrap_or(false);
+        log::trace!("{}.new | invert: {:?}", dbg, invert);
+        let rope = conf.get("rope").expect(&format!("{dbg}.new | 'rope' - not found or wrong configuration"));
+        let rope = RopeConf::new(&dbg, rope);
+        log::trace!("{}.new | rope: {:?}", dbg, rope);
+        let hoist = conf.get("hoist").expect(&format!("{dbg}.new | 'hoist' - not found or wrong configuration"));
+        let hoist = HoistServiceConf::new(&dbg, hoist);
+        log::trace!("{}.new | hoist: {:?}", dbg, hoist);
+        // The rope position is extrapolated between the samples, the speed change within the period isn't seen
+        let travel = hoist.period.as_secs_f64() * rope.max_speed;
+        if travel >= 2.0 * rope.segment_threshold {
+            panic!(
+                "{dbg}.new | 'hoist.period' x 'rope.max-speed' must be less than the doubled 'rope.segment-threshold', found: {:?} x {} m/s >= 2 x {} m",
+                hoist.period, rope.max_speed, rope.segment_threshold,
+            );
+        }
+        let reconnect: Option<ConfTree> = conf.get("reconnect");
+        let reconnect = match reconnect {
+            Some(reconnect) => ReconnectConf::new(&dbg, reconnect),
+            None => ReconnectConf::default(),
+        };
+        log::trace!("{}.new | reconnect: {:?}", dbg, reconnect);
+        Self {
+            name,
+            wait_started,
+            camera,
+            image,
+            threshold,
+            invert,
+            rope,
+            hoist,
+            reconnect,
+        }
+    }
+}
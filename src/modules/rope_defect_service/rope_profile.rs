@@ -0,0 +1,86 @@
+///
+/// Diameter profile of the rope along the frame
+///
+/// The rope is expected to go along the X axis of the frame,
+/// each column holds the top & bottom edges of the rope, px, if the rope is found in the column
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RopeProfile {
+    edges: Vec<Option<(f64, f64)>>,
+}
+//
+//
+impl RopeProfile {
+    ///
+    /// Returns [RopeProfile] new instance
+    /// - `edges` - top & bottom edges of the rope in each column, px
+    pub fn new(edges: Vec<Option<(f64, f64)>>) -> Self {
+        Self { edges }
+    }
+    ///
+    /// Returns [RopeProfile] of the binary `mask` of the rope, row by row, `width` x `height`
+    ///
+    /// The rope section of the column is the longest run of the nonzero pixels,
+    /// so the background spots above or below the rope are ignored
+    pub fn from_mask(mask: &[u8], width: usize, height: usize) -> Self {
+        let edges = (0..width)
+            .map(|x| {
+                let mut longest: Option<(usize, usize)> = None;
+                let mut start = None;
+                for y in 0..=height {
+                    let rope = y < height && mask.get(y * width + x).is_some_and(|pixel| *pixel != 0);
+                    match (rope, start) {
+                        (true, None) => start = Some(y),
+                        (false, Some(top)) => {
+                            if longest.is_none_or(|(t, b)| b - t < y - top) {
+                                longest = Some((top, y));
+                            }
+                            start = None;
+                        }
+                        _ => {}
+                    }
+                }
+                longest.map(|(top, bottom)| (top as f64, bottom as f64))
+            })
+            .collect();
+        Self::new(edges)
+    }
+    ///
+    /// Returns the top & bottom edges of the rope in each column, px
+    pub fn edges(&self) -> &[Option<(f64, f64)>] {
+        &self.edges
+    }
+    ///
+    /// Returns the number of the columns
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+    ///
+    /// Returns true if the rope is not found in any column
+    pub fn is_empty(&self) -> bool {
+        self.edges.iter().all(Option::is_none)
+    }
+    ///
+    /// Returns the median top & bottom edges & the median diameter of the rope, px
+    ///
+    /// The medians are not affected by the local defects shorter than the half of the frame
+    pub fn median(&self) -> Option<(f64, f64, f64)> {
+        let found: Vec<(f64, f64)> = self.edges.iter().flatten().copied().collect();
+        let top = Self::median_of(found.iter().map(|(top, _)| *top).collect())?;
+        let bottom = Self::median_of(found.iter().map(|(_, bottom)| *bottom).collect())?;
+        let diameter = Self::median_of(found.iter().map(|(top, bottom)| bottom - top).collect())?;
+        Some((top, bottom, diameter))
+    }
+    ///
+    /// Returns the median of the `values`
+    fn median_of(mut values: Vec<f64>) -> Option<f64> {
+        if values.is_empty() {
+            return None;
+        }
+        values.sort_by(|a, b| a.total_cmp(b));
+        let mid = values.len() / 2;
+        match values.len() % 2 {
+            0 => Some((values[mid - 1] + values[mid]) / 2.0),
+            _ => Some(values[mid]),
+        }
+    }
+}
@@ -0,0 +1,101 @@
+///
+/// Boundary between the rope segments passed by the camera
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SegmentBoundary {
+    /// Index of the boundary, the boundary `k` separates the segments `k - 1` & `k`
+    pub index: i64,
+    /// Rope point under the camera, m
+    pub position: f64,
+}
+///
+/// Tracks the rope segments passing the camera by the rope position updates
+///
+/// - The rope position is expected at the moment of each frame,
+///   so the frame is taken at the position it's added with
+/// - The rope is cut into the segments of the `segment` length,
+///   the segment `k` is `k * segment .. (k + 1) * segment` of the rope point under the camera
+/// - The rope point under the camera = rope position + `camera_offset`
+/// - The boundary is triggered once the rope point under the camera comes within the `threshold` of it, in either direction
+/// - The triggered boundary is re-armed when the rope point passed it further than the doubled `threshold`,
+///   so the encoder jitter, the rope stopped at the boundary or returned before passing it trigger the boundary once
+/// - The boundaries passed between the updates without coming within the `threshold` are counted as missed
+#[derive(Debug, Clone)]
+pub struct SegmentTracker {
+    segment: f64,
+    threshold: f64,
+    camera_offset: f64,
+    /// Rope point under the camera of the previous update
+    previous: Option<f64>,
+    /// The boundary triggered, not re-armed yet, & the side it was approached from: -1 - below, 1 - above, 0 - unknown
+    triggered: Option<(i64, f64)>,
+    missed: usize,
+}
+//
+//
+impl SegmentTracker {
+    ///
+    /// Returns [SegmentTracker] new instance
+    /// - `segment` - length of the segment, m
+    /// - `threshold` - allowed distance from the boundary, m, less than quarter of the `segment`
+    /// - `camera_offset` - rope point under the camera = rope position + `camera_offset`, m
+    pub fn new(segment: f64, threshold: f64, camera_offset: f64) -> Self {
+        Self {
+            segment,
+            threshold,
+            camera_offset,
+            previous: None,
+            triggered: None,
+            missed: 0,
+        }
+    }
+    ///
+    /// Adds the rope `position`, m, returns the boundary if the frame must be taken now
+    pub fn add(&mut self, position: f64) -> Option<SegmentBoundary> {
+        let point = position + self.camera_offset;
+        let triggered = self.triggered.map(|(index, _)| index);
+        if let Some((index, side)) = self.triggered {
+            let passed = match side == 0.0 {
+                true => (point - self.boundary(index)).abs(),
+                false => (self.boundary(index) - point) * side,
+            };
+            if passed > 2.0 * self.threshold {
+                self.triggered = None;
+            }
+        }
+        let index = (point / self.segment).round() as i64;
+        let boundary = match (point - self.boundary(index)).abs() <= self.threshold && self.triggered.is_none_or(|(triggered, _)| triggered != index) {
+            true => {
+                let side = self.previous.map_or(0.0, |previous| (previous - self.boundary(index)).signum());
+                self.triggered = Some((index, side));
+                Some(SegmentBoundary { index, position: point })
+            }
+            false => None,
+        };
+        if let Some(previous) = self.previous {
+            let (from, to) = (previous.min(point), previous.max(point));
+            let first = (from / self.segment).floor() as i64 + 1;
+            let last = (to / self.segment).floor() as i64;
+            self.missed += (first..=last)
+                .filter(|crossed| triggered != Some(*crossed) && boundary.is_none_or(|boundary| boundary.index != *crossed))
+                .count();
+        }
+        self.previous = Some(point);
+        boundary
+    }
+    ///
+    /// Returns the number of the boundaries passed without coming within the `threshold`
+    pub fn missed(&self) -> usize {
+        self.missed
+    }
+    ///
+    /// Forgets the previous rope position, used if the rope position was unknown for a while,
+    /// so the boundaries passed meanwhile are not counted as missed
+    pub fn reset(&mut self) {
+        self.previous = None;
+    }
+    ///
+    /// Returns the rope point of the boundary `index`, m
+    fn boundary(&self, index: i64) -> f64 {
+        index as f64 * self.segment
+    }
+}
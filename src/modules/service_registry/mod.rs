@@ -0,0 +1,5 @@
+mod service_registry;
+mod service_status;
+
+pub(crate) use service_registry::*;
+pub(crate) use service_status::*;
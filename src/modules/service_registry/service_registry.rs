@@ -0,0 +1,257 @@
+use std::{sync::{Arc, Mutex, MutexGuard}, time::{Duration, Instant}};
+use sal_core::{dbg::Dbg, error::Error};
+use sal_sync::services::Service;
+
+use crate::modules::{CameraPosition, Metrics, ServiceExit, ServiceStatus, Status};
+
+/// Interval of the service state checking while stopping
+const WAIT_INTERVAL: Duration = Duration::from_millis(10);
+/// Default time to wait for the single service to finish on stop & restart
+const STOP_TIMEOUT: Duration = Duration::from_secs(5);
+
+///
+/// Shared list of the application services
+/// 
+/// - Controls the services lifecycle over the [Service] trait - `run`, `exit`, `wait`, `is_finished`
+/// - Services are addressed by the name, the last part of the service [Name](sal_sync::services::entity::Name)
+/// - Cloned instances share the same services
+/// - The registry isn't locked while the stopped services are finishing & for the all services while starting,
+///   so the hung service doesn't block the state of the others
+#[derive(Clone)]
+pub struct ServiceRegistry {
+    entries: Arc<Mutex<Vec<ServiceEntry>>>,
+    metrics: Metrics,
+    stop_timeout: Duration,
+    dbg: Dbg,
+}
+///
+/// The service and its lifecycle state
+struct ServiceEntry {
+    name: String,
+    service: Box<dyn Service + Send>,
+    started: Option<Instant>,
+    /// Exit requested over the registry, the service is finishing
+    stopping: bool,
+    error: Option<String>,
+}
+//
+//
+impl ServiceEntry {
+    ///
+    /// Starts the service
+    fn run(&mut self) -> Result<(), Error> {
+        match self.service.run() {
+            Ok(_) => {
+                self.started = Some(Instant::now());
+                self.error = None;
+                Ok(())
+            }
+            Err(err) => {
+                self.started = None;
+                self.error = Some(err.to_string());
+                Err(err)
+            }
+        }
+    }
+    ///
+    /// Requests the service to exit, doesn't wait until it finished
+    fn exit(&mut self) {
+        if self.started.is_some() {
+            self.stopping = true;
+            self.service.exit();
+        }
+    }
+    ///
+    /// Returns the result of the service, if it's finished
+    fn stopped(&mut self) -> Option<Result<(), Error>> {
+        match self.started.is_none() || self.service.is_finished() {
+            true => Some(self.collect()),
+            false => None,
+        }
+    }
+    ///
+    /// Collects the result of the finished service
+    fn collect(&mut self) -> Result<(), Error> {
+        self.stopping = false;
+        if self.started.take().is_some() {
+            if let Err(err) = self.service.wait() {
+                self.error = Some(err.to_string());
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
+}
+//
+//
+impl ServiceRegistry {
+    ///
+    /// Returns [ServiceRegistry] new instance
+    /// - `metrics` - last positions of the services & recent rope defects are taken from
+    pub fn new(parent: impl Into<String>, metrics: Metrics) -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(vec![])),
+            metrics,
+            stop_timeout: STOP_TIMEOUT,
+            dbg: Dbg::new(parent, "ServiceRegistry"),
+        }
+    }
+    ///
+    /// Returns [ServiceRegistry] waiting for the single service to finish on stop & restart the `timeout`, default 5 s
+    pub fn with_stop_timeout(self, timeout: Duration) -> Self {
+        Self { stop_timeout: timeout, ..self }
+    }
+    ///
+    /// Adds the `service` to the registry, the service is not started
+    pub fn add(&self, service: Box<dyn Service + Send>) {
+        let name = service.name().me();
+        self.lock().push(ServiceEntry { name, service, started: None, stopping: false, error: None });
+    }
+    ///
+    /// Starts the all services in the order they were added
+    pub fn run_all(&self) -> Result<(), Error> {
+        let names: Vec<String> = self.lock().iter().map(|entry| entry.name.clone()).collect();
+        // The registry is locked for the single service only, so the state of the started ones is available
+        for name in names {
+            self.with(&name, "run_all", ServiceEntry::run)?;
+        }
+        Ok(())
+    }
+    ///
+    /// Stops the all running services in the reverse start order
+    /// - `timeout` - total time to wait for the services to finish
+    /// - Returns false if some services are not finished in time
+    pub fn stop_all(&self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        let names: Vec<String> = self.lock().iter().rev()
+            .filter(|entry| entry.started.is_some())
+            .map(|entry| entry.name.clone())
+            .collect();
+        let mut stopped = true;
+        for name in names {
+            log::info!("{}.stop_all | Service '{}'...", self.dbg, name);
+            match self.exit(&name, deadline) {
+                Ok(Ok(_)) => {}
+                Ok(Err(err)) => log::warn!("{}.stop_all | Service '{}' stopped with error: {}", self.dbg, name, err),
+                Err(err) => {
+                    log::warn!("{}.stop_all | {}", self.dbg, err);
+                    stopped = false;
+                }
+            }
+        }
+        stopped
+    }
+    ///
+    /// Returns the services finished by themselves since the last call, with their results
+    /// 
+    /// Services stopped over the registry are not returned
+    pub fn finished(&self) -> Vec<ServiceExit> {
+        self.lock().iter_mut()
+            .filter(|entry| entry.started.is_some() && !entry.stopping && entry.service.is_finished())
+            .map(|entry| {
+                let uptime = entry.started.map(|started| started.elapsed()).unwrap_or_default();
+                let result = entry.collect();
+                ServiceExit { name: entry.name.clone(), uptime, result }
+            })
+            .collect()
+    }
+    ///
+    /// Returns true if the all services are finished
+    pub fn is_finished(&self) -> bool {
+        self.lock().iter().all(|entry| entry.started.is_none() || entry.service.is_finished())
+    }
+    ///
+    /// Starts the stopped service `name`
+    pub fn run(&self, name: &str) -> Result<(), Error> {
+        log::info!("{}.run | Service '{}'...", self.dbg, name);
+        self.with(name, "run", |entry| match entry.started {
+            Some(_) => Ok(()),
+            None => entry.run(),
+        })
+    }
+    ///
+    /// Stops the service `name` and waits until it finished, up to the stop timeout
+    pub fn stop(&self, name: &str) -> Result<(), Error> {
+        log::info!("{}.stop | Service '{}'...", self.dbg, name);
+        self.exit(name, Instant::now() + self.stop_timeout)?
+    }
+    ///
+    /// Stops the service `name` and starts it again,
+    /// the service not finished in the stop timeout isn't started
+    pub fn restart(&self, name: &str) -> Result<(), Error> {
+        log::info!("{}.restart | Service '{}'...", self.dbg, name);
+        if let Err(err) = self.exit(name, Instant::now() + self.stop_timeout)? {
+            log::warn!("{}.restart | Service '{}' stopped with error: {}", self.dbg, name, err);
+        }
+        self.with(name, "restart", ServiceEntry::run)
+    }
+    ///
+    /// Returns true if the service `name` is registered
+    pub fn contains(&self, name: &str) -> bool {
+        self.lock().iter().any(|entry| entry.name == name)
+    }
+    ///
+    /// Returns the current state of the all services
+    pub fn status(&self) -> Status {
+        let mut entries = self.lock();
+        let services = entries.iter_mut()
+            .map(|entry| {
+                if entry.started.is_some() && entry.service.is_finished() {
+                    let _ = entry.collect();
+                }
+                ServiceStatus {
+                    name: entry.name.clone(),
+                    finished: entry.started.is_none(),
+                    error: entry.error.clone(),
+                    uptime: entry.started.map(|started| started.elapsed().as_secs_f64()),
+                    positions: self.metrics.last_positions(&entry.name).into_iter()
+                        .map(|(camera, position)| CameraPosition { camera: camera.0, position })
+                        .collect(),
+                }
+            })
+            .collect();
+        Status { services, defects: self.metrics.rope_defects() }
+    }
+    ///
+    /// Requests the service `name` to exit and waits until it finished, the registry is locked only to check the service
+    /// - Returns the result of the service, or Err if the service isn't finished until the `deadline`
+    fn exit(&self, name: &str, deadline: Instant) -> Result<Result<(), Error>, Error> {
+        self.with(name, "exit", |entry| {
+            entry.exit();
+            Ok(())
+        })?;
+        loop {
+            if let Some(result) = self.with(name, "exit", |entry| Ok(entry.stopped()))? {
+                return Ok(result);
+            }
+            if Instant::now() >= deadline {
+                return Err(Error::new(&self.dbg, "exit").err(format!("Service '{name}' not finished in time")));
+            }
+            std::thread::sleep(WAIT_INTERVAL);
+        }
+    }
+    ///
+    /// Applies `op` to the service `name`
+    fn with<T>(&self, name: &str, method: &str, op: impl FnOnce(&mut ServiceEntry) -> Result<T, Error>) -> Result<T, Error> {
+        let mut entries = self.lock();
+        match entries.iter_mut().find(|entry| entry.name == name) {
+            Some(entry) => op(entry),
+            None => Err(Error::new(&self.dbg, method).err(format!("Service '{name}' - not found"))),
+        }
+    }
+    ///
+    /// Returns the locked services, poisoned lock is recovered
+    fn lock(&self) -> MutexGuard<'_, Vec<ServiceEntry>> {
+        self.entries.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+//
+// 
+impl std::fmt::Debug for ServiceRegistry {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_struct("ServiceRegistry")
+            .field("dbg", &self.dbg)
+            .finish()
+    }
+}
@@ -0,0 +1,46 @@
+use std::time::Duration;
+use sal_core::error::Error;
+use serde::Serialize;
+
+use crate::modules::{Position, RopeDefect};
+
+///
+/// State of the single service, reported by the `GET /status`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ServiceStatus {
+    pub name: String,
+    /// The service is not running
+    pub finished: bool,
+    /// Last error the service started or finished with
+    pub error: Option<String>,
+    /// Time since the service started, seconds, None if finished
+    pub uptime: Option<f64>,
+    /// Last positions handled by the service, one per camera
+    pub positions: Vec<CameraPosition>,
+}
+///
+/// Last position of the single camera handled by the service
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct CameraPosition {
+    /// Id of the camera the position is found by
+    pub camera: usize,
+    pub position: Position<f64>,
+}
+///
+/// State of the all services, reported by the `GET /status`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Status {
+    pub services: Vec<ServiceStatus>,
+    /// Recent defects found by the `RopeDefectService`, the oldest first
+    pub defects: Vec<RopeDefect>,
+}
+///
+/// The service finished by itself
+#[derive(Debug, Clone)]
+pub struct ServiceExit {
+    pub name: String,
+    /// Time the service worked
+    pub uptime: Duration,
+    /// Result of the service thread, Err if failed or panicked
+    pub result: Result<(), Error>,
+}
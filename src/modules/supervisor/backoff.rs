@@ -0,0 +1,10 @@
+use std::time::Duration;
+
+///
+/// Returns the exponential delay after the `failures` in a row
+/// - `base` - delay after the first failure, doubled on each next failure in a row
+/// - `max` - the delay is limited to
+pub fn backoff(base: Duration, max: Duration, failures: usize) -> Duration {
+    let factor = 2u32.saturating_pow(failures.saturating_sub(1).min(31) as u32);
+    base.saturating_mul(factor).min(max)
+}
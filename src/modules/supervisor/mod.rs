@@ -0,0 +1,7 @@
+mod backoff;
+mod supervisor_conf;
+mod supervisor;
+
+pub(crate) use backoff::*;
+pub(crate) use supervisor_conf::*;
+pub(crate) use supervisor::*;
@@ -0,0 +1,132 @@
+use std::{sync::{atomic::{AtomicBool, Ordering}, Arc}, time::{Duration, Instant}};
+use sal_core::{dbg::Dbg, error::Error};
+
+use crate::modules::{ServiceExit, ServiceRegistry, SupervisorConf};
+
+/// Interval of the services state checking
+const CHECK_INTERVAL: Duration = Duration::from_millis(100);
+///
+/// Watches the services and restarts the finished ones
+/// 
+/// - Service finished by itself (returned, failed or panicked) is restarted according to the [RestartPolicy](crate::modules::RestartPolicy)
+/// - The restart is delayed, the delay grows with the failures in a row
+/// - After `max-failures` in a row of the single service all services are stopped and the error is returned,
+///   so the process exits and the system supervisor (systemd) can take over
+/// - Services stopped over the [ServiceRegistry] are not restarted
+/// - When the `shutdown` is set, all services are stopped in the reverse start order
+pub struct Supervisor {
+    conf: SupervisorConf,
+    registry: ServiceRegistry,
+    shutdown: Arc<AtomicBool>,
+    dbg: Dbg,
+}
+///
+/// The reason the [Supervisor] finished
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Shutdown {
+    /// All services finished by themselves and not to be restarted
+    Finished,
+    /// All services stopped by the shutdown request
+    Stopped,
+    /// Some services are not finished in the `shutdown-timeout` after the shutdown request
+    Timeout,
+}
+///
+/// Restart state of the single service
+#[derive(Debug)]
+struct Restart {
+    name: String,
+    failures: usize,
+    at: Option<Instant>,
+}
+//
+//
+impl Supervisor {
+    ///
+    /// Returns [Supervisor] new instance
+    /// - `shutdown` - all services are stopped when set, by the signal handler for example
+    pub fn new(parent: impl Into<String>, conf: SupervisorConf, registry: ServiceRegistry, shutdown: Arc<AtomicBool>) -> Self {
+        Self {
+            conf,
+            registry,
+            shutdown,
+            dbg: Dbg::new(parent, "Supervisor"),
+        }
+    }
+    ///
+    /// Watches the services until all of them are finished and not to be restarted or the shutdown requested
+    /// - Returns Err if some service failed `max-failures` times in a row
+    pub fn run(&self) -> Result<Shutdown, Error> {
+        let dbg = &self.dbg;
+        let mut restarts: Vec<Restart> = vec![];
+        loop {
+            if self.shutdown.load(Ordering::Acquire) {
+                log::info!("{dbg}.run | Shutdown requested, stopping services...");
+                return match self.registry.stop_all(self.conf.shutdown_timeout) {
+                    true => Ok(Shutdown::Stopped),
+                    false => Ok(Shutdown::Timeout),
+                };
+            }
+            for exit in self.registry.finished() {
+                let restart = match restarts.iter_mut().find(|restart| restart.name == exit.name) {
+                    Some(restart) => restart,
+                    None => {
+                        restarts.push(Restart { name: exit.name.clone(), failures: 0, at: None });
+                        restarts.last_mut().unwrap()
+                    }
+                };
+                self.schedule(restart, &exit)?;
+            }
+            let now = Instant::now();
+            for restart in restarts.iter_mut().filter(|restart| restart.at.is_some_and(|at| at <= now)) {
+                restart.at = None;
+                log::info!("{dbg}.run | Restarting service '{}', failures in a row: {}", restart.name, restart.failures);
+                if let Err(err) = self.registry.run(&restart.name) {
+                    let exit = ServiceExit { name: restart.name.clone(), uptime: Duration::ZERO, result: Err(err) };
+                    self.schedule(restart, &exit)?;
+                }
+            }
+            if restarts.iter().all(|restart| restart.at.is_none()) && self.registry.is_finished() {
+                log::info!("{dbg}.run | All services finished");
+                return Ok(Shutdown::Finished);
+            }
+            std::thread::sleep(CHECK_INTERVAL);
+        }
+    }
+    ///
+    /// Schedules the restart of the finished service according to the restart policy
+    /// - Returns Err if the service failed `max-failures` times in a row, all services are stopped
+    fn schedule(&self, restart: &mut Restart, exit: &ServiceExit) -> Result<(), Error> {
+        let dbg = &self.dbg;
+        if exit.uptime >= self.conf.reset_after {
+            restart.failures = 0;
+        }
+        match &exit.result {
+            Ok(_) => log::info!("{dbg}.schedule | Service '{}' finished after {:?}", exit.name, exit.uptime),
+            Err(err) => {
+                restart.failures += 1;
+                log::warn!("{dbg}.schedule | Service '{}' failed after {:?}, failures in a row: {}, error: {}", exit.name, exit.uptime, restart.failures, err);
+                if restart.failures >= self.conf.max_failures {
+                    self.registry.stop_all(self.conf.shutdown_timeout);
+                    return Err(Error::new(dbg, "schedule").err(format!("Service '{}' failed {} times in a row", exit.name, restart.failures)));
+                }
+            }
+        }
+        if self.conf.restart.restarts(&exit.result) {
+            let backoff = self.conf.backoff(restart.failures);
+            log::info!("{dbg}.schedule | Service '{}' will be restarted in {:?}", exit.name, backoff);
+            restart.at = Some(Instant::now() + backoff);
+        }
+        Ok(())
+    }
+}
+//
+// 
+impl std::fmt::Debug for Supervisor {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_struct("Supervisor")
+            .field("dbg", &self.dbg)
+            .finish()
+    }
+}
@@ -0,0 +1,130 @@
+use std::time::Duration;
+use sal_core::{dbg::Dbg, error::Error};
+use sal_sync::services::{conf::{ConfTree, ConfTreeGet}, entity::Name};
+
+use crate::modules::backoff;
+
+///
+/// ## The configuration parameters for the `Supervisor`
+/// 
+/// ### Conf example
+/// ```yaml
+/// supervisor:                     # optional, restarting of the finished services
+///     restart: on-failure         # optional, never / on-failure (default) / always
+///     backoff: 1 s                # optional, delay before the restart, doubled on each failure in a row, default 1 s
+///     max-backoff: 60 s           # optional, maximum delay before the restart, default 60 s
+///     max-failures: 5             # optional, process exits after this number of failures in a row, default 5
+///     reset-after: 60 s           # optional, failures in a row are forgotten if the service worked this time, default 60 s
+///     shutdown-timeout: 5 s       # optional, time to wait for the services to finish on SIGINT / SIGTERM & for the single service on HTTP stop / restart, default 5 s
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SupervisorConf {
+    pub name: Name,
+    /// Which finished services are restarted
+    pub restart: RestartPolicy,
+    /// Delay before the restart after the first failure, doubled on each failure in a row
+    pub backoff: Duration,
+    /// Maximum delay before the restart
+    pub max_backoff: Duration,
+    /// Process exits after this number of failures in a row of the single service
+    pub max_failures: usize,
+    /// Failures in a row are forgotten if the service worked this time
+    pub reset_after: Duration,
+    /// Time to wait for the services to finish on shutdown
+    pub shutdown_timeout: Duration,
+}
+//
+// 
+impl SupervisorConf {
+    ///
+    /// Returns [SupervisorConf] built from `ConfTree`:
+    pub fn new(
+        parent: impl Into<String>,
+        conf: ConfTree,
+    ) -> Self {
+        let parent = parent.into();
+        let me = "SupervisorConf";
+        let dbg = Dbg::new(&parent, me);
+        let name = Name::new(parent, me);
+        log::trace!("{dbg}.new | name: {:?}", name);
+        let restart: Option<String> = conf.get("restart");
+        let restart = match restart {
+            Some(restart) => Self::restart_from_str(&restart).expect(&format!("{dbg}.new | Unknown 'restart' {restart}")),
+            None => RestartPolicy::OnFailure,
+        };
+        log::trace!("{}.new | restart: {:?}", dbg, restart);
+        let backoff = conf.get_duration("backoff").unwrap_or(Duration::from_secs(1));
+        log::trace!("{}.new | backoff: {:?}", dbg, backoff);
+        let max_backoff = conf.get_duration("max-backoff").unwrap_or(Duration::from_secs(60));
+        log::trace!("{}.new | max-backoff: {:?}", dbg, max_backoff);
+        let max_failures: usize = conf.get("max-failures").unwrap_or(5);
+        log::trace!("{}.new | max-failures: {:?}", dbg, max_failures);
+        let reset_after = conf.get_duration("reset-after").unwrap_or(Duration::from_secs(60));
+        log::trace!("{}.new | reset-after: {:?}", dbg, reset_after);
+        let shutdown_timeout = conf.get_duration("shutdown-timeout").unwrap_or(Duration::from_secs(5));
+        log::trace!("{}.new | shutdown-timeout: {:?}", dbg, shutdown_timeout);
+        Self {
+            name,
+            restart,
+            backoff,
+            max_backoff,
+            max_failures,
+            reset_after,
+            shutdown_timeout,
+        }
+    }
+    ///
+    /// Returns the delay before the restart after the `failures` in a row
+    pub fn backoff(&self, failures: usize) -> Duration {
+        backoff(self.backoff, self.max_backoff, failures)
+    }
+    ///
+    /// Returns [RestartPolicy] parsed from string
+    fn restart_from_str(restart: &str) -> Result<RestartPolicy, Error> {
+        match restart.to_lowercase().as_str() {
+            "never" => Ok(RestartPolicy::Never),
+            "on-failure" => Ok(RestartPolicy::OnFailure),
+            "always" => Ok(RestartPolicy::Always),
+            _ => Err(Error::new("SupervisorConf", "restart_from_str").err(format!("Unknown restart policy {}", restart))),
+        }
+    }
+}
+//
+//
+impl Default for SupervisorConf {
+    fn default() -> Self {
+        Self {
+            name: Name::new("", "SupervisorConf"),
+            restart: RestartPolicy::OnFailure,
+            backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+            max_failures: 5,
+            reset_after: Duration::from_secs(60),
+            shutdown_timeout: Duration::from_secs(5),
+        }
+    }
+}
+///
+/// Which finished services are restarted by the `Supervisor`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RestartPolicy {
+    /// Finished services are not restarted
+    Never,
+    /// Services finished with error (or panicked) are restarted
+    OnFailure,
+    /// All finished services are restarted
+    Always,
+}
+//
+//
+impl RestartPolicy {
+    ///
+    /// Returns true if the service finished with `result` to be restarted
+    pub fn restarts<T, E>(&self, result: &Result<T, E>) -> bool {
+        match self {
+            RestartPolicy::Never => false,
+            RestartPolicy::OnFailure => result.is_err(),
+            RestartPolicy::Always => true,
+        }
+    }
+}
@@ -0,0 +1,9 @@
+mod oscillation;
+mod sway_analysis;
+mod sway_conf;
+mod sway;
+
+pub use oscillation::*;
+pub use sway_analysis::*;
+pub use sway_conf::*;
+pub use sway::*;
@@ -0,0 +1,107 @@
+use std::collections::VecDeque;
+
+use crate::modules::SwayAxis;
+
+///
+/// Angular velocity & swing period of the angle along the single axis
+///
+/// - Angular velocity is the difference of the last two angles over the time between them
+/// - Period is estimated by the zero crossings of the angle:
+///   the crossing is counted when the angle leaves the `deadband` on the other side of zero,
+///   its time is interpolated between the samples around zero,
+///   the period is the doubled mean time between the last `crossings`
+#[derive(Debug, Clone)]
+pub struct Oscillation {
+    /// Hysteresis of the zero crossings, rad
+    deadband: f64,
+    /// Number of the last half-periods averaged
+    crossings: usize,
+    /// The period is unknown if no crossings this time, s
+    max_period: f64,
+    /// Time & angle of the last sample
+    last: Option<(f64, f64)>,
+    /// Last angular velocity
+    rate: f64,
+    /// Side of zero the angle has been out of the deadband last time: -1, 1, 0 - not yet
+    side: i8,
+    /// Time of the last raw zero crossing, waiting the angle to leave the deadband
+    zero: Option<f64>,
+    /// Times of the last crossings
+    times: VecDeque<f64>,
+}
+//
+//
+impl Oscillation {
+    ///
+    /// Returns [Oscillation] new instance
+    /// - `deadband` - hysteresis of the zero crossings, rad
+    /// - `crossings` - number of the last half-periods averaged, at least 1
+    /// - `max_period` - the period is unknown if no crossings this time, s
+    pub fn new(deadband: f64, crossings: usize, max_period: f64) -> Self {
+        Self {
+            deadband,
+            crossings: crossings.max(1),
+            max_period,
+            last: None,
+            rate: 0.0,
+            side: 0,
+            zero: None,
+            times: VecDeque::new(),
+        }
+    }
+    ///
+    /// Adds the `angle`, rad, of the `time`, s, returns the sway along the axis
+    pub fn add(&mut self, time: f64, angle: f64) -> SwayAxis {
+        if let Some((last_time, last_angle)) = self.last && time > last_time {
+            self.rate = (angle - last_angle) / (time - last_time);
+            if last_angle != 0.0 && last_angle.signum() != angle.signum() {
+                self.zero = Some(last_time + (time - last_time) * last_angle / (last_angle - angle));
+            }
+        }
+        if angle == 0.0 {
+            self.zero = Some(time);
+        }
+        self.last = Some((time, angle));
+        let side = match angle {
+            _ if angle > self.deadband => 1,
+            _ if angle < -self.deadband => -1,
+            _ => 0,
+        };
+        if side != 0 && side != self.side {
+            if self.side != 0 {
+                let crossing = self.zero.unwrap_or(time);
+                // The swing has been stopped in between, previous crossings belong to the other swing
+                if self.times.back().is_some_and(|last| crossing - last > self.max_period) {
+                    self.times.clear();
+                }
+                self.times.push_back(crossing);
+                if self.times.len() > self.crossings + 1 {
+                    self.times.pop_front();
+                }
+            }
+            self.side = side;
+            self.zero = None;
+        }
+        SwayAxis {
+            angle,
+            rate: self.rate,
+            period: self.period(time),
+        }
+    }
+    ///
+    /// Returns the swing period, s, at the `time`, if the angle crossed zero recently
+    fn period(&mut self, time: f64) -> Option<f64> {
+        match (self.times.front(), self.times.back()) {
+            (Some(first), Some(last)) if self.times.len() >= 2 => {
+                match time - last <= self.max_period {
+                    true => Some(2.0 * (last - first) / (self.times.len() - 1) as f64),
+                    false => {
+                        self.times.clear();
+                        None
+                    }
+                }
+            }
+            _ => None,
+        }
+    }
+}
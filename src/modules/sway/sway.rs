@@ -0,0 +1,20 @@
+///
+/// Sway of the load along the single axis
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SwayAxis {
+    /// Angle of the rope to the vertical, rad
+    pub angle: f64,
+    /// Angular velocity of the rope, rad/s
+    pub rate: f64,
+    /// Dominant swing period, s, if some swing detected
+    pub period: Option<f64>,
+}
+///
+/// Sway of the load on the rope, estimated from the world position of the target
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Sway {
+    /// Sway along the reported X axis
+    pub x: SwayAxis,
+    /// Sway along the reported Y axis
+    pub y: SwayAxis,
+}
@@ -0,0 +1,39 @@
+use crate::modules::{Oscillation, Position, Sway, SwayConf};
+
+///
+/// Sway analysis of the load on the rope
+///
+/// Converts the lateral offset of the load from the point under the suspension (the origin of the world position)
+/// into the angle of the rope `atan(offset / length)` along each axis,
+/// then estimates angular velocity & swing period along each axis by [Oscillation]
+#[derive(Debug, Clone)]
+pub struct SwayAnalysis {
+    x: Oscillation,
+    y: Oscillation,
+}
+//
+//
+impl SwayAnalysis {
+    ///
+    /// Returns [SwayAnalysis] new instance
+    pub fn new(conf: &SwayConf) -> Self {
+        let max_period = conf.max_period.as_secs_f64();
+        Self {
+            x: Oscillation::new(conf.deadband, conf.crossings, max_period),
+            y: Oscillation::new(conf.deadband, conf.crossings, max_period),
+        }
+    }
+    ///
+    /// Adds the `offset` of the load, m, of the `time`, s, returns the [Sway]
+    /// - `length` - pendulum length, from the suspension to the load, m
+    /// - None if the `length` is not positive
+    pub fn add(&mut self, time: f64, offset: Position<f64>, length: f64) -> Option<Sway> {
+        match length > 0.0 {
+            true => Some(Sway {
+                x: self.x.add(time, offset.x.atan2(length)),
+                y: self.y.add(time, offset.y.atan2(length)),
+            }),
+            false => None,
+        }
+    }
+}
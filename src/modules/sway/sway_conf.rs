@@ -0,0 +1,105 @@
+use std::time::Duration;
+use sal_core::dbg::Dbg;
+use sal_sync::services::{conf::{ConfTree, ConfTreeGet}, entity::Name};
+
+use crate::modules::Units;
+
+///
+/// ## The configuration parameters of the sway analysis of the load
+///
+/// Applied to the world position, the `world` transform of the camera required,
+/// its `origin` must be the point right under the suspension of the rope,
+/// the fused 3D positions have no sway, only the single camera ones
+///
+/// ### Conf example
+/// ```yaml
+/// sway:                           # optional, sway angle, angular velocity & swing period of the load
+///     units: mm                   # optional, units of the `rope-length` & `rope-offset`, m / cm / mm, default mm
+///     rope-length: 12000          # optional, used if no rope length read by the `HoistService`
+///     rope-offset: 500            # optional, pendulum length = rope length + rope-offset, default 0
+///     deadband: 0.002             # optional, hysteresis of the zero crossings of the angle, rad, default 0.002
+///     crossings: 4                # optional, number of the last half-periods averaged, default 4
+///     max-period: 20 s            # optional, the period is unknown if no zero crossings this time, default 20 s
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwayConf {
+    pub name: Name,
+    /// Rope length, m, used if no rope length read by the `HoistService`
+    pub rope_length: Option<f64>,
+    /// Pendulum length = rope length + rope offset, m
+    pub rope_offset: f64,
+    /// Hysteresis of the zero crossings of the angle, rad
+    pub deadband: f64,
+    /// Number of the last half-periods averaged
+    pub crossings: usize,
+    /// The period is unknown if no zero crossings this time
+    pub max_period: Duration,
+}
+//
+//
+impl SwayConf {
+    ///
+    /// Returns [SwayConf] built from `ConfTree`:
+    pub fn new(
+        parent: impl Into<String>,
+        conf: ConfTree,
+    ) -> Self {
+        let parent = parent.into();
+        let me = "SwayConf";
+        let dbg = Dbg::new(&parent, me);
+        let name = Name::new(parent, me);
+        log::trace!("{dbg}.new | name: {:?}", name);
+        let units: Option<String> = conf.get("units");
+        let units: Units = units
+            .map(|units| units.parse().unwrap_or_else(|err| panic!("{dbg}.new | 'units' - wrong configuration: {err}")))
+            .unwrap_or_default();
+        log::trace!("{}.new | units: {:?}", dbg, units);
+        let rope_length: Option<f64> = conf.get("rope-length");
+        let rope_length = rope_length.map(|length| units.to_meters(length));
+        log::trace!("{}.new | rope-length: {:?}", dbg, rope_length);
+        let rope_offset: f64 = conf.get("rope-offset").unwrap_or(0.0);
+        let rope_offset = units.to_meters(rope_offset);
+        log::trace!("{}.new | rope-offset: {:?}", dbg, rope_offset);
+        let deadband: f64 = conf.get("deadband").unwrap_or(0.002);
+        log::trace!("{}.new | deadband: {:?}", dbg, deadband);
+        if deadband < 0.0 {
+            panic!("{dbg}.new | 'deadband' must not be negative, found: {deadband}");
+        }
+        let crossings: usize = conf.get("crossings").unwrap_or(4);
+        log::trace!("{}.new | crossings: {:?}", dbg, crossings);
+        if crossings == 0 {
+            panic!("{dbg}.new | 'crossings' must be positive");
+        }
+        let max_period = conf.get_duration("max-period").unwrap_or(Duration::from_secs(20));
+        log::trace!("{}.new | max-period: {:?}", dbg, max_period);
+        Self {
+            name,
+            rope_length,
+            rope_offset,
+            deadband,
+            crossings,
+            max_period,
+        }
+    }
+    ///
+    /// Returns the pendulum length, m
+    /// - `rope` - rope length read by the `HoistService`, m, the configured `rope_length` is used if None
+    /// - None if the rope length is unknown
+    pub fn length(&self, rope: Option<f64>) -> Option<f64> {
+        rope.or(self.rope_length).map(|length| length + self.rope_offset)
+    }
+}
+//
+//
+impl Default for SwayConf {
+    fn default() -> Self {
+        Self {
+            name: Name::new("", "SwayConf"),
+            rope_length: None,
+            rope_offset: 0.0,
+            deadband: 0.002,
+            crossings: 4,
+            max_period: Duration::from_secs(20),
+        }
+    }
+}
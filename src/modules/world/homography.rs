@@ -0,0 +1,130 @@
+use crate::modules::{solve_linear, Position};
+
+///
+/// Planar homography, maps the points of one plane into the other
+///
+/// Estimated from 4 or more reference point pairs by the normalized linear least squares (DLT), `h33 = 1`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Homography {
+    matrix: [[f64; 3]; 3],
+}
+//
+//
+impl Homography {
+    ///
+    /// Returns [Homography] of the `matrix`
+    pub fn new(matrix: [[f64; 3]; 3]) -> Self {
+        Self { matrix }
+    }
+    ///
+    /// Returns [Homography] mapping the `from` points into the `to` points of the `references`
+    /// - None if less then 4 references or they are degenerate (no 4 of them in the general position)
+    pub fn from_references(references: &[(Position<f64>, Position<f64>)]) -> Option<Self> {
+        if references.len() < 4 {
+            return None;
+        }
+        let from = Self::normalization(references.iter().map(|(from, _)| *from));
+        let to = Self::normalization(references.iter().map(|(_, to)| *to));
+        let mut ata = [[0.0; 8]; 8];
+        let mut atb = [0.0; 8];
+        for (p, w) in references {
+            let (u, v) = Self::transform(&from, *p)?;
+            let (x, y) = Self::transform(&to, *w)?;
+            let rows = [
+                ([u, v, 1.0, 0.0, 0.0, 0.0, -u * x, -v * x], x),
+                ([0.0, 0.0, 0.0, u, v, 1.0, -u * y, -v * y], y),
+            ];
+            for (a, b) in rows {
+                for i in 0..8 {
+                    for j in 0..8 {
+                        ata[i][j] += a[i] * a[j];
+                    }
+                    atb[i] += a[i] * b;
+                }
+            }
+        }
+        let h = solve_linear(ata, atb)?;
+        let normalized = [[h[0], h[1], h[2]], [h[3], h[4], h[5]], [h[6], h[7], 1.0]];
+        // H = T_to^-1 * Hn * T_from
+        let matrix = Self::multiply(&Self::inverse_normalization(&to), &Self::multiply(&normalized, &from));
+        let scale = matrix[2][2];
+        if scale.abs() < f64::EPSILON {
+            return None;
+        }
+        let homography = Self { matrix: matrix.map(|row| row.map(|value| value / scale)) };
+        // Collinear references make the system rank deficient, but the solution may exist numerically
+        match references.iter().all(|(from, _)| homography.apply(*from).is_some()) && !Self::collinear(references) {
+            true => Some(homography),
+            false => None,
+        }
+    }
+    ///
+    /// Returns the matrix of the homography
+    pub fn matrix(&self) -> &[[f64; 3]; 3] {
+        &self.matrix
+    }
+    ///
+    /// Returns the `point` mapped by the homography
+    /// - None if the point is mapped to the infinity
+    pub fn apply(&self, point: Position<f64>) -> Option<Position<f64>> {
+        let p = [point.x, point.y, 1.0];
+        let [x, y, w] = self.matrix.map(|row| row[0] * p[0] + row[1] * p[1] + row[2] * p[2]);
+        match w.abs() > f64::EPSILON {
+            true => Some(Position::new(x / w, y / w)),
+            false => None,
+        }
+    }
+    ///
+    /// Returns true if all the `from` or all the `to` points of the `references` lie on the single line
+    fn collinear(references: &[(Position<f64>, Position<f64>)]) -> bool {
+        let collinear = |points: Vec<Position<f64>>| -> bool {
+            let size = points.iter()
+                .flat_map(|a| points.iter().map(move |b| ((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt()))
+                .fold(0.0, f64::max);
+            // Area of the triangle relative to the square of the points spread
+            let tolerance = size * size * 1e-6;
+            !points.iter().enumerate().any(|(i, a)| {
+                points.iter().enumerate().skip(i + 1).any(|(j, b)| {
+                    points.iter().skip(j + 1).any(|c| ((b.x - a.x) * (c.y - a.y) - (c.x - a.x) * (b.y - a.y)).abs() > tolerance)
+                })
+            })
+        };
+        collinear(references.iter().map(|(from, _)| *from).collect()) || collinear(references.iter().map(|(_, to)| *to).collect())
+    }
+    ///
+    /// Returns the similarity transform moving the centroid of the `points` to the origin
+    /// and scaling their mean distance to the origin to `sqrt(2)`
+    fn normalization(points: impl Iterator<Item = Position<f64>> + Clone) -> [[f64; 3]; 3] {
+        let count = points.clone().count() as f64;
+        let (sx, sy) = points.clone().fold((0.0, 0.0), |(sx, sy), p| (sx + p.x, sy + p.y));
+        let (cx, cy) = (sx / count, sy / count);
+        let mean = points.map(|p| ((p.x - cx).powi(2) + (p.y - cy).powi(2)).sqrt()).sum::<f64>() / count;
+        let scale = match mean > f64::EPSILON {
+            true => std::f64::consts::SQRT_2 / mean,
+            false => 1.0,
+        };
+        [[scale, 0.0, -scale * cx], [0.0, scale, -scale * cy], [0.0, 0.0, 1.0]]
+    }
+    ///
+    /// Returns the inverse of the `normalization` similarity transform
+    fn inverse_normalization(t: &[[f64; 3]; 3]) -> [[f64; 3]; 3] {
+        let scale = t[0][0];
+        let (cx, cy) = (-t[0][2] / scale, -t[1][2] / scale);
+        [[1.0 / scale, 0.0, cx], [0.0, 1.0 / scale, cy], [0.0, 0.0, 1.0]]
+    }
+    ///
+    /// Returns the `point` transformed by the similarity transform `t`
+    fn transform(t: &[[f64; 3]; 3], point: Position<f64>) -> Option<(f64, f64)> {
+        let x = t[0][0] * point.x + t[0][2];
+        let y = t[1][1] * point.y + t[1][2];
+        match x.is_finite() && y.is_finite() {
+            true => Some((x, y)),
+            false => None,
+        }
+    }
+    ///
+    /// Returns the product of the matrices
+    fn multiply(a: &[[f64; 3]; 3], b: &[[f64; 3]; 3]) -> [[f64; 3]; 3] {
+        std::array::from_fn(|row| std::array::from_fn(|col| (0..3).map(|i| a[row][i] * b[i][col]).sum()))
+    }
+}
@@ -0,0 +1,31 @@
+///
+/// Returns the solution of the linear system `a * x = b`, Gaussian elimination with partial pivoting
+/// - None if the system is singular
+pub fn solve_linear<const N: usize>(mut a: [[f64; N]; N], mut b: [f64; N]) -> Option<[f64; N]> {
+    let scale = a.iter().flatten().fold(0.0_f64, |max, v| max.max(v.abs()));
+    if scale == 0.0 {
+        return None;
+    }
+    for col in 0..N {
+        let pivot = (col..N).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivot][col].abs() < scale * 1e-12 {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        let pivot = a[col];
+        for row in col + 1..N {
+            let factor = a[row][col] / pivot[col];
+            for (value, pivot) in a[row].iter_mut().zip(pivot).skip(col) {
+                *value -= factor * pivot;
+            }
+            b[row] -= factor * b[col];
+        }
+    }
+    let mut x = [0.0; N];
+    for row in (0..N).rev() {
+        let sum: f64 = (row + 1..N).map(|i| a[row][i] * x[i]).sum();
+        x[row] = (b[row] - sum) / a[row][row];
+    }
+    Some(x)
+}
@@ -0,0 +1,11 @@
+mod homography;
+mod linear_system;
+mod units;
+mod world_conf;
+mod world_transform;
+
+pub use homography::*;
+pub use linear_system::*;
+pub use units::*;
+pub use world_conf::*;
+pub use world_transform::*;
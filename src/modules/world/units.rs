@@ -0,0 +1,48 @@
+use std::str::FromStr;
+use sal_core::error::Error;
+
+///
+/// Units of the world coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Units {
+    M,
+    Cm,
+    #[default]
+    Mm,
+}
+//
+//
+impl Units {
+    ///
+    /// Returns the number of the units in the meter
+    pub fn per_meter(self) -> f64 {
+        match self {
+            Units::M => 1.0,
+            Units::Cm => 100.0,
+            Units::Mm => 1000.0,
+        }
+    }
+    ///
+    /// Returns the `value` of the units in meters
+    pub fn to_meters(self, value: f64) -> f64 {
+        value / self.per_meter()
+    }
+    ///
+    /// Returns the `value` in meters in the units
+    pub fn of_meters(self, value: f64) -> f64 {
+        value * self.per_meter()
+    }
+}
+//
+//
+impl FromStr for Units {
+    type Err = Error;
+    fn from_str(units: &str) -> Result<Self, Self::Err> {
+        match units.trim().to_lowercase().as_str() {
+            "m" => Ok(Units::M),
+            "cm" => Ok(Units::Cm),
+            "mm" => Ok(Units::Mm),
+            _ => Err(Error::new("Units", "from_str").err(format!("Unknown units '{}', m / cm / mm expected", units))),
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use sal_core::dbg::Dbg;
+use sal_sync::services::{conf::{ConfTree, ConfTreeGet}, entity::Name};
+
+use crate::modules::{Axis, Homography, Intrinsics, Position, Projection, Units, WorldTransform};
+
+///
+/// ## The configuration parameters of the pixel to world transform of the camera
+///
+/// Applied to the filtered position, the world position is written by the `ModbusService`
+///
+/// ### Conf example
+/// ```yaml
+/// world:                          # optional, pixel to world transform of the filtered position
+///     units: mm                   # optional, units of the `homography`, `distance`, `rope-offset` & `origin`, m / cm / mm, default mm
+///     homography:                 # planar homography by 4 or more reference points: [pixel x, pixel y, world x, world y],
+///                                 # height-independent, the target must move in the plane of the points, the rope length isn't applied
+///         - [102.0, 87.0, 0.0, 0.0]
+///         - [1090.0, 92.0, 2000.0, 0.0]
+///         - [1095.0, 710.0, 2000.0, 1500.0]
+///         - [98.0, 705.0, 0.0, 1500.0]
+///     # pinhole:                  # or pinhole projection, `intrinsics` or `calibration` of the camera required
+///     #     distance: 5000        # fixed working distance from the camera to the plane of the target
+///     #     # rope-offset: -300   # or working distance = rope length of the `HoistService` + rope-offset,
+///     #     #                     # the position isn't published while the rope length is unknown
+///     origin: [1000.0, 0.0]       # optional, world point the positions are relative to, default [0, 0]
+///     axes: [x, -y]               # optional, world axes the reported X & Y are taken along, x / -x / y / -y, default [x, y]
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorldConf {
+    pub name: Name,
+    /// Units of the configured world values
+    pub units: Units,
+    pub projection: Projection,
+    /// World point the positions are relative to, m
+    pub origin: Position<f64>,
+    /// World axes the reported X & Y are taken along
+    pub axes: [Axis; 2],
+}
+//
+//
+impl WorldConf {
+    ///
+    /// Returns [WorldConf] built from `ConfTree`:
+    /// - `intrinsics` - of the camera, required for the `pinhole` projection
+    pub fn new(
+        parent: impl Into<String>,
+        conf: ConfTree,
+        intrinsics: Option<Intrinsics>,
+    ) -> Self {
+        let parent = parent.into();
+        let me = "WorldConf";
+        let dbg = Dbg::new(&parent, me);
+        let name = Name::new(parent, me);
+        log::trace!("{dbg}.new | name: {:?}", name);
+        let units: Option<String> = conf.get("units");
+        let units: Units = units
+            .map(|units| units.parse().unwrap_or_else(|err| panic!("{dbg}.new | 'units' - wrong configuration: {err}")))
+            .unwrap_or_default();
+        log::trace!("{}.new | units: {:?}", dbg, units);
+        let homography: Option<ConfTree> = conf.get("homography");
+        let pinhole: Option<ConfTree> = conf.get("pinhole");
+        let projection = match (homography, pinhole) {
+            (Some(homography), None) => {
+                let references = homography.conf.as_sequence().expect(&format!("{dbg}.new | 'homography' must be a list"));
+                let references: Vec<_> = references.iter().enumerate().map(|(i, reference)| {
+                    let values = Self::values(&dbg, &format!("homography[{i}]"), reference);
+                    match values.as_slice() {
+                        &[px, py, wx, wy] => (Position::new(px, py), Position::new(units.to_meters(wx), units.to_meters(wy))),
+                        _ => panic!("{dbg}.new | 'homography[{i}]' must be [pixel x, pixel y, world x, world y], found {} values", values.len()),
+                    }
+                }).collect();
+                if references.len() < 4 {
+                    panic!("{dbg}.new | 'homography' requires 4 or more reference points, found {}", references.len());
+                }
+                let homography = Homography::from_references(&references)
+                    .unwrap_or_else(|| panic!("{dbg}.new | 'homography' - degenerate reference points, 4 of them in the general position required"));
+                Projection::Homography(homography)
+            }
+            (None, Some(pinhole)) => {
+                let distance: Option<f64> = pinhole.get("distance");
+                let rope_offset: Option<f64> = pinhole.get("rope-offset");
+                match (distance, rope_offset) {
+                    (Some(distance), None) if distance <= 0.0 => panic!("{dbg}.new | 'pinhole.distance' must be positive, found: {distance}"),
+                    (Some(_), None) | (None, Some(_)) => {}
+                    (Some(_), Some(_)) => panic!("{dbg}.new | 'pinhole.distance' & 'pinhole.rope-offset' - only one of them expected"),
+                    (None, None) => panic!("{dbg}.new | 'pinhole.distance' or 'pinhole.rope-offset' - not found or wrong configuration"),
+                }
+                let intrinsics = intrinsics.expect(&format!("{dbg}.new | 'pinhole' requires 'intrinsics' or 'calibration' of the camera"));
+                Projection::Pinhole {
+                    intrinsics,
+                    distance: distance.map(|distance| units.to_meters(distance)),
+                    rope_offset: rope_offset.map(|offset| units.to_meters(offset)),
+                }
+            }
+            (Some(_), Some(_)) => panic!("{dbg}.new | 'homography' & 'pinhole' - only one of them expected"),
+            (None, None) => panic!("{dbg}.new | 'homography' or 'pinhole' - not found or wrong configuration"),
+        };
+        log::trace!("{}.new | projection: {:?}", dbg, projection);
+        let origin: Option<ConfTree> = conf.get("origin");
+        let origin = match origin {
+            Some(origin) => match Self::values(&dbg, "origin", &origin.conf).as_slice() {
+                &[x, y] => Position::new(units.to_meters(x), units.to_meters(y)),
+                values => panic!("{dbg}.new | 'origin' must have 2 values, found {}", values.len()),
+            },
+            None => Position::new(0.0, 0.0),
+        };
+        log::trace!("{}.new | origin: {:?}", dbg, origin);
+        let axes: Option<ConfTree> = conf.get("axes");
+        let axes = match axes {
+            Some(axes) => {
+                let axes = axes.conf.as_sequence().expect(&format!("{dbg}.new | 'axes' must be a list"));
+                match axes.as_slice() {
+                    [x, y] => [x, y].map(|axis| {
+                        axis.as_str()
+                            .and_then(|axis| axis.parse::<Axis>().ok())
+                            .unwrap_or_else(|| panic!("{dbg}.new | 'axes' - wrong axis {:?}, x / -x / y / -y expected", axis))
+                    }),
+                    _ => panic!("{dbg}.new | 'axes' must have 2 values, found {}", axes.len()),
+                }
+            }
+            None => [Axis::X, Axis::Y],
+        };
+        if axes[0].is_x() == axes[1].is_x() {
+            panic!("{dbg}.new | 'axes' must take both x & y axes, found: {:?}", axes);
+        }
+        log::trace!("{}.new | axes: {:?}", dbg, axes);
+        Self {
+            name,
+            units,
+            projection,
+            origin,
+            axes,
+        }
+    }
+    ///
+    /// Returns the [WorldTransform] of the configuration
+    pub fn transform(&self) -> WorldTransform {
+        WorldTransform::new(self.projection.clone(), self.origin, self.axes)
+    }
+    ///
+    /// Returns the numbers of the `key` sequence
+    fn values(dbg: &Dbg, key: &str, conf: &serde_yaml::Value) -> Vec<f64> {
+        let values = conf.as_sequence().expect(&format!("{dbg}.new | 'world.{key}' must be a list"));
+        values.iter().enumerate().map(|(i, value)| {
+            value.as_f64().expect(&format!("{dbg}.new | 'world.{key}[{i}]' wrong number: {:?}", value))
+        }).collect()
+    }
+}
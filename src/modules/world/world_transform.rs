@@ -0,0 +1,125 @@
+use std::str::FromStr;
+use sal_core::error::Error;
+
+use crate::modules::{Homography, Intrinsics, Position};
+
+///
+/// Axis of the world plane, the reported axis is taken from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Axis {
+    X,
+    NegX,
+    Y,
+    NegY,
+}
+//
+//
+impl Axis {
+    ///
+    /// Returns the component of the `point` along the axis
+    pub fn of(&self, point: Position<f64>) -> f64 {
+        match self {
+            Axis::X => point.x,
+            Axis::NegX => -point.x,
+            Axis::Y => point.y,
+            Axis::NegY => -point.y,
+        }
+    }
+    ///
+    /// Returns true if the axis is the X axis of the plane, either direction
+    pub fn is_x(&self) -> bool {
+        matches!(self, Axis::X | Axis::NegX)
+    }
+}
+//
+//
+impl FromStr for Axis {
+    type Err = Error;
+    fn from_str(axis: &str) -> Result<Self, Self::Err> {
+        match axis.trim().to_lowercase().as_str() {
+            "x" | "+x" => Ok(Axis::X),
+            "-x" => Ok(Axis::NegX),
+            "y" | "+y" => Ok(Axis::Y),
+            "-y" => Ok(Axis::NegY),
+            _ => Err(Error::new("Axis", "from_str").err(format!("Unknown axis '{}', x / -x / y / -y expected", axis))),
+        }
+    }
+}
+///
+/// Projection of the pixel into the world plane
+#[derive(Debug, Clone, PartialEq)]
+pub enum Projection {
+    /// Planar homography from the image into the world plane, m
+    ///
+    /// Height-independent, valid only for the target moving in the plane of the reference points,
+    /// the rope length isn't applied, the `Pinhole` with the `rope_offset` is used for the hoisted target
+    Homography(Homography),
+    /// Pinhole camera looking perpendicular to the world plane
+    Pinhole {
+        intrinsics: Intrinsics,
+        /// Fixed distance from the camera to the world plane, m, if no `rope_offset`
+        distance: Option<f64>,
+        /// Distance from the camera to the world plane = rope length + `rope_offset`, m,
+        /// the pixel isn't projected while the rope length is unknown
+        rope_offset: Option<f64>,
+    },
+}
+//
+//
+impl Projection {
+    ///
+    /// Returns the `pixel` projected into the world plane, m
+    /// - `rope` - current length of the hoist rope, m, if known
+    /// - None if the `rope_offset` configured, but the rope length is unknown
+    pub fn project(&self, pixel: Position<f64>, rope: Option<f64>) -> Option<Position<f64>> {
+        match self {
+            Projection::Homography(homography) => homography.apply(pixel),
+            Projection::Pinhole { intrinsics, distance, rope_offset } => {
+                let distance = match rope_offset {
+                    Some(offset) => rope? + offset,
+                    None => (*distance)?,
+                };
+                match distance > 0.0 {
+                    true => Some(Position::new(
+                        (pixel.x - intrinsics.cx) / intrinsics.fx * distance,
+                        (pixel.y - intrinsics.cy) / intrinsics.fy * distance,
+                    )),
+                    false => None,
+                }
+            }
+        }
+    }
+}
+///
+/// Transforms the pixel position of the target into the world position
+///
+/// The pixel is projected into the world plane, moved to the `origin`
+/// and its components are taken along the `axes`
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorldTransform {
+    projection: Projection,
+    /// World point the positions are relative to, m
+    origin: Position<f64>,
+    /// World axes the reported X & Y are taken along
+    axes: [Axis; 2],
+}
+//
+//
+impl WorldTransform {
+    ///
+    /// Returns [WorldTransform]
+    /// - `origin` - world point the positions are relative to, m
+    /// - `axes` - world axes the reported X & Y are taken along
+    pub fn new(projection: Projection, origin: Position<f64>, axes: [Axis; 2]) -> Self {
+        Self { projection, origin, axes }
+    }
+    ///
+    /// Returns the world position of the `pixel` at the current length of the hoist `rope`, m
+    /// - `rope` - scales the `pinhole` projection with the `rope_offset`, ignored otherwise
+    /// - None if the pixel can't be projected into the world plane or the rope length required is unknown
+    pub fn world_at(&self, pixel: Position<f64>, rope: Option<f64>) -> Option<Position<f64>> {
+        let point = self.projection.project(pixel, rope)?;
+        let point = Position::new(point.x - self.origin.x, point.y - self.origin.y);
+        Some(Position::new(self.axes[0].of(point), self.axes[1].of(point)))
+    }
+}
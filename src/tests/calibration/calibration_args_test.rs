@@ -0,0 +1,69 @@
+#[cfg(test)]
+use std::time::Duration;
+
+use std::{
+    path::PathBuf,
+    sync::Once,
+};
+use sal_core::dbg::Dbg;
+use testing::stuff::max_test_duration::TestDuration;
+use debugging::session::debug_session::{
+    DebugSession,
+    LogLevel,
+    Backtrace
+};
+
+use crate::modules::CalibrationArgs;
+///
+///
+static INIT: Once = Once::new();
+///
+/// once called initialisation
+fn init_once() {
+    INIT.call_once(|| {
+        // implement your initialisation code to be called only once for current test file
+    })
+}
+///
+/// returns:
+///  - ...
+fn init_each() -> () {}
+///
+/// Testing [CalibrationArgs] parsed from the arguments of the `calibrate` command
+#[test]
+fn parse() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("CalibrationArgs-test-parse");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    // step, arguments, target: None - error
+    let test_data = [
+        (01, vec!["chessboard/"], Some(("chessboard/", (9, 6), "calibration.yaml"))),
+        (02, vec!["chessboard/", "--board", "7x5"], Some(("chessboard/", (7, 5), "calibration.yaml"))),
+        (03, vec!["--output", "camera1.yaml", "images", "--board", "11X8"], Some(("images", (11, 8), "camera1.yaml"))),
+        (04, vec![], None),
+        (05, vec!["chessboard/", "--board"], None),
+        (06, vec!["chessboard/", "--board", "9"], None),
+        (07, vec!["chessboard/", "--board", "1x6"], None),
+        (08, vec!["chessboard/", "--square", "25"], None),
+        (09, vec!["chessboard/", "other/"], None),
+        (10, vec!["chessboard/", "--output"], None),
+    ];
+    for (step, args, target) in test_data {
+        let args: Vec<String> = args.into_iter().map(String::from).collect();
+        let result = CalibrationArgs::parse(&args);
+        log::debug!("step {} | result: {:?}", step, result);
+        match target {
+            Some((images, board, output)) => {
+                let target = CalibrationArgs { images: PathBuf::from(images), board, output: PathBuf::from(output) };
+                let result = result.unwrap();
+                assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            }
+            None => assert!(result.is_err(), "step {} \nresult: {:?}\ntarget: {:?}", step, result, "Err"),
+        }
+    }
+    test_duration.exit();
+}
@@ -0,0 +1,145 @@
+#[cfg(test)]
+use std::time::Duration;
+
+use std::{
+    sync::Once,
+};
+use sal_core::dbg::Dbg;
+use testing::stuff::max_test_duration::TestDuration;
+use debugging::session::debug_session::{
+    DebugSession,
+    LogLevel,
+    Backtrace
+};
+
+use crate::modules::{Calibration, Position};
+///
+///
+static INIT: Once = Once::new();
+///
+/// once called initialisation
+fn init_once() {
+    INIT.call_once(|| {
+        // implement your initialisation code to be called only once for current test file
+    })
+}
+///
+/// returns:
+///  - ...
+fn init_each() -> () {}
+///
+/// Returns the calibration of the 1200 x 800 camera with the `distortion`
+fn calibration(distortion: Vec<f64>) -> Calibration {
+    Calibration {
+        image_size: (1200, 800),
+        camera_matrix: [[1400.0, 0.0, 610.0], [0.0, 1380.0, 395.0], [0.0, 0.0, 1.0]],
+        distortion,
+        rms: 0.3,
+        images: 20,
+    }
+}
+///
+/// Testing [Calibration::undistort] restores the ideal point distorted by [Calibration::distort]
+#[test]
+fn undistort() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("Calibration-test-undistort");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let points = [
+        Position::new(610.0, 395.0),
+        Position::new(0.0, 0.0),
+        Position::new(1200.0, 800.0),
+        Position::new(100.0, 700.0),
+        Position::new(900.0, 150.0),
+    ];
+    // step, distortion coefficients
+    let test_data = [
+        (01, vec![0.0, 0.0, 0.0, 0.0]),
+        (02, vec![-0.12, 0.05, 0.0, 0.0, 0.0]),
+        (03, vec![0.08, -0.02, 0.001, -0.0005]),
+        (04, vec![-0.25, 0.1, 0.0005, 0.0008, -0.02]),
+        (05, vec![-0.2, 0.05, 0.0, 0.0, 0.0, 0.01, 0.0, 0.0]),
+    ];
+    for (step, distortion) in test_data {
+        let calibration = calibration(distortion);
+        for point in points {
+            let distorted = calibration.distort(point);
+            let result = calibration.undistort(distorted);
+            log::debug!("step {} | point: {:?}, distorted: {:?}, result: {:?}", step, point, distorted, result);
+            let error = ((result.x - point.x).powi(2) + (result.y - point.y).powi(2)).sqrt();
+            assert!(error < 0.01, "step {} \nresult: {:?}\ntarget: {:?}", step, result, point);
+        }
+    }
+    // Barrel distortion moves the corner to the center
+    let calibration = calibration(vec![-0.12, 0.05, 0.0, 0.0, 0.0]);
+    let result = calibration.distort(Position::new(0.0, 0.0));
+    assert!(result.x > 0.0 && result.y > 0.0, "\nresult: {:?}\ntarget: {:?}", result, "x > 0, y > 0");
+    test_duration.exit();
+}
+///
+/// Testing [Calibration] saved into the YAML file is loaded back, wrong distortion is rejected
+#[test]
+fn save_load() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("Calibration-test-save_load");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let dir = std::env::temp_dir();
+    // step, distortion coefficients, loaded
+    let test_data = [
+        (01, vec![-0.12, 0.05, 0.001, -0.002, 0.01], true),
+        (02, vec![-0.12, 0.05, 0.001, -0.002], true),
+        (03, vec![-0.12, 0.05, 0.001, -0.002, 0.01, 0.0, 0.0, 0.0], true),
+        (04, vec![-0.12, 0.05], false),
+        (05, vec![-0.12, 0.05, 0.001, -0.002, 0.01, 0.0], false),
+    ];
+    for (step, distortion, target) in test_data {
+        let path = dir.join(format!("position-tracking-calibration-test-{}-{}.yaml", std::process::id(), step));
+        let calibration = calibration(distortion);
+        calibration.save(&path).unwrap();
+        let result = Calibration::load(&path);
+        let _ = std::fs::remove_file(&path);
+        log::debug!("step {} | result: {:?}", step, result);
+        match target {
+            true => {
+                let result = result.unwrap();
+                assert!(result == calibration, "step {} \nresult: {:?}\ntarget: {:?}", step, result, calibration);
+            }
+            false => assert!(result.is_err(), "step {} \nresult: {:?}\ntarget: {:?}", step, result, "Err"),
+        }
+    }
+    test_duration.exit();
+}
+///
+/// Testing [Calibration::check_size] accepts only the frames of the calibrated size
+#[test]
+fn check_size() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("Calibration-test-check_size");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let calibration = calibration(vec![-0.12, 0.05, 0.0, 0.0]);
+    // step, width, height, accepted
+    let test_data = [
+        (01, 1200, 800, true),
+        (02, 800, 1200, false),
+        (03, 1920, 1080, false),
+        (04, 600, 400, false),
+    ];
+    for (step, width, height, target) in test_data {
+        let result = calibration.check_size(width, height);
+        log::debug!("step {} | {} x {} result: {:?}", step, width, height, result);
+        assert!(result.is_ok() == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+    }
+    test_duration.exit();
+}
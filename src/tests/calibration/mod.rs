@@ -0,0 +1,2 @@
+mod calibration_args_test;
+mod calibration_test;
@@ -0,0 +1,96 @@
+#[cfg(test)]
+use std::time::Duration;
+
+use std::{
+    sync::Once, 
+};
+use opencv::core::{DMatch, Point2f};
+use sal_core::dbg::Dbg;
+use testing::stuff::max_test_duration::TestDuration;
+use debugging::session::debug_session::{
+    DebugSession, 
+    LogLevel, 
+    Backtrace
+};
+
+use crate::modules::BfMatch;
+///
+///
+static INIT: Once = Once::new();
+///
+/// once called initialisation
+fn init_once() {
+    INIT.call_once(|| {
+        // implement your initialisation code to be called only once for current test file
+    })
+}
+///
+/// returns:
+///  - ...
+fn init_each() -> () {}
+///
+/// Returns [DMatch] of the query keypoint `query` to the train keypoint `train`
+fn dmatch(query: i32, train: i32) -> DMatch {
+    DMatch { query_idx: query, train_idx: train, img_idx: 0, distance: 1.0 }
+}
+///
+/// Testing [BfMatch].center
+#[test]
+fn center() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("BfMatch-test-center");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let test_data: [(i32, f32, Vec<(f32, f32)>, Option<(f32, f32)>); 5] = [
+        (01, 1.7, vec![], None),
+        (02, 1.7, vec![(10.0, 20.0)], Some((10.0, 20.0))),
+        (03, 1.7, vec![(9.0, 9.0), (11.0, 9.0), (9.0, 11.0), (11.0, 11.0)], Some((10.0, 10.0))),
+        // Two far outliers on the same side must not pull the center
+        (04, 1.7, vec![(99.0, 99.0), (101.0, 99.0), (99.0, 101.0), (101.0, 101.0), (100.0, 100.0), (400.0, 10.0), (420.0, 12.0)], Some((100.0, 100.0))),
+        // Outliers are the half minus one of the points
+        (05, 1.5, vec![(50.0, 50.0), (51.0, 50.0), (50.0, 51.0), (49.0, 50.0), (50.0, 49.0), (0.0, 0.0), (300.0, 0.0), (0.0, 300.0), (300.0, 300.0)], Some((50.0, 50.0))),
+    ];
+    for (step, deviation_ratio, points, target) in test_data {
+        let points: Vec<Point2f> = points.into_iter().map(|(x, y)| Point2f::new(x, y)).collect();
+        let result = BfMatch::center(&dbg, deviation_ratio, &points).map(|p| (p.x, p.y));
+        log::debug!("step {step}   points: {:?}  result: {:?}", points, result);
+        match (result, target) {
+            (Some((x, y)), Some((target_x, target_y))) => {
+                assert!((x - target_x).abs() < 0.1 && (y - target_y).abs() < 0.1, "step {step} \nresult: {:?}\ntarget: {:?}", result, target);
+            }
+            _ => assert!(result == target, "step {step} \nresult: {:?}\ntarget: {:?}", result, target),
+        }
+    }
+    test_duration.exit();
+}
+///
+/// Testing [BfMatch].matched_points
+#[test]
+fn matched_points() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("BfMatch-test-matched_points");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let keypoints = [Point2f::new(0.0, 0.0), Point2f::new(1.0, 1.0), Point2f::new(2.0, 2.0), Point2f::new(3.0, 3.0)];
+    let test_data: [(i32, Vec<DMatch>, Vec<(f32, f32)>); 4] = [
+        (01, vec![], vec![]),
+        // `train_idx` addresses the frame keypoints, `query_idx` is the template keypoint and doesn't matter
+        (02, vec![dmatch(7, 1), dmatch(0, 3)], vec![(1.0, 1.0), (3.0, 3.0)]),
+        // Frame keypoint matched by several template keypoints is used once
+        (03, vec![dmatch(0, 2), dmatch(1, 2), dmatch(2, 0)], vec![(2.0, 2.0), (0.0, 0.0)]),
+        // Wrong indexes are skipped
+        (04, vec![dmatch(0, 4), dmatch(1, -1), dmatch(2, 1)], vec![(1.0, 1.0)]),
+    ];
+    for (step, matches, target) in test_data {
+        let result: Vec<(f32, f32)> = BfMatch::matched_points(&dbg, &matches, &keypoints).into_iter().map(|p| (p.x, p.y)).collect();
+        log::debug!("step {step}   matches: {:?}  result: {:?}", matches, result);
+        assert!(result == target, "step {step} \nresult: {:?}\ntarget: {:?}", result, target);
+    }
+    test_duration.exit();
+}
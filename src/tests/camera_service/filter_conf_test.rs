@@ -0,0 +1,139 @@
+#[cfg(test)]
+use std::time::Duration;
+
+use std::{
+    sync::Once,
+};
+use sal_core::dbg::Dbg;
+use sal_sync::services::conf::ConfTree;
+use testing::stuff::max_test_duration::TestDuration;
+use debugging::session::debug_session::{
+    DebugSession,
+    LogLevel,
+    Backtrace
+};
+
+use crate::modules::{Filter, FilterChain, FilterConf, FilterKind, MaxSpeed, Position};
+///
+///
+static INIT: Once = Once::new();
+///
+/// once called initialisation
+fn init_once() {
+    INIT.call_once(|| {
+        // implement your initialisation code to be called only once for current test file
+    })
+}
+///
+/// returns:
+///  - ...
+fn init_each() -> () {}
+///
+/// Returns the `filters` list parsed from the `yaml`
+fn filters(dbg: &Dbg, yaml: &str) -> Vec<FilterConf> {
+    let conf = serde_yaml::from_str(yaml).unwrap();
+    FilterConf::list(dbg, ConfTree::new_root(conf))
+}
+///
+/// Testing [FilterConf].list parses the filters in the configured order
+#[test]
+fn list() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("FilterConf-test-list");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let test_data = [
+        (01, r#"
+            - median:
+                window: 5
+            - outlier:
+                max-speed: 40
+            - smooth:
+                factor: 2
+                axis: x
+        "#, vec![
+            FilterConf { axis: None, kind: FilterKind::Median { window: 5 } },
+            FilterConf { axis: None, kind: FilterKind::Outlier { max_speed: MaxSpeed::PerFrame(40.0), relock: 3 } },
+            FilterConf { axis: Some(0), kind: FilterKind::Smooth { factor: 2.0, sample_time: None } },
+        ]),
+        (02, r#"
+            - average:
+                window: 3
+                axis: y
+            - low-pass:
+                cutoff: 2.0
+                sample-time: 0.04
+        "#, vec![
+            FilterConf { axis: Some(1), kind: FilterKind::Average { window: 3 } },
+            FilterConf { axis: None, kind: FilterKind::LowPass { cutoff: 2.0, sample_time: 0.04 } },
+        ]),
+    ];
+    for (step, yaml, target) in test_data {
+        let result = filters(&dbg, yaml);
+        log::debug!("step {step}   result: {:?}", result);
+        assert!(result == target, "step {step} \nresult: {:?}\ntarget: {:?}", result, target);
+    }
+    test_duration.exit();
+}
+///
+/// Testing the chain median → outlier → smooth built from the config removes the spike
+#[test]
+fn chain() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("FilterConf-test-chain");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let confs = filters(&dbg, r#"
+        - median:
+            window: 3
+        - outlier:
+            max-speed: 40
+        - smooth:
+            factor: 1
+    "#);
+    let mut filter: FilterChain<Position<f64>> = FilterChain::new(confs.iter().map(FilterConf::filter).collect());
+    let test_data = [
+        (01, Position::new(10.0, 100.0), Position::new(10.0, 100.0)),
+        (02, Position::new(12.0, 102.0), Position::new(11.0, 101.0)),
+        // single spike is removed by the median
+        (03, Position::new(200.0, 101.0), Position::new(12.0, 101.0)),
+        (04, Position::new(14.0, 103.0), Position::new(14.0, 102.0)),
+        (05, Position::new(16.0, 104.0), Position::new(16.0, 103.0)),
+    ];
+    for (step, val, target) in test_data {
+        let result = filter.add(val);
+        log::debug!("step {step}   val: {:?}  result: {:?}", val, result);
+        assert!(result == Some(target), "step {step} \nresult: {:?}\ntarget: {:?}", result, target);
+    }
+    test_duration.exit();
+}
+///
+/// Testing [FilterConf].list rejects the wrong parameters
+#[test]
+fn wrong() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("FilterConf-test-wrong");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let test_data = [
+        (01, "[median: {window: 0}]"),
+        (02, "[average: {}]"),
+        (03, "[unknown: {window: 3}]"),
+        (04, "[smooth: {factor: 2, sample-time: 0.0}]"),
+        (05, "[low-pass: {cutoff: 2.0, sample-time: -0.04}]"),
+    ];
+    for (step, yaml) in test_data {
+        let result = std::panic::catch_unwind(|| filters(&dbg, yaml));
+        assert!(result.is_err(), "step {step} \nresult: {:?}\ntarget: {:?}", result, "panic");
+    }
+    test_duration.exit();
+}
@@ -0,0 +1,152 @@
+#[cfg(test)]
+use std::time::Duration;
+
+use std::{
+    sync::Once,
+};
+use sal_core::dbg::Dbg;
+use sal_sync::services::conf::ConfTree;
+use testing::stuff::max_test_duration::TestDuration;
+use debugging::session::debug_session::{
+    DebugSession,
+    LogLevel,
+    Backtrace
+};
+
+use crate::modules::{ImageConf, ImageStage, Matcher};
+///
+///
+static INIT: Once = Once::new();
+///
+/// once called initialisation
+fn init_once() {
+    INIT.call_once(|| {
+        // implement your initialisation code to be called only once for current test file
+    })
+}
+///
+/// returns:
+///  - ...
+fn init_each() -> () {}
+///
+/// Returns [ImageConf] parsed from the `image` section `yaml`
+fn image(dbg: &Dbg, yaml: &str) -> ImageConf {
+    let conf = serde_yaml::from_str(yaml).unwrap();
+    ImageConf::new(dbg, ConfTree::new_root(conf))
+}
+const GAMMA: &str = "gamma: {factor: 95.0}\n";
+const BRIGHTNESS_CONTRAST: &str = "brightness-contrast: {hist-clip-left: 1.0, hist-clip-right: 1.0}\n";
+const GAUSIAN: &str = "gausian: {blur-size: {width: 3, height: 3}, sigma-x: 0.0, sigma-y: 0.0}\n";
+const SOBEL: &str = "sobel: {kernel-size: 3, scale: 1.0, delta: 0.0}\n";
+const OVERLAY: &str = "overlay: {src1-weight: 0.5, src2-weight: 0.5, gamma: 0.0}\n";
+const CROPPING: &str = "cropping: {x: 10, width: 50, y: 20, height: 40}\n";
+///
+/// Testing [ImageConf] stages are applied in the order of the sections
+#[test]
+fn stages() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("ImageConf-test-stages");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let test_data = [
+        (01, "{}".to_owned(), vec![]),
+        (02, [GAMMA, BRIGHTNESS_CONTRAST].concat(), vec![ImageStage::Gamma, ImageStage::BrightnessContrast]),
+        (03, [BRIGHTNESS_CONTRAST, GAMMA].concat(), vec![ImageStage::BrightnessContrast, ImageStage::Gamma]),
+        (04, [CROPPING, GAUSIAN, SOBEL].concat(), vec![ImageStage::Cropping, ImageStage::Gausian, ImageStage::Sobel]),
+        (05, [GAUSIAN, OVERLAY, CROPPING].concat(), vec![ImageStage::Gausian, ImageStage::Overlay, ImageStage::Cropping]),
+    ];
+    for (step, yaml, target) in test_data {
+        let result = image(&dbg, &yaml).stages;
+        log::debug!("step {step}   result: {:?}", result);
+        assert!(result == target, "step {step} \nresult: {:?}\ntarget: {:?}", result, target);
+    }
+    test_duration.exit();
+}
+///
+/// Testing [ImageConf].with_stages replaces the order of the sections
+#[test]
+fn with_stages() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("ImageConf-test-with_stages");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let conf = image(&dbg, &[OVERLAY, CROPPING, GAMMA, SOBEL].concat());
+    let test_data = [
+        (01, vec![]),
+        (02, vec![ImageStage::Gamma]),
+        (03, vec![ImageStage::GrayScale, ImageStage::Sobel, ImageStage::Overlay, ImageStage::Cropping]),
+        (04, vec![ImageStage::Overlay, ImageStage::Cropping, ImageStage::Gamma]),
+    ];
+    for (step, target) in test_data {
+        let result = conf.clone().with_stages(&dbg, target.clone()).stages;
+        log::debug!("step {step}   result: {:?}", result);
+        assert!(result == target, "step {step} \nresult: {:?}\ntarget: {:?}", result, target);
+    }
+    test_duration.exit();
+}
+///
+/// Testing [ImageConf] rejects the overlay after cropping & the stages not configured
+#[test]
+fn wrong() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("ImageConf-test-wrong");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    // step, image section, stages replacing the sections order
+    let test_data = [
+        // overlay needs the source frame size
+        (01, [CROPPING, OVERLAY].concat(), None),
+        (02, [CROPPING, GAMMA, OVERLAY].concat(), None),
+        (03, [GAMMA, OVERLAY, CROPPING].concat(), Some(vec![ImageStage::Cropping, ImageStage::Overlay])),
+        // not configured
+        (04, [GAMMA, OVERLAY, CROPPING].concat(), Some(vec![ImageStage::Sobel])),
+        // unknown section
+        (05, "blur: {}".to_owned(), None),
+    ];
+    for (step, yaml, stages) in test_data {
+        let result = std::panic::catch_unwind(|| {
+            let conf = image(&dbg, &yaml);
+            match stages {
+                Some(stages) => conf.with_stages(&dbg, stages),
+                None => conf,
+            }
+        });
+        assert!(result.is_err(), "step {step} \nresult: {:?}\ntarget: {:?}", result, "panic");
+    }
+    test_duration.exit();
+}
+///
+/// Testing [ImageConf::check_matcher] rejects the single channel stages of the `image` sections with the `TemplateMatch`
+#[test]
+fn matcher() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("ImageConf-test-matcher");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    // step, image section, matcher, target accepted
+    let test_data = [
+        (01, [GAMMA, GAUSIAN].concat(), Matcher::TemplateMatch, true),
+        (02, [GAMMA, SOBEL].concat(), Matcher::BfMatch, true),
+        // the frame is single channel after sobel, the template is colour
+        (03, [GAMMA, SOBEL].concat(), Matcher::TemplateMatch, false),
+        (04, [SOBEL, OVERLAY].concat(), Matcher::TemplateMatch, false),
+    ];
+    for (step, yaml, matcher, target) in test_data {
+        let conf = image(&dbg, &yaml);
+        let result = std::panic::catch_unwind(|| ImageConf::check_matcher(&dbg, &conf.stages, matcher)).is_ok();
+        assert!(result == target, "step {step} \nresult: {:?}\ntarget: {:?}", result, target);
+    }
+    test_duration.exit();
+}
@@ -0,0 +1,8 @@
+mod bf_match_test;
+mod filter_conf_test;
+mod image_conf_test;
+mod pipeline_conf_test;
+mod preprocessing_test;
+mod reconnect_test;
+mod template_match_conf_test;
+mod tracking_test;
\ No newline at end of file
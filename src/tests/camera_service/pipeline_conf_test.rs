@@ -0,0 +1,99 @@
+#[cfg(test)]
+use std::time::Duration;
+
+use std::{
+    sync::Once,
+};
+use sal_core::dbg::Dbg;
+use sal_sync::services::conf::ConfTree;
+use testing::stuff::max_test_duration::TestDuration;
+use debugging::session::debug_session::{
+    DebugSession,
+    LogLevel,
+    Backtrace
+};
+
+use crate::modules::{ImageStage, Matcher, PipelineConf};
+///
+///
+static INIT: Once = Once::new();
+///
+/// once called initialisation
+fn init_once() {
+    INIT.call_once(|| {
+        // implement your initialisation code to be called only once for current test file
+    })
+}
+///
+/// returns:
+///  - ...
+fn init_each() -> () {}
+///
+/// Returns the stages of the `pipeline` list `yaml`
+fn pipeline(dbg: &Dbg, yaml: &str, matcher: Matcher) -> Vec<ImageStage> {
+    let conf = serde_yaml::from_str(yaml).unwrap();
+    PipelineConf::new(dbg, ConfTree::new_root(conf), matcher).stages
+}
+///
+/// Testing [PipelineConf] keeps the order of the stages, `initial` & `matcher` are optional
+#[test]
+fn stages() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("PipelineConf-test-stages");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    // step, pipeline, matcher, target stages
+    let test_data = [
+        (01, "[]", Matcher::BfMatch, vec![]),
+        (02, "[initial, matcher]", Matcher::BfMatch, vec![]),
+        (03, "[initial, gray-scale, gamma, brightness-contrast, blur, matcher]", Matcher::BfMatch, vec![
+            ImageStage::GrayScale, ImageStage::Gamma, ImageStage::BrightnessContrast, ImageStage::Gausian,
+        ]),
+        (04, "[blur, gamma]", Matcher::BfMatch, vec![ImageStage::Gausian, ImageStage::Gamma]),
+        (05, "[overlay, crop, gray-scale, sobel]", Matcher::BfMatch, vec![
+            ImageStage::Overlay, ImageStage::Cropping, ImageStage::GrayScale, ImageStage::Sobel,
+        ]),
+        (06, "[initial, gamma, blur, overlay, crop, matcher]", Matcher::TemplateMatch, vec![
+            ImageStage::Gamma, ImageStage::Gausian, ImageStage::Overlay, ImageStage::Cropping,
+        ]),
+    ];
+    for (step, yaml, matcher, target) in test_data {
+        let result = pipeline(&dbg, yaml, matcher);
+        log::debug!("step {step}   result: {:?}", result);
+        assert!(result == target, "step {step} \nresult: {:?}\ntarget: {:?}", result, target);
+    }
+    test_duration.exit();
+}
+///
+/// Testing [PipelineConf] rejects the wrong order of the stages
+#[test]
+fn wrong() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("PipelineConf-test-wrong");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    // step, pipeline, matcher
+    let test_data = [
+        // the frame is single channel after sobel
+        (01, "[sobel, gray-scale]", Matcher::BfMatch),
+        (02, "[sobel, blur, gray-scale]", Matcher::BfMatch),
+        // the template is colour
+        (03, "[gray-scale]", Matcher::TemplateMatch),
+        (04, "[blur, sobel, matcher]", Matcher::TemplateMatch),
+        (05, "[gamma, initial]", Matcher::BfMatch),
+        (06, "[matcher, gamma]", Matcher::BfMatch),
+        (07, "[gamma, blur, gamma]", Matcher::BfMatch),
+        (08, "[unknown]", Matcher::BfMatch),
+    ];
+    for (step, yaml, matcher) in test_data {
+        let result = std::panic::catch_unwind(|| pipeline(&dbg, yaml, matcher));
+        assert!(result.is_err(), "step {step} \nresult: {:?}\ntarget: {:?}", result, "panic");
+    }
+    test_duration.exit();
+}
@@ -0,0 +1,123 @@
+#[cfg(test)]
+use std::time::Duration;
+
+use std::{
+    sync::Once,
+};
+use frdm_tools::{ContextRead, Eval, Image, ResultCtx};
+use opencv::core::MatTraitConst;
+use sal_core::dbg::Dbg;
+use sal_sync::services::conf::ConfTree;
+use testing::stuff::max_test_duration::TestDuration;
+use debugging::session::debug_session::{
+    DebugSession,
+    LogLevel,
+    Backtrace
+};
+
+use crate::modules::{Diagnostics, ImageConf, ImageStage, Preprocessing};
+///
+///
+static INIT: Once = Once::new();
+///
+/// once called initialisation
+fn init_once() {
+    INIT.call_once(|| {
+        // implement your initialisation code to be called only once for current test file
+    })
+}
+///
+/// returns:
+///  - ...
+fn init_each() -> () {}
+///
+/// Returns [ImageConf] parsed from the `image` section `yaml`
+fn image(dbg: &Dbg, yaml: &str) -> ImageConf {
+    let conf = serde_yaml::from_str(yaml).unwrap();
+    ImageConf::new(dbg, ConfTree::new_root(conf))
+}
+///
+/// Returns the width, height & number of channels of the `frame` preprocessed by the `conf`
+fn preprocess(conf: &ImageConf, frame: &Image) -> Option<(i32, i32, i32)> {
+    let preprocessing = Preprocessing::new(conf, &Diagnostics::new());
+    match preprocessing.eval(frame.clone()) {
+        Ok(ctx) => {
+            let result: &ResultCtx = ctx.read();
+            let mat = &result.frame.mat;
+            Some((mat.cols(), mat.rows(), mat.channels()))
+        }
+        Err(err) => {
+            log::debug!("preprocess | error: {}", err);
+            None
+        }
+    }
+}
+const GAUSIAN: &str = "gausian: {blur-size: {width: 3, height: 3}, sigma-x: 0.0, sigma-y: 0.0}\n";
+const SOBEL: &str = "sobel: {kernel-size: 3, scale: 1.0, delta: 0.0}\n";
+const OVERLAY: &str = "overlay: {src1-weight: 0.5, src2-weight: 0.5, gamma: 0.0}\n";
+const CROPPING: &str = "cropping: {x: 10, width: 50, y: 20, height: 40}\n";
+///
+/// Testing [Preprocessing] applies the configured stages to the frame
+#[test]
+fn stages() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("Preprocessing-test-stages");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+    test_duration.run().unwrap();
+    let frame = Image::load("template_128.png").unwrap();
+    let (cols, rows, channels) = (frame.mat.cols(), frame.mat.rows(), frame.mat.channels());
+    // step, image section, target width, height & channels of the preprocessed frame
+    let test_data = [
+        (01, "{}".to_owned(), Some((cols, rows, channels))),
+        (02, CROPPING.to_owned(), Some((50, 40, channels))),
+        // cropping is clamped to the frame
+        (03, format!("cropping: {{x: 10, width: {cols}, y: 20, height: {rows}}}"), Some((cols - 10, rows - 20, channels))),
+        (04, format!("cropping: {{x: {}, width: 50, y: 20, height: 40}}", cols + 10), None),
+        (05, GAUSIAN.to_owned(), Some((cols, rows, channels))),
+        (06, SOBEL.to_owned(), Some((cols, rows, 1))),
+        (07, [SOBEL, CROPPING].concat(), Some((50, 40, 1))),
+        (08, [GAUSIAN, OVERLAY, CROPPING].concat(), Some((50, 40, channels))),
+    ];
+    for (step, yaml, target) in test_data {
+        let conf = image(&dbg, &yaml);
+        let result = preprocess(&conf, &frame);
+        log::debug!("step {step}   stages: {:?}  result: {:?}", conf.stages, result);
+        assert!(result == target, "step {step} \nresult: {:?}\ntarget: {:?}", result, target);
+    }
+    test_duration.exit();
+}
+///
+/// Testing [Preprocessing] applies the stages in the configured order
+#[test]
+fn order() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("Preprocessing-test-order");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+    test_duration.run().unwrap();
+    let frame = Image::load("template_128.png").unwrap();
+    let (cols, rows) = (frame.mat.cols(), frame.mat.rows());
+    let conf = image(&dbg, &[GAUSIAN, OVERLAY, CROPPING].concat());
+    // step, stages, target width, height & channels of the preprocessed frame
+    let test_data = [
+        (01, vec![ImageStage::GrayScale], Some((cols, rows, 1))),
+        (02, vec![ImageStage::GrayScale, ImageStage::Cropping], Some((50, 40, 1))),
+        (03, vec![ImageStage::Cropping, ImageStage::GrayScale], Some((50, 40, 1))),
+        // overlay keeps the channels of the source frame
+        (04, vec![ImageStage::GrayScale, ImageStage::Gausian, ImageStage::Overlay], Some((cols, rows, frame.mat.channels()))),
+        // overlay after cropping can't be configured, the source frame size differs from the processed one
+        (05, vec![ImageStage::Cropping, ImageStage::Overlay], None),
+    ];
+    for (step, stages, target) in test_data {
+        let conf = ImageConf { stages, ..conf.clone() };
+        let result = preprocess(&conf, &frame);
+        log::debug!("step {step}   stages: {:?}  result: {:?}", conf.stages, result);
+        assert!(result == target, "step {step} \nresult: {:?}\ntarget: {:?}", result, target);
+    }
+    test_duration.exit();
+}
@@ -0,0 +1,61 @@
+#[cfg(test)]
+use std::time::Duration;
+
+use std::{
+    sync::Once, 
+};
+use sal_core::dbg::Dbg;
+use testing::stuff::max_test_duration::TestDuration;
+use debugging::session::debug_session::{
+    DebugSession, 
+    LogLevel, 
+    Backtrace
+};
+
+use crate::modules::{CameraState, CameraStatus};
+///
+///
+static INIT: Once = Once::new();
+///
+/// once called initialisation
+fn init_once() {
+    INIT.call_once(|| {
+        // implement your initialisation code to be called only once for current test file
+    })
+}
+///
+/// returns:
+///  - ...
+fn init_each() -> () {}
+///
+/// Testing [CameraStatus] is shared between the clones
+#[test]
+fn status() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("Reconnect-test-status");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let status = CameraStatus::new();
+    let consumer = status.clone();
+    assert!(consumer.state() == CameraState::Connecting, "\nresult: {:?}\ntarget: {:?}", consumer.state(), CameraState::Connecting);
+    // step, state set, reconnect, target reconnects
+    let test_data = [
+        (01, CameraState::Streaming, false, 0),
+        (02, CameraState::Down, true, 1),
+        (03, CameraState::Connecting, false, 1),
+        (04, CameraState::Down, true, 2),
+        (05, CameraState::Streaming, false, 2),
+    ];
+    for (step, state, reconnect, target) in test_data {
+        status.set(state);
+        if reconnect {
+            status.reconnect();
+        }
+        assert!(consumer.state() == state, "step {} \nresult: {:?}\ntarget: {:?}", step, consumer.state(), state);
+        assert!(consumer.reconnects() == target, "step {} \nresult: {:?}\ntarget: {:?}", step, consumer.reconnects(), target);
+    }
+    test_duration.exit();
+}
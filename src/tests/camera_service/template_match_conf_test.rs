@@ -0,0 +1,62 @@
+#[cfg(test)]
+use std::time::Duration;
+
+use std::{
+    sync::Once,
+};
+use sal_core::dbg::Dbg;
+use sal_sync::services::conf::ConfTree;
+use testing::stuff::max_test_duration::TestDuration;
+use debugging::session::debug_session::{
+    DebugSession,
+    LogLevel,
+    Backtrace
+};
+
+use crate::modules::{FilterConf, FilterKind, TemplateMatchConf};
+///
+///
+static INIT: Once = Once::new();
+///
+/// once called initialisation
+fn init_once() {
+    INIT.call_once(|| {
+        // implement your initialisation code to be called only once for current test file
+    })
+}
+///
+/// returns:
+///  - ...
+fn init_each() -> () {}
+///
+/// Returns [TemplateMatchConf] parsed from the `yaml`
+fn template_match(dbg: &Dbg, yaml: &str) -> TemplateMatchConf {
+    let conf = serde_yaml::from_str(yaml).unwrap();
+    TemplateMatchConf::new(dbg, ConfTree::new_root(conf))
+}
+///
+/// Testing the legacy `smooth-moving` is validated as the `smooth` filter
+#[test]
+fn smooth_moving() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("TemplateMatchConf-test-smooth_moving");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let conf = "{template: template.png, method: TM_CCOEFF_NORMED, match-ratio: 0.8, deviation-ratio: 1.7";
+    let test_data = [
+        (01, format!("{conf}, smooth-moving: 4}}"), Some(vec![FilterConf { axis: None, kind: FilterKind::Smooth { factor: 4.0, sample_time: None } }])),
+        (02, format!("{conf}, smooth-moving: 1}}"), Some(vec![FilterConf { axis: None, kind: FilterKind::Smooth { factor: 1.0, sample_time: None } }])),
+        (03, format!("{conf}}}"), Some(vec![])),
+        (04, format!("{conf}, smooth-moving: 0}}"), None),
+        (05, format!("{conf}, smooth-moving: 0.5}}"), None),
+    ];
+    for (step, yaml, target) in test_data {
+        let result = std::panic::catch_unwind(|| template_match(&dbg, &yaml).filters).ok();
+        log::debug!("step {step}   yaml: {:?}  result: {:?}", yaml, result);
+        assert!(result == target, "step {step} \nresult: {:?}\ntarget: {:?}", result, target);
+    }
+    test_duration.exit();
+}
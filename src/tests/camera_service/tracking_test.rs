@@ -0,0 +1,130 @@
+#[cfg(test)]
+use std::time::Duration;
+
+use std::{
+    sync::Once, 
+};
+use opencv::core::Rect;
+use sal_core::dbg::Dbg;
+use testing::stuff::max_test_duration::TestDuration;
+use debugging::session::debug_session::{
+    DebugSession, 
+    LogLevel, 
+    Backtrace
+};
+
+use crate::modules::{Detection, Tracking, TrackingConf};
+///
+///
+static INIT: Once = Once::new();
+///
+/// once called initialisation
+fn init_once() {
+    INIT.call_once(|| {
+        // implement your initialisation code to be called only once for current test file
+    })
+}
+///
+/// returns:
+///  - ...
+fn init_each() -> () {}
+///
+/// Returns [Detection] at the `position` with the `score`
+fn detection(position: Option<(f64, f64)>, score: f64) -> Option<Detection> {
+    position.map(|(x, y)| Detection { x, y, scale: 1.0, angle: 0.0, score })
+}
+///
+/// Testing [Tracking].update & [Tracking].roi
+#[test]
+fn roi() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("Tracking-test-roi");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let conf = TrackingConf { width: 100.0, height: 50.0, expand: 2.0, max_misses: 2, min_score: None };
+    let mut tracking = Tracking::new(conf);
+    let test_data = [
+        (01, None, None),
+        (02, Some((500.0, 300.0)), Some(Rect::new(450, 275, 100, 50))),
+        // Moving by (10, -5) per frame
+        (03, Some((510.0, 295.0)), Some(Rect::new(470, 265, 100, 50))),
+        // Missed, ROI expanded around the predicted position
+        (04, None, Some(Rect::new(430, 235, 200, 100))),
+        (05, None, Some(Rect::new(340, 180, 400, 200))),
+        // Lost after `max-misses`, full frame search
+        (06, None, None),
+        (07, None, None),
+        // Found again, no velocity known yet
+        (08, Some((100.0, 100.0)), Some(Rect::new(50, 75, 100, 50))),
+        // Found after a miss, velocity is unknown
+        (09, None, Some(Rect::new(0, 50, 200, 100))),
+        (10, Some((120.0, 110.0)), Some(Rect::new(70, 85, 100, 50))),
+    ];
+    for (step, position, target) in test_data {
+        tracking.update(detection(position, 1.0).as_ref());
+        let result = tracking.roi();
+        log::debug!("step {step}   position: {:?}  result: {:?}", position, result);
+        assert!(result == target, "step {step} \nresult: {:?}\ntarget: {:?}", result, target);
+    }
+    test_duration.exit();
+}
+///
+/// Testing [Tracking].update with the `min-score`
+#[test]
+fn min_score() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("Tracking-test-min_score");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let conf = TrackingConf { width: 100.0, height: 50.0, expand: 2.0, max_misses: 1, min_score: Some(10.0) };
+    let mut tracking = Tracking::new(conf);
+    let test_data = [
+        // Not confident, full frame search
+        (01, Some((500.0, 300.0)), 3.0, None),
+        (02, Some((500.0, 300.0)), 10.0, Some(Rect::new(450, 275, 100, 50))),
+        // Not confident look-alike is a miss
+        (03, Some((100.0, 100.0)), 9.0, Some(Rect::new(400, 250, 200, 100))),
+        (04, Some((500.0, 300.0)), 25.0, Some(Rect::new(450, 275, 100, 50))),
+        // Lost after `max-misses` of not confident detections
+        (05, Some((100.0, 100.0)), 1.0, Some(Rect::new(400, 250, 200, 100))),
+        (06, Some((100.0, 100.0)), 1.0, None),
+    ];
+    for (step, position, score, target) in test_data {
+        tracking.update(detection(position, score).as_ref());
+        let result = tracking.roi();
+        log::debug!("step {step}   position: {:?}  score: {score}  result: {:?}", position, result);
+        assert!(result == target, "step {step} \nresult: {:?}\ntarget: {:?}", result, target);
+    }
+    test_duration.exit();
+}
+///
+/// Testing [Tracking].clamp
+#[test]
+fn clamp() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("Tracking-test-clamp");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let test_data = [
+        (01, Rect::new(10, 10, 100, 100), Some(Rect::new(10, 10, 100, 100))),
+        (02, Rect::new(-20, -10, 100, 100), Some(Rect::new(0, 0, 80, 90))),
+        (03, Rect::new(600, 400, 100, 100), Some(Rect::new(600, 400, 40, 80))),
+        (04, Rect::new(620, 400, 100, 100), None),
+        (05, Rect::new(700, 500, 100, 100), None),
+    ];
+    for (step, roi, target) in test_data {
+        let result = Tracking::clamp(&roi, 640, 480, 32, 32);
+        log::debug!("step {step}   roi: {:?}  result: {:?}", roi, result);
+        assert!(result == target, "step {step} \nresult: {:?}\ntarget: {:?}", result, target);
+    }
+    test_duration.exit();
+}
@@ -0,0 +1,85 @@
+#[cfg(test)]
+use std::time::Duration;
+
+use std::{
+    sync::Once, 
+};
+use sal_core::dbg::Dbg;
+use testing::stuff::max_test_duration::TestDuration;
+use debugging::session::debug_session::{
+    DebugSession, 
+    LogLevel, 
+    Backtrace
+};
+
+use crate::modules::{Diagnostics, StageRecord};
+///
+///
+static INIT: Once = Once::new();
+///
+/// once called initialisation
+fn init_once() {
+    INIT.call_once(|| {
+        // implement your initialisation code to be called only once for current test file
+    })
+}
+///
+/// returns:
+///  - ...
+fn init_each() -> () {}
+///
+/// Returns [StageRecord]
+fn record(stage: &str, elapsed_ms: u64, output: Option<(i32, i32)>, error: Option<&str>) -> StageRecord {
+    StageRecord {
+        stage: stage.to_owned(),
+        elapsed: std::time::Duration::from_millis(elapsed_ms),
+        output,
+        error: error.map(|err| err.to_owned()),
+    }
+}
+///
+/// Testing [Diagnostics] aggregates the own duration, sizes & errors of the nested stages
+#[test]
+fn stages() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("Diagnostics-test-stages");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let diagnostics = Diagnostics::new();
+    let shared = diagnostics.clone();
+    // Frame 1, all stages ok, elapsed includes the inner stages
+    shared.record(record("initial", 1, Some((1200, 800)), None));
+    shared.record(record("crop", 3, Some((600, 400)), None));
+    shared.record(record("matcher", 13, Some((600, 400)), None));
+    diagnostics.finish_frame(Some(Duration::from_millis(20)));
+    // Frame 2, crop failed, matcher gets the error of the crop
+    shared.record(record("initial", 1, Some((1200, 800)), None));
+    shared.record(record("crop", 5, None, Some("out of the frame")));
+    shared.record(record("matcher", 5, None, Some("out of the frame")));
+    diagnostics.finish_frame(None);
+    let report = diagnostics.report();
+    log::debug!("report: {}", report);
+    assert!(report.frames == 2, "\nframes: {:?}\ntarget: {:?}", report.frames, 2);
+    let stages: Vec<&str> = report.stages.iter().map(|stage| stage.stage.as_str()).collect();
+    assert!(stages == ["initial", "crop", "matcher"], "\nstages: {:?}\ntarget: {:?}", stages, ["initial", "crop", "matcher"]);
+    // stage, mean, max, errors, input, output
+    let test_data = [
+        (01, "initial", 1.0, 1.0, 0, Some((1200, 800)), Some((1200, 800))),
+        (02, "crop", 3.0, 4.0, 1, Some((1200, 800)), Some((600, 400))),
+        (03, "matcher", 5.0, 10.0, 0, Some((600, 400)), Some((600, 400))),
+    ];
+    for (step, stage, mean, max, errors, input, output) in test_data {
+        let result = report.stages.iter().find(|report| report.stage == stage).unwrap();
+        log::debug!("step {step}   result: {:?}", result);
+        assert!(result.duration.mean == Some(mean), "step {step} \nmean: {:?}\ntarget: {:?}", result.duration.mean, mean);
+        assert!(result.duration.max == Some(max), "step {step} \nmax: {:?}\ntarget: {:?}", result.duration.max, max);
+        assert!(result.errors == errors, "step {step} \nerrors: {:?}\ntarget: {:?}", result.errors, errors);
+        assert!(result.input == input, "step {step} \ninput: {:?}\ntarget: {:?}", result.input, input);
+        assert!(result.output == output, "step {step} \noutput: {:?}\ntarget: {:?}", result.output, output);
+    }
+    assert!(report.latency.count == 1 && report.latency.max == Some(20.0), "\nlatency: {:?}\ntarget: 20 ms", report.latency);
+    test_duration.exit();
+}
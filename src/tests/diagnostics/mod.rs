@@ -0,0 +1,2 @@
+mod diagnostics_test;
+mod rolling_stats_test;
\ No newline at end of file
@@ -0,0 +1,64 @@
+#[cfg(test)]
+use std::time::Duration;
+
+use std::{
+    sync::Once, 
+};
+use sal_core::dbg::Dbg;
+use testing::stuff::max_test_duration::TestDuration;
+use debugging::session::debug_session::{
+    DebugSession, 
+    LogLevel, 
+    Backtrace
+};
+
+use crate::modules::RollingStats;
+///
+///
+static INIT: Once = Once::new();
+///
+/// once called initialisation
+fn init_once() {
+    INIT.call_once(|| {
+        // implement your initialisation code to be called only once for current test file
+    })
+}
+///
+/// returns:
+///  - ...
+fn init_each() -> () {}
+///
+/// Testing [RollingStats] mean, percentile & max over the window
+#[test]
+fn stats() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("RollingStats-test-stats");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let mut stats: RollingStats<20> = RollingStats::new();
+    assert!(stats.mean().is_none() && stats.percentile(0.95).is_none() && stats.max().is_none(), "empty stats must have no values");
+    // step, value, mean, p95, max
+    let test_data = [
+        (01, 10.0, 10.0, 10.0, 10.0),
+        (02, 20.0, 15.0, 20.0, 20.0),
+        (03, 30.0, 20.0, 30.0, 30.0),
+    ];
+    for (step, value, mean, p95, max) in test_data {
+        stats.add(value);
+        let result = (stats.mean().unwrap(), stats.percentile(0.95).unwrap(), stats.max().unwrap());
+        log::debug!("step {step}   value: {value}  result: {:?}", result);
+        assert!(result == (mean, p95, max), "step {step} \nresult: {:?}\ntarget: {:?}", result, (mean, p95, max));
+    }
+    // 1..=20 replace the values above
+    for value in 1..=20 {
+        stats.add(value as f64);
+    }
+    let result = (stats.mean().unwrap(), stats.percentile(0.95).unwrap(), stats.percentile(0.5).unwrap(), stats.max().unwrap());
+    let target = (10.5, 19.0, 10.0, 20.0);
+    assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+    assert!(stats.count() == 23, "\ncount: {:?}\ntarget: {:?}", stats.count(), 23);
+    test_duration.exit();
+}
@@ -0,0 +1,83 @@
+#[cfg(test)]
+use std::time::Duration;
+
+use std::{
+    sync::Once, 
+};
+use sal_core::dbg::Dbg;
+use testing::stuff::max_test_duration::TestDuration;
+use debugging::session::debug_session::{
+    DebugSession, 
+    LogLevel, 
+    Backtrace
+};
+
+use crate::modules::{Filter, FilterAverage, Position};
+///
+///
+static INIT: Once = Once::new();
+///
+/// once called initialisation
+fn init_once() {
+    INIT.call_once(|| {
+        // implement your initialisation code to be called only once for current test file
+    })
+}
+///
+/// returns:
+///  - ...
+fn init_each() -> () {}
+///
+/// Testing [FilterAverage].add
+#[test]
+fn filter_u16() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("FilterAverage-test-u16");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let test_data = [
+        (01, 003, Some(003)),
+        (02, 005, Some(004)),
+        (03, 010, Some(006)),
+        // window is filled, the oldest value (3) is dropped
+        (04, 013, Some(009)),
+        (05, 013, Some(012)),
+        (06, 013, Some(013)),
+        (07, 004, Some(010)),
+    ];
+    let mut filter: FilterAverage<u16> = FilterAverage::new(3, None);
+    for (step, val, target) in test_data {
+        let result = filter.add(val);
+        log::debug!("step {step}   val: {:?}  result: {:?}", val, result);
+        assert!(result == target, "step {step}   \nresult: {:?}\ntarget: {:?}", result, target);
+    }
+    test_duration.exit();
+}
+///
+/// Testing [FilterAverage].add on the [Position] values
+#[test]
+fn filter_position() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("FilterAverage-test-position");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let test_data = [
+        (01, Position::new(10.0, 100.0), Position::new(10.0, 100.0)),
+        (02, Position::new(20.0, 80.0), Position::new(15.0, 90.0)),
+        (03, Position::new(30.0, 80.0), Position::new(25.0, 80.0)),
+        (04, Position::new(40.0, 60.0), Position::new(35.0, 70.0)),
+    ];
+    let mut filter: FilterAverage<Position<f64>> = FilterAverage::new(2, None);
+    for (step, val, target) in test_data {
+        let result = filter.add(val);
+        log::debug!("step {step}   val: {:?}  result: {:?}", val, result);
+        assert!(result == Some(target), "step {step}   \nresult: {:?}\ntarget: {:?}", result, target);
+    }
+    test_duration.exit();
+}
@@ -0,0 +1,90 @@
+#[cfg(test)]
+use std::time::Duration;
+
+use std::{
+    sync::Once, 
+};
+use sal_core::dbg::Dbg;
+use testing::stuff::max_test_duration::TestDuration;
+use debugging::session::debug_session::{
+    DebugSession, 
+    LogLevel, 
+    Backtrace
+};
+
+use crate::modules::{Filter, FilterAxis, FilterChain, FilterKalman, FilterSmooth, KalmanModel, Position};
+///
+///
+static INIT: Once = Once::new();
+///
+/// once called initialisation
+fn init_once() {
+    INIT.call_once(|| {
+        // implement your initialisation code to be called only once for current test file
+    })
+}
+///
+/// returns:
+///  - ...
+fn init_each() -> () {}
+///
+/// Testing [FilterChain].add with the filters applied to both & single axis
+#[test]
+fn chain() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("FilterChain-test-chain");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let mut empty: FilterChain<Position<f64>> = FilterChain::new(vec![]);
+    let result = empty.add(Position::new(1.0, 2.0));
+    assert!(result == Some(Position::new(1.0, 2.0)), "\nresult: {:?}\ntarget: {:?}", result, Position::new(1.0, 2.0));
+    assert!(empty.predict().is_none(), "empty chain can't predict");
+    let mut filter: FilterChain<Position<f64>> = FilterChain::new(vec![
+        Box::new(FilterAxis::new(0, Box::new(FilterSmooth::new(None, 2.0)))),
+        Box::new(FilterSmooth::new(None, 2.0)),
+    ]);
+    let test_data = [
+        (01, Position::new(16.0, 16.0), Position::new(16.0, 16.0)),
+        // x: 16 + (32 - 16) / 2 = 24 => 16 + (24 - 16) / 2 = 20,  y: 16 + (32 - 16) / 2 = 24
+        (02, Position::new(32.0, 32.0), Position::new(20.0, 24.0)),
+        // x: 24 + (32 - 24) / 2 = 28 => 20 + (28 - 20) / 2 = 24,  y: 24 + (32 - 24) / 2 = 28
+        (03, Position::new(32.0, 32.0), Position::new(24.0, 28.0)),
+    ];
+    for (step, val, target) in test_data {
+        let result = filter.add(val);
+        log::debug!("step {step}   val: {:?}  result: {:?}", val, result);
+        assert!(result == Some(target), "step {step}   \nresult: {:?}\ntarget: {:?}", result, target);
+    }
+    assert!(filter.velocity().is_none(), "\nvelocity: {:?}\ntarget: None", filter.velocity());
+    test_duration.exit();
+}
+///
+/// Testing [FilterChain].predict, passes predicted value to the next filters
+#[test]
+fn predict() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("FilterChain-test-predict");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let mut filter: FilterChain<Position<f64>> = FilterChain::new(vec![
+        Box::new(FilterSmooth::new(None, 1.0)),
+        Box::new(FilterKalman::new(KalmanModel::Velocity, 0.01, 1.0, 2)),
+        Box::new(FilterSmooth::new(None, 1.0)),
+    ]);
+    for step in 0..50 {
+        filter.add(Position::new(2.0 * step as f64, 0.0));
+    }
+    let velocity = filter.velocity().unwrap();
+    assert!((velocity.x - 2.0).abs() < 0.1 && velocity.y.abs() < 0.1, "\nvelocity: {:?}\ntarget: {:?}", velocity, (2.0, 0.0));
+    let result = filter.predict().unwrap();
+    assert!((result.x - 100.0).abs() < 0.5 && result.y.abs() < 0.5, "\nresult: {:?}\ntarget: {:?}", result, (100.0, 0.0));
+    assert!(filter.predict().is_some(), "second prediction expected");
+    assert!(filter.predict().is_none(), "predictions must stop after 2 missed values");
+    test_duration.exit();
+}
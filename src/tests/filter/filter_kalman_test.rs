@@ -0,0 +1,128 @@
+#[cfg(test)]
+use std::time::Duration;
+
+use std::{
+    sync::Once, 
+};
+use sal_core::dbg::Dbg;
+use testing::stuff::max_test_duration::TestDuration;
+use debugging::session::debug_session::{
+    DebugSession, 
+    LogLevel, 
+    Backtrace
+};
+
+use crate::modules::{Filter, FilterKalman, KalmanModel, Position};
+///
+///
+static INIT: Once = Once::new();
+///
+/// once called initialisation
+fn init_once() {
+    INIT.call_once(|| {
+        // implement your initialisation code to be called only once for current test file
+    })
+}
+///
+/// returns:
+///  - ...
+fn init_each() -> () {}
+///
+/// Testing [FilterKalman] converges to the target moving with constant velocity
+#[test]
+fn constant_velocity() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("FilterKalman-test-constant_velocity");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    for model in [KalmanModel::Velocity, KalmanModel::Acceleration] {
+        let mut filter = FilterKalman::new(model, 0.01, 4.0, 5);
+        assert!(filter.velocity().is_none(), "model: {:?} \nvelocity must be unknown before the first value", model);
+        // Target moves by (3, -2) px per frame with the measurement noise +/- 1 px
+        let mut result = None;
+        for step in 0..100 {
+            let noise = if step % 2 == 0 { 1.0 } else { -1.0 };
+            let value = Position::new(100.0 + 3.0 * step as f64 + noise, 500.0 - 2.0 * step as f64 - noise);
+            result = filter.add(value);
+            log::trace!("model: {:?}  step {step}   val: {:?}  result: {:?}", model, value, result);
+        }
+        let result = result.unwrap();
+        let velocity = filter.velocity().unwrap();
+        log::debug!("model: {:?}  result: {:?}  velocity: {:?}", model, result, velocity);
+        let target = Position::new(100.0 + 3.0 * 99.0, 500.0 - 2.0 * 99.0);
+        assert!((result.x - target.x).abs() < 1.0 && (result.y - target.y).abs() < 1.0, "model: {:?} \nresult: {:?}\ntarget: {:?}", model, result, target);
+        assert!((velocity.x - 3.0).abs() < 0.25 && (velocity.y + 2.0).abs() < 0.25, "model: {:?} \nvelocity: {:?}\ntarget: {:?}", model, velocity, (3.0, -2.0));
+    }
+    test_duration.exit();
+}
+///
+/// Testing [FilterKalman].predict through the missed values
+#[test]
+fn predict() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("FilterKalman-test-predict");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let max_predictions = 3;
+    let mut filter = FilterKalman::new(KalmanModel::Velocity, 0.01, 1.0, max_predictions);
+    assert!(filter.predict().is_none(), "nothing to predict before the first value");
+    for step in 0..50 {
+        filter.add(Position::new(10.0 * step as f64, 20.0));
+    }
+    // Last value is (490, 20), target keeps moving by 10 px per step
+    for step in 1..=max_predictions {
+        let result = filter.predict().unwrap();
+        let target = Position::new(490.0 + 10.0 * step as f64, 20.0);
+        log::debug!("step {step}  result: {:?}  target: {:?}", result, target);
+        assert!((result.x - target.x).abs() < 0.5 && (result.y - target.y).abs() < 0.5, "step {step} \nresult: {:?}\ntarget: {:?}", result, target);
+    }
+    let result = filter.predict();
+    assert!(result.is_none(), "predictions must stop after {max_predictions} missed values, \nresult: {:?}", result);
+    assert!(filter.velocity().is_none(), "filter must be reset after {max_predictions} missed values");
+    // Starts again from the new value
+    let result = filter.add(Position::new(5.0, 5.0));
+    assert!(result == Some(Position::new(5.0, 5.0)), "\nresult: {:?}\ntarget: {:?}", result, Position::new(5.0, 5.0));
+    test_duration.exit();
+}
+///
+/// Testing [FilterKalman].add_at & predict_at with the jittering & dropped frames,
+/// velocity remains per nominal sample
+#[test]
+fn time() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("FilterKalman-test-time");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let sample_time = 0.04;
+    // Target moves by 100 px/s => 4 px per nominal sample
+    let position = |time: f64| Position::new(100.0 * time, 50.0);
+    let mut filter = FilterKalman::new(KalmanModel::Velocity, 0.01, 1.0, 5).with_sample_time(sample_time);
+    let mut time = 0.0;
+    for step in 0..100 {
+        // frames come with jitter, each 5th frame is dropped
+        time += sample_time * if step % 2 == 0 { 0.7 } else { 1.3 };
+        if step % 5 == 4 {
+            continue;
+        }
+        filter.add_at(position(time), time);
+    }
+    let velocity = filter.velocity().unwrap();
+    log::debug!("velocity: {:?}", velocity);
+    assert!((velocity.x - 4.0).abs() < 0.05 && velocity.y.abs() < 0.05, "\nvelocity: {:?}\ntarget: {:?}", velocity, (4.0, 0.0));
+    // Frame without detection 0.1 s later
+    time += 0.1;
+    let result = filter.predict_at(time).unwrap();
+    let target = position(time);
+    log::debug!("result: {:?}  target: {:?}", result, target);
+    assert!((result.x - target.x).abs() < 0.5 && (result.y - target.y).abs() < 0.5, "\nresult: {:?}\ntarget: {:?}", result, target);
+    test_duration.exit();
+}
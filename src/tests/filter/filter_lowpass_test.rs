@@ -0,0 +1,126 @@
+#[cfg(test)]
+use std::time::Duration;
+
+use std::{
+    sync::Once, 
+};
+use sal_core::dbg::Dbg;
+use testing::stuff::max_test_duration::TestDuration;
+use debugging::session::debug_session::{
+    DebugSession, 
+    LogLevel, 
+    Backtrace
+};
+
+use crate::modules::{Filter, FilterLowPass, Position};
+///
+///
+static INIT: Once = Once::new();
+///
+/// once called initialisation
+fn init_once() {
+    INIT.call_once(|| {
+        // implement your initialisation code to be called only once for current test file
+    })
+}
+///
+/// returns:
+///  - ...
+fn init_each() -> () {}
+///
+/// Testing [FilterLowPass].add, step response
+#[test]
+fn step() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("FilterLowPass-test-step");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    // cutoff = 1 / (2 * PI * dt) => alpha = 0.5
+    let sample_time = 0.04;
+    let cutoff = 1.0 / (2.0 * std::f64::consts::PI * sample_time);
+    let test_data = [
+        (01, 0.0, 0.0),
+        (02, 8.0, 4.0),
+        (03, 8.0, 6.0),
+        (04, 8.0, 7.0),
+        (05, 8.0, 7.5),
+        (06, 0.0, 3.75),
+    ];
+    let mut filter: FilterLowPass<f64> = FilterLowPass::new(None, cutoff, sample_time);
+    for (step, val, target) in test_data {
+        let result = filter.add(val).unwrap();
+        log::debug!("step {step}   val: {:?}  result: {:?}", val, result);
+        assert!((result - target).abs() < 1e-9, "step {step}   \nresult: {:?}\ntarget: {:?}", result, target);
+    }
+    test_duration.exit();
+}
+///
+/// Testing [FilterLowPass].add, sine amplitude near the cutoff frequency
+/// and far above it
+#[test]
+fn cutoff() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("FilterLowPass-test-cutoff");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let sample_time = 0.001;
+    let cutoff = 2.0;
+    // frequency, Hz; expected amplitude, min, max
+    let test_data = [
+        (01, 0.2, 0.95, 1.0),
+        (02, 2.0, 0.65, 0.76),
+        (03, 40.0, 0.0, 0.1),
+    ];
+    for (step, frequency, min, max) in test_data {
+        let mut filter: FilterLowPass<Position<f64>> = FilterLowPass::new(None, cutoff, sample_time);
+        let samples = (10.0 / frequency / sample_time) as usize;
+        let mut amplitude: f64 = 0.0;
+        for i in 0..samples {
+            let val = (2.0 * std::f64::consts::PI * frequency * sample_time * i as f64).sin();
+            let result = filter.add(Position::new(val, val)).unwrap();
+            // skip the transient process
+            if i > samples / 2 {
+                amplitude = amplitude.max(result.x.abs());
+            }
+        }
+        log::debug!("step {step}   frequency: {:?}  amplitude: {:?}", frequency, amplitude);
+        assert!(amplitude >= min && amplitude <= max, "step {step}   \namplitude: {:?}\ntarget: {:?}..{:?}", amplitude, min, max);
+    }
+    test_duration.exit();
+}
+///
+/// Testing [FilterLowPass].add_at, alpha calculated from the real time between the values
+#[test]
+fn time() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("FilterLowPass-test-time");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    // RC = 0.04 s
+    let cutoff = 1.0 / (2.0 * std::f64::consts::PI * 0.04);
+    let test_data = [
+        (01, 0.00, 0.0, 0.0),
+        // dt = 0.04 => alpha = 0.5
+        (02, 0.04, 8.0, 4.0),
+        // frame dropped, dt = 0.12 => alpha = 0.75
+        (03, 0.16, 8.0, 7.0),
+        // same time, nominal dt = 0.02 => alpha = 1 / 3
+        (04, 0.16, 10.0, 8.0),
+    ];
+    let mut filter: FilterLowPass<f64> = FilterLowPass::new(None, cutoff, 0.02);
+    for (step, time, val, target) in test_data {
+        let result = filter.add_at(val, time).unwrap();
+        log::debug!("step {step}   time: {time}  val: {:?}  result: {:?}", val, result);
+        assert!((result - target).abs() < 1e-9, "step {step}   \nresult: {:?}\ntarget: {:?}", result, target);
+    }
+    test_duration.exit();
+}
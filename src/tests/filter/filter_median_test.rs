@@ -0,0 +1,87 @@
+#[cfg(test)]
+use std::time::Duration;
+
+use std::{
+    sync::Once, 
+};
+use sal_core::dbg::Dbg;
+use testing::stuff::max_test_duration::TestDuration;
+use debugging::session::debug_session::{
+    DebugSession, 
+    LogLevel, 
+    Backtrace
+};
+
+use crate::modules::{Filter, FilterMedian, Position};
+///
+///
+static INIT: Once = Once::new();
+///
+/// once called initialisation
+fn init_once() {
+    INIT.call_once(|| {
+        // implement your initialisation code to be called only once for current test file
+    })
+}
+///
+/// returns:
+///  - ...
+fn init_each() -> () {}
+///
+/// Testing [FilterMedian].add
+#[test]
+fn filter_u16() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("FilterMedian-test-u16");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let test_data = [
+        (01, 010, Some(010)),
+        // even number of values - average of two middle
+        (02, 020, Some(015)),
+        (03, 012, Some(012)),
+        // single spike is removed
+        (04, 200, Some(020)),
+        (05, 014, Some(014)),
+        (06, 015, Some(015)),
+        // value really changed, passes after (N + 1) / 2 values
+        (07, 050, Some(015)),
+        (08, 051, Some(050)),
+    ];
+    let mut filter: FilterMedian<u16> = FilterMedian::new(3, None);
+    for (step, val, target) in test_data {
+        let result = filter.add(val);
+        log::debug!("step {step}   val: {:?}  result: {:?}", val, result);
+        assert!(result == target, "step {step}   \nresult: {:?}\ntarget: {:?}", result, target);
+    }
+    test_duration.exit();
+}
+///
+/// Testing [FilterMedian].add on the [Position] values, each axis filtered separately
+#[test]
+fn filter_position() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("FilterMedian-test-position");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let test_data = [
+        (01, Position::new(10.0, 100.0), Position::new(10.0, 100.0)),
+        (02, Position::new(11.0, 102.0), Position::new(10.5, 101.0)),
+        (03, Position::new(90.0, 101.0), Position::new(11.0, 101.0)),
+        (04, Position::new(12.0, 0.0), Position::new(12.0, 101.0)),
+        (05, Position::new(13.0, 103.0), Position::new(13.0, 101.0)),
+    ];
+    let mut filter: FilterMedian<Position<f64>> = FilterMedian::new(3, None);
+    for (step, val, target) in test_data {
+        let result = filter.add(val);
+        log::debug!("step {step}   val: {:?}  result: {:?}", val, result);
+        assert!(result == Some(target), "step {step}   \nresult: {:?}\ntarget: {:?}", result, target);
+    }
+    test_duration.exit();
+}
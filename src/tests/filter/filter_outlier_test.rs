@@ -0,0 +1,105 @@
+#[cfg(test)]
+use std::time::Duration;
+
+use std::{
+    sync::Once, 
+};
+use sal_core::dbg::Dbg;
+use testing::stuff::max_test_duration::TestDuration;
+use debugging::session::debug_session::{
+    DebugSession, 
+    LogLevel, 
+    Backtrace
+};
+
+use crate::modules::{Filter, FilterOutlier, MaxSpeed, Position};
+///
+///
+static INIT: Once = Once::new();
+///
+/// once called initialisation
+fn init_once() {
+    INIT.call_once(|| {
+        // implement your initialisation code to be called only once for current test file
+    })
+}
+///
+/// returns:
+///  - ...
+fn init_each() -> () {}
+///
+/// Testing [FilterOutlier] with the max speed per frame
+#[test]
+fn per_frame() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("FilterOutlier-test-per_frame");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let mut filter: FilterOutlier<Position<f64>> = FilterOutlier::new(MaxSpeed::PerFrame(10.0), 3);
+    // step, value (None - missed frame), target, rejections
+    let test_data = [
+        (01, Some(Position::new(100.0, 100.0)), Some(Position::new(100.0, 100.0)), 0),
+        (02, Some(Position::new(106.0, 108.0)), Some(Position::new(106.0, 108.0)), 0),
+        // single reflection
+        (03, Some(Position::new(300.0, 100.0)), None, 1),
+        // 2 frames after the last accepted => 20 px allowed
+        (04, Some(Position::new(120.0, 108.0)), Some(Position::new(120.0, 108.0)), 0),
+        (05, None, None, 0),
+        // missed frame => 20 px allowed
+        (06, Some(Position::new(120.0, 126.0)), Some(Position::new(120.0, 126.0)), 0),
+        // target really moved, re-lock after 3 consistent values
+        (07, Some(Position::new(400.0, 126.0)), None, 1),
+        (08, Some(Position::new(405.0, 126.0)), None, 2),
+        (09, Some(Position::new(410.0, 126.0)), Some(Position::new(410.0, 126.0)), 0),
+        (10, Some(Position::new(415.0, 126.0)), Some(Position::new(415.0, 126.0)), 0),
+        // inconsistent outliers never re-lock
+        (11, Some(Position::new(0.0, 0.0)), None, 1),
+        (12, Some(Position::new(0.0, 300.0)), None, 2),
+        (13, Some(Position::new(300.0, 300.0)), None, 3),
+        (14, Some(Position::new(300.0, 0.0)), None, 4),
+    ];
+    for (step, value, target, rejections) in test_data {
+        let result = match value {
+            Some(value) => filter.add(value),
+            None => filter.predict(),
+        };
+        log::debug!("step {step}   value: {:?}  result: {:?}  rejections: {}", value, result, filter.rejections());
+        assert!(result == target, "step {step} \nresult: {:?}\ntarget: {:?}", result, target);
+        assert!(filter.rejections() == rejections, "step {step} \nrejections: {:?}\ntarget: {:?}", filter.rejections(), rejections);
+    }
+    assert!(filter.rejected() == 7, "\nrejected: {:?}\ntarget: {:?}", filter.rejected(), 7);
+    test_duration.exit();
+}
+///
+/// Testing [FilterOutlier] with the max speed per second
+#[test]
+fn per_second() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("FilterOutlier-test-per_second");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let mut filter: FilterOutlier<f64> = FilterOutlier::new(MaxSpeed::PerSecond(100.0), 2);
+    // step, value, time, target
+    let test_data = [
+        (01, 0.0, 0.0, Some(0.0)),
+        (02, 10.0, 0.1, Some(10.0)),
+        // 0.05 s => 5 allowed
+        (03, 20.0, 0.15, None),
+        // frame dropped, 0.3 s since the last accepted => 30 allowed
+        (04, 35.0, 0.4, Some(35.0)),
+        (05, 500.0, 0.5, None),
+        (06, 505.0, 0.55, Some(505.0)),
+    ];
+    for (step, value, time, target) in test_data {
+        let result = filter.add_at(value, time);
+        log::debug!("step {step}   value: {:?}  time: {:?}  result: {:?}", value, time, result);
+        assert!(result == target, "step {step} \nresult: {:?}\ntarget: {:?}", result, target);
+    }
+    test_duration.exit();
+}
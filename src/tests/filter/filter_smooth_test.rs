@@ -12,7 +12,7 @@ use debugging::session::debug_session::{
     Backtrace
 };
 
-use crate::modules::{Filter, FilterSmooth};
+use crate::modules::{Filter, FilterSmooth, Position};
 ///
 ///
 static INIT: Once = Once::new();
@@ -115,3 +115,61 @@ fn filter_u16() {
     }
     test_duration.exit();
 }
+///
+/// Testing [FilterSmooth].add on the float & [Position] values
+#[test]
+fn filter_position() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("FilterSmooth-test-position");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let test_data = [
+        (01, Position::new(10.0, 100.0), Position::new(10.0, 100.0)),
+        (02, Position::new(20.0, 80.0), Position::new(15.0, 90.0)),
+        (03, Position::new(20.0, 80.0), Position::new(17.5, 85.0)),
+        (04, Position::new(16.5, 75.0), Position::new(17.0, 80.0)),
+    ];
+    let mut filter: FilterSmooth<Position<f64>> = FilterSmooth::new(None, 2.0);
+    let mut filter_x: FilterSmooth<f32> = FilterSmooth::new(None, 2.0);
+    for (step, val, target) in test_data {
+        let result = filter.add(val);
+        log::debug!("step {step}   val: {:?}  result: {:?}", val, result);
+        assert!(result == Some(target), "step {step}   \nresult: {:?}\ntarget: {:?}", result, target);
+        let result = filter_x.add(val.x as f32);
+        assert!(result == Some(target.x as f32), "step {step}   \nresult: {:?}\ntarget: {:?}", result, target.x);
+    }
+    test_duration.exit();
+}
+///
+/// Testing [FilterSmooth].add_at, the value after the dropped frame
+/// is weighted as if the previous value was added twice
+#[test]
+fn filter_time() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("FilterSmooth-test-time");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let test_data = [
+        (01, 0.00, 0.0, 0.0),
+        (02, 0.04, 16.0, 8.0),
+        // frame dropped, 2 samples: 8 + (16 - 8) * (1 - 0.5 ^ 2)
+        (03, 0.12, 16.0, 14.0),
+        // time jitter, 0.5 sample: 14 + (30 - 14) * (1 - 0.5 ^ 0.5)
+        (04, 0.14, 30.0, 14.0 + 16.0 * (1.0 - 0.5f64.sqrt())),
+        // same time, nominal sample
+        (05, 0.14, 0.0, (14.0 + 16.0 * (1.0 - 0.5f64.sqrt())) / 2.0),
+    ];
+    let mut filter: FilterSmooth<f64> = FilterSmooth::new(None, 2.0).with_sample_time(0.04);
+    for (step, time, val, target) in test_data {
+        let result = filter.add_at(val, time).unwrap();
+        log::debug!("step {step}   time: {time}  val: {:?}  result: {:?}", val, result);
+        assert!((result - target).abs() < 1e-9, "step {step}   \nresult: {:?}\ntarget: {:?}", result, target);
+    }
+    test_duration.exit();
+}
@@ -1 +1,7 @@
+mod filter_average_test;
+mod filter_chain_test;
+mod filter_kalman_test;
+mod filter_lowpass_test;
+mod filter_median_test;
+mod filter_outlier_test;
 mod filter_smooth_test;
\ No newline at end of file
@@ -0,0 +1,205 @@
+#[cfg(test)]
+use std::time::Duration;
+
+use std::{
+    sync::Once,
+    time::Instant,
+};
+use sal_core::dbg::Dbg;
+use testing::stuff::max_test_duration::TestDuration;
+use debugging::session::debug_session::{
+    DebugSession,
+    LogLevel,
+    Backtrace
+};
+
+use crate::modules::{CameraId, CameraModel, Fusion, FusionConf, Intrinsics, Position, Position3, Sway, Target, WorldPosition};
+///
+///
+static INIT: Once = Once::new();
+///
+/// once called initialisation
+fn init_once() {
+    INIT.call_once(|| {
+        // implement your initialisation code to be called only once for current test file
+    })
+}
+///
+/// returns:
+///  - ...
+fn init_each() -> () {}
+///
+/// Returns the stereo pair, the second camera is 0.5 m to the right of the first one
+fn models() -> Vec<(CameraId, CameraModel)> {
+    let intrinsics = Intrinsics { fx: 1000.0, fy: 1000.0, cx: 600.0, cy: 400.0 };
+    let identity = CameraModel::rodrigues([0.0; 3]);
+    vec![
+        (CameraId(1), CameraModel::new(intrinsics, identity, [0.0, 0.0, 0.0])),
+        (CameraId(2), CameraModel::new(intrinsics, identity, [-0.5, 0.0, 0.0])),
+    ]
+}
+///
+/// Returns the target of the `camera` seeing the world `point` on the frame taken at `time`, ms
+fn target(models: &[(CameraId, CameraModel)], camera: usize, time: u64, point: Position3<f64>) -> Target {
+    let (id, model) = models.iter().find(|(id, _)| id.0 == camera).unwrap();
+    Target { camera: *id, time: time as f64 / 1000.0, position: model.project(point).unwrap(), velocity: None, world: None, sway: None }
+}
+///
+/// Returns the cameras & fused flags of the `result`
+fn published(result: &[Target]) -> Vec<(usize, bool)> {
+    result.iter().map(|target| (target.camera.0, matches!(target.world, Some(WorldPosition::Space(_))))).collect()
+}
+///
+/// Testing [Fusion] publishes the 3D position if all the cameras see the target within `sync`
+/// and falls back to the single camera 2D position if some camera loses the target
+#[test]
+fn fusion() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("Fusion-test-fusion");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let models = models();
+    let conf = FusionConf { sync: Duration::from_millis(40), max_error: 2.0, ..FusionConf::default() };
+    let mut fusion = Fusion::new(conf, models.clone());
+    let point1 = Position3::new(0.2, -0.1, 5.0);
+    let point2 = Position3::new(0.25, -0.1, 5.0);
+    let start = Instant::now();
+    // step, time ms, camera to add or None to poll, point, target published: (camera, fused)
+    let test_data = [
+        (01, 0, Some(1), point1, vec![]),
+        (02, 10, Some(2), point1, vec![(1, true)]),
+        (03, 30, Some(2), point2, vec![]),
+        (04, 35, Some(1), point2, vec![(1, true)]),
+        // Camera 2 lost the target
+        (05, 60, Some(1), point1, vec![]),
+        (06, 70, None, point1, vec![]),
+        (07, 93, Some(1), point2, vec![(1, false)]),
+        (08, 100, None, point1, vec![]),
+        (09, 140, None, point1, vec![(1, false)]),
+        (10, 150, None, point1, vec![]),
+        // View of the camera 1 is too old to be fused
+        (11, 200, Some(1), point1, vec![]),
+        (12, 250, Some(2), point1, vec![(1, false)]),
+        (13, 260, Some(1), point1, vec![(1, true)]),
+        // Camera 1 lost the target
+        (14, 300, Some(2), point2, vec![]),
+        (15, 350, None, point2, vec![(2, false)]),
+    ];
+    for (step, time, camera, point, target) in test_data {
+        let now = start + Duration::from_millis(time);
+        let result = match camera {
+            Some(camera) => fusion.add(self::target(&models, camera, time, point), now),
+            None => fusion.poll(now),
+        };
+        log::debug!("step {} | result: {:?}", step, result);
+        assert!(published(&result) == target, "step {} \nresult: {:?}\ntarget: {:?}", step, published(&result), target);
+        for world in result.iter().filter_map(|target| match target.world {
+            Some(WorldPosition::Space(world)) => Some(world),
+            _ => None,
+        }) {
+            let error = ((world.x - point.x).powi(2) + (world.y - point.y).powi(2) + (world.z - point.z).powi(2)).sqrt();
+            assert!(error < 1e-6, "step {} \nresult: {:?}\ntarget: {:?}", step, world, point);
+        }
+    }
+    assert!(fusion.fused() == 3, "\nresult: {:?}\ntarget: {:?}", fusion.fused(), 3);
+    assert!(fusion.fallback() == 4, "\nresult: {:?}\ntarget: {:?}", fusion.fallback(), 4);
+    test_duration.exit();
+}
+///
+/// Testing [Fusion] publishes the single camera 2D positions of all the cameras if the views are inconsistent
+#[test]
+fn inconsistent() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("Fusion-test-inconsistent");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let models = models();
+    let conf = FusionConf { sync: Duration::from_millis(40), max_error: 2.0, ..FusionConf::default() };
+    let mut fusion = Fusion::new(conf, models.clone());
+    let start = Instant::now();
+    // step, position of the camera 1, position of the camera 2, target published: (camera, fused)
+    let test_data = [
+        (01, Position::new(640.0, 380.0), Position::new(540.0, 380.0), vec![(1, true)]),
+        // Different rows, not on the epipolar line
+        (02, Position::new(640.0, 380.0), Position::new(540.0, 420.0), vec![(1, false), (2, false)]),
+        (03, Position::new(640.0, 380.0), Position::new(540.0, 381.0), vec![(1, true)]),
+    ];
+    for (step, position1, position2, target) in test_data {
+        let now = start + Duration::from_millis(step * 100);
+        let time = step as f64 * 0.1;
+        let result = fusion.add(Target { camera: CameraId(1), time, position: position1, velocity: None, world: None, sway: Some(Sway::default()) }, now);
+        assert!(result.is_empty(), "step {} \nresult: {:?}\ntarget: {:?}", step, result, Vec::<Target>::new());
+        let result = fusion.add(Target { camera: CameraId(2), time, position: position2, velocity: None, world: None, sway: None }, now);
+        log::debug!("step {} | result: {:?}", step, result);
+        assert!(published(&result) == target, "step {} \nresult: {:?}\ntarget: {:?}", step, published(&result), target);
+        // The single camera positions are published as they were found
+        for target in result.iter().filter(|target| target.world.is_none()) {
+            let position = match target.camera.0 {
+                1 => position1,
+                _ => position2,
+            };
+            assert!(target.position == position, "step {} \nresult: {:?}\ntarget: {:?}", step, target.position, position);
+        }
+        // sway of the main camera isn't of the fused position
+        let result: Vec<_> = result.iter().filter(|target| target.world.is_some()).collect();
+        assert!(result.iter().all(|target| target.sway.is_none()), "step {} \nresult: {:?}\ntarget: {:?}", step, result, "no sway");
+    }
+    assert!(fusion.fused() == 2, "\nresult: {:?}\ntarget: {:?}", fusion.fused(), 2);
+    assert!(fusion.fallback() == 2, "\nresult: {:?}\ntarget: {:?}", fusion.fallback(), 2);
+    test_duration.exit();
+}
+///
+/// Testing [Fusion] syncs the views by the frame time & publishes all the expired views
+#[test]
+fn sync() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("Fusion-test-sync");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let intrinsics = Intrinsics { fx: 1000.0, fy: 1000.0, cx: 600.0, cy: 400.0 };
+    let identity = CameraModel::rodrigues([0.0; 3]);
+    let mut models = models();
+    models.push((CameraId(3), CameraModel::new(intrinsics, identity, [0.0, -0.5, 0.0])));
+    let conf = FusionConf { sync: Duration::from_millis(40), max_error: 2.0, ..FusionConf::default() };
+    let mut fusion = Fusion::new(conf, models.clone());
+    let point = Position3::new(0.2, -0.1, 5.0);
+    let start = Instant::now();
+    // step, received ms, frame time ms, camera to add or None to poll, target published: (camera, fused)
+    let test_data = [
+        (01, 0, 0, Some(1), vec![]),
+        (02, 5, 5, Some(2), vec![]),
+        (03, 10, 10, Some(3), vec![(1, true)]),
+        // Camera 3 lost the target, both views are published
+        (04, 100, 100, Some(1), vec![]),
+        (05, 105, 105, Some(2), vec![]),
+        (06, 130, 0, None, vec![]),
+        (07, 150, 0, None, vec![(1, false), (2, false)]),
+        // Received in time, but the frames are out of sync
+        (08, 200, 200, Some(1), vec![]),
+        (09, 205, 150, Some(2), vec![(1, false)]),
+        (10, 210, 210, Some(3), vec![(2, false)]),
+        (11, 215, 215, Some(1), vec![]),
+        (12, 220, 220, Some(2), vec![(1, true)]),
+    ];
+    for (step, received, time, camera, target) in test_data {
+        let now = start + Duration::from_millis(received);
+        let result = match camera {
+            Some(camera) => fusion.add(self::target(&models, camera, time, point), now),
+            None => fusion.poll(now),
+        };
+        log::debug!("step {} | result: {:?}", step, result);
+        assert!(published(&result) == target, "step {} \nresult: {:?}\ntarget: {:?}", step, published(&result), target);
+    }
+    assert!(fusion.fused() == 2, "\nresult: {:?}\ntarget: {:?}", fusion.fused(), 2);
+    assert!(fusion.fallback() == 4, "\nresult: {:?}\ntarget: {:?}", fusion.fallback(), 4);
+    test_duration.exit();
+}
@@ -0,0 +1,2 @@
+mod fusion_test;
+mod triangulation_test;
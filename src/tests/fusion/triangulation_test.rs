@@ -0,0 +1,139 @@
+#[cfg(test)]
+use std::time::Duration;
+
+use std::{
+    sync::Once,
+};
+use sal_core::dbg::Dbg;
+use testing::stuff::max_test_duration::TestDuration;
+use debugging::session::debug_session::{
+    DebugSession,
+    LogLevel,
+    Backtrace
+};
+
+use crate::modules::{CameraId, CameraModel, Intrinsics, Position, Position3, Triangulation};
+///
+///
+static INIT: Once = Once::new();
+///
+/// once called initialisation
+fn init_once() {
+    INIT.call_once(|| {
+        // implement your initialisation code to be called only once for current test file
+    })
+}
+///
+/// returns:
+///  - ...
+fn init_each() -> () {}
+///
+/// Returns the camera located at the `center` of the world, rotated by the Rodrigues `rotation`
+fn camera(center: [f64; 3], rotation: [f64; 3]) -> CameraModel {
+    let intrinsics = Intrinsics { fx: 1000.0, fy: 1000.0, cx: 600.0, cy: 400.0 };
+    let rotation = CameraModel::rodrigues(rotation);
+    let translation: [f64; 3] = std::array::from_fn(|row| -(0..3).map(|i| rotation[row][i] * center[i]).sum::<f64>());
+    CameraModel::new(intrinsics, rotation, translation)
+}
+///
+/// Returns the distance between the points
+fn distance(a: Position3<f64>, b: Position3<f64>) -> f64 {
+    ((a.x - b.x).powi(2) + (a.y - b.y).powi(2) + (a.z - b.z).powi(2)).sqrt()
+}
+///
+/// Testing [CameraModel::rodrigues] returns the rotation matrix of the axis & angle
+#[test]
+fn rodrigues() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("Triangulation-test-rodrigues");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let half_pi = std::f64::consts::FRAC_PI_2;
+    // step, Rodrigues vector, rotated point, target
+    let test_data = [
+        (01, [0.0, 0.0, 0.0], Position3::new(1.0, 2.0, 3.0), Position3::new(1.0, 2.0, 3.0)),
+        (02, [0.0, 0.0, half_pi], Position3::new(1.0, 0.0, 0.0), Position3::new(0.0, 1.0, 0.0)),
+        (03, [half_pi, 0.0, 0.0], Position3::new(0.0, 1.0, 0.0), Position3::new(0.0, 0.0, 1.0)),
+        (04, [0.0, half_pi, 0.0], Position3::new(0.0, 0.0, 1.0), Position3::new(1.0, 0.0, 0.0)),
+        (05, [0.0, 0.0, -half_pi * 2.0], Position3::new(1.0, 1.0, 5.0), Position3::new(-1.0, -1.0, 5.0)),
+    ];
+    for (step, vector, point, target) in test_data {
+        let model = CameraModel::new(Intrinsics { fx: 1.0, fy: 1.0, cx: 0.0, cy: 0.0 }, CameraModel::rodrigues(vector), [0.0; 3]);
+        let result = model.to_camera(point);
+        assert!(distance(result, target) < 1e-9, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+    }
+    test_duration.exit();
+}
+///
+/// Testing [Triangulation] restores the world point from its projections on two & three cameras
+#[test]
+fn triangulate() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("Triangulation-test-triangulate");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let models = vec![
+        (CameraId(1), camera([0.0, 0.0, 0.0], [0.0, 0.0, 0.0])),
+        (CameraId(2), camera([0.5, 0.0, 0.0], [0.0, -0.05, 0.0])),
+        (CameraId(3), camera([0.0, -0.6, 0.2], [0.08, 0.0, 0.0])),
+    ];
+    let triangulation = Triangulation::new(models.clone());
+    // step, world point, cameras, noise of the projections, px, max distance to the point, m
+    let test_data = [
+        (01, Position3::new(0.0, 0.0, 5.0), vec![1, 2], 0.0, 1e-6),
+        (02, Position3::new(0.3, -0.2, 4.0), vec![1, 2], 0.0, 1e-6),
+        (03, Position3::new(-1.0, 0.5, 10.0), vec![1, 2], 0.0, 1e-6),
+        (04, Position3::new(0.2, 0.1, 6.0), vec![1, 2, 3], 0.0, 1e-6),
+        (05, Position3::new(0.2, 0.1, 6.0), vec![2, 3], 0.0, 1e-6),
+        (06, Position3::new(0.2, 0.1, 6.0), vec![1, 2], 0.5, 0.1),
+        (07, Position3::new(0.2, 0.1, 6.0), vec![1, 2, 3], 0.5, 0.1),
+    ];
+    for (step, point, cameras, noise, max_distance) in test_data {
+        let views: Vec<(CameraId, Position<f64>)> = cameras.iter().enumerate().map(|(i, camera)| {
+            let (_, model) = models.iter().find(|(id, _)| id.0 == *camera).unwrap();
+            let projection = model.project(point).unwrap();
+            let sign = if i % 2 == 0 { 1.0 } else { -1.0 };
+            (CameraId(*camera), Position::new(projection.x + noise * sign, projection.y - noise * sign))
+        }).collect();
+        let (result, error) = triangulation.triangulate(&views).unwrap();
+        log::debug!("step {} | result: {:?}, reprojection error: {:.4} px", step, result, error);
+        assert!(distance(result, point) < max_distance, "step {} \nresult: {:?}\ntarget: {:?}", step, result, point);
+        assert!(error <= noise * 2.0 + 1e-6, "step {} \nresult: {:?}\ntarget: {:?}", step, error, noise * 2.0);
+    }
+    test_duration.exit();
+}
+///
+/// Testing [Triangulation] returns None for the single view, unknown cameras & the point behind the cameras
+#[test]
+fn degenerate() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("Triangulation-test-degenerate");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let triangulation = Triangulation::new(vec![
+        (CameraId(1), camera([0.0, 0.0, 0.0], [0.0, 0.0, 0.0])),
+        (CameraId(2), camera([0.5, 0.0, 0.0], [0.0, 0.0, 0.0])),
+    ]);
+    // step, views
+    let test_data = [
+        (01, vec![]),
+        (02, vec![(CameraId(1), Position::new(600.0, 400.0))]),
+        (03, vec![(CameraId(1), Position::new(600.0, 400.0)), (CameraId(7), Position::new(500.0, 400.0))]),
+        // Rays are diverging, the point is behind the cameras
+        (04, vec![(CameraId(1), Position::new(500.0, 400.0)), (CameraId(2), Position::new(700.0, 400.0))]),
+    ];
+    for (step, views) in test_data {
+        let result = triangulation.triangulate(&views);
+        assert!(result.is_none(), "step {} \nresult: {:?}\ntarget: {:?}", step, result, None::<()>);
+    }
+    test_duration.exit();
+}
@@ -0,0 +1,2 @@
+mod read_registers_test;
+mod rope_length_test;
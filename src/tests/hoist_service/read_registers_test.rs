@@ -0,0 +1,145 @@
+#[cfg(test)]
+use std::time::Duration;
+
+use std::{
+    io::{Cursor, Read, Write},
+    sync::Once,
+};
+use sal_core::dbg::Dbg;
+use testing::stuff::max_test_duration::TestDuration;
+use debugging::session::debug_session::{
+    DebugSession,
+    LogLevel,
+    Backtrace
+};
+
+use crate::modules::ReadRegisters;
+///
+///
+static INIT: Once = Once::new();
+///
+/// once called initialisation
+fn init_once() {
+    INIT.call_once(|| {
+        // implement your initialisation code to be called only once for current test file
+    })
+}
+///
+/// returns:
+///  - ...
+fn init_each() -> () {}
+///
+/// The device replying with the prepared bytes, keeps the bytes written
+struct FakeDevice {
+    reply: Cursor<Vec<u8>>,
+    written: Vec<u8>,
+}
+impl Read for FakeDevice {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.reply.read(buf)
+    }
+}
+impl Write for FakeDevice {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.written.write(buf)
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+///
+/// Testing [ReadRegisters::request] message
+#[test]
+fn request() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("ReadRegisters-test-request");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    // step, transaction id, request, target
+    let test_data = [
+        (01, 0x0001, ReadRegisters::new(1, 0x03, 111, 1), vec![0x00, 0x01, 0x00, 0x00, 0x00, 0x06, 0x01, 0x03, 0x00, 0x6F, 0x00, 0x01]),
+        (02, 0x1234, ReadRegisters::new(7, 0x04, 0x0A0B, 2), vec![0x12, 0x34, 0x00, 0x00, 0x00, 0x06, 0x07, 0x04, 0x0A, 0x0B, 0x00, 0x02]),
+    ];
+    for (step, tr_id, request, target) in test_data {
+        let result = request.request(tr_id);
+        assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+    }
+    test_duration.exit();
+}
+///
+/// Testing [ReadRegisters::response] parsed & the wrong responses rejected
+#[test]
+fn response() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("ReadRegisters-test-response");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let request = ReadRegisters::new(1, 0x03, 111, 2);
+    // step, transaction id, response, target: None - error
+    let test_data = [
+        (01, 5, vec![0x00, 0x05, 0x00, 0x00, 0x00, 0x07, 0x01, 0x03, 0x04, 0x12, 0x34, 0xAB, 0xCD], Some(vec![0x1234, 0xABCD])),
+        // wrong transaction id
+        (02, 6, vec![0x00, 0x05, 0x00, 0x00, 0x00, 0x07, 0x01, 0x03, 0x04, 0x12, 0x34, 0xAB, 0xCD], None),
+        // exception, illegal data address
+        (03, 5, vec![0x00, 0x05, 0x00, 0x00, 0x00, 0x03, 0x01, 0x83, 0x02], None),
+        // wrong function code
+        (04, 5, vec![0x00, 0x05, 0x00, 0x00, 0x00, 0x07, 0x01, 0x04, 0x04, 0x12, 0x34, 0xAB, 0xCD], None),
+        // single register instead of two
+        (05, 5, vec![0x00, 0x05, 0x00, 0x00, 0x00, 0x05, 0x01, 0x03, 0x02, 0x12, 0x34], None),
+        // truncated
+        (06, 5, vec![0x00, 0x05, 0x00, 0x00, 0x00, 0x07, 0x01, 0x03, 0x04, 0x12, 0x34], None),
+        (07, 5, vec![0x00, 0x05, 0x00], None),
+    ];
+    for (step, tr_id, response, target) in test_data {
+        let result = request.response(tr_id, &response);
+        log::debug!("step {} | result: {:?}", step, result);
+        match target {
+            Some(target) => {
+                let result = result.unwrap();
+                assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            }
+            None => assert!(result.is_err(), "step {} \nresult: {:?}\ntarget: {:?}", step, result, "Err"),
+        }
+    }
+    test_duration.exit();
+}
+///
+/// Testing [ReadRegisters::read] sends the request & reads the whole response from the stream
+#[test]
+fn read() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("ReadRegisters-test-read");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let request = ReadRegisters::new(1, 0x03, 111, 1);
+    // step, reply of the device, target: None - error
+    let test_data = [
+        (01, vec![0x00, 0x09, 0x00, 0x00, 0x00, 0x05, 0x01, 0x03, 0x02, 0x30, 0x39], Some(vec![12345])),
+        (02, vec![0x00, 0x09, 0x00, 0x00, 0x00, 0x03, 0x01, 0x83, 0x02], None),
+        (03, vec![0x00, 0x09, 0x00, 0x00, 0x00, 0x05, 0x01, 0x03], None),
+        (04, vec![], None),
+    ];
+    for (step, reply, target) in test_data {
+        let mut device = FakeDevice { reply: Cursor::new(reply), written: vec![] };
+        let result = request.read(&mut device, 9);
+        log::debug!("step {} | result: {:?}", step, result);
+        assert!(device.written == request.request(9), "step {} \nresult: {:?}\ntarget: {:?}", step, device.written, request.request(9));
+        match target {
+            Some(target) => {
+                let result = result.unwrap();
+                assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            }
+            None => assert!(result.is_err(), "step {} \nresult: {:?}\ntarget: {:?}", step, result, "Err"),
+        }
+    }
+    test_duration.exit();
+}
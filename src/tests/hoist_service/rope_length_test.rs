@@ -0,0 +1,149 @@
+#[cfg(test)]
+use std::time::Duration;
+
+use std::{
+    sync::Once,
+};
+use sal_core::dbg::Dbg;
+use testing::stuff::max_test_duration::TestDuration;
+use debugging::session::debug_session::{
+    DebugSession,
+    LogLevel,
+    Backtrace
+};
+
+use crate::modules::{RegisterType, RopeLength};
+///
+///
+static INIT: Once = Once::new();
+///
+/// once called initialisation
+fn init_once() {
+    INIT.call_once(|| {
+        // implement your initialisation code to be called only once for current test file
+    })
+}
+///
+/// returns:
+///  - ...
+fn init_each() -> () {}
+///
+/// Testing [RopeLength] shared between the clones
+#[test]
+fn shared() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("RopeLength-test-shared");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let rope = RopeLength::new();
+    let reader = rope.clone();
+    // step, length set, target
+    let test_data = [
+        (01, None, None),
+        (02, Some(12.5), Some(12.5)),
+        (03, Some(0.0), Some(0.0)),
+        (04, None, None),
+        (05, Some(f64::INFINITY), None),
+        (06, Some(31.25), Some(31.25)),
+    ];
+    for (step, length, target) in test_data {
+        rope.set(length);
+        let result = reader.get();
+        assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+    }
+    test_duration.exit();
+}
+///
+/// Testing [RopeLength] estimated at the time between & after the timestamped samples
+#[test]
+fn at() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("RopeLength-test-at");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let rope = RopeLength::new().with_max_age(Duration::from_millis(300));
+    // step, sample set (time, length), time estimated at, target
+    let test_data = [
+        (01, None, 10.0, None),
+        (02, Some((10.0, Some(2.0))), 10.05, Some(2.0)),
+        (03, Some((10.1, Some(2.1))), 10.15, Some(2.15)),
+        (04, None, 10.05, Some(2.05)),
+        (05, None, 10.1, Some(2.1)),
+        (06, Some((10.2, Some(2.1))), 10.25, Some(2.1)),
+        (07, Some((10.3, Some(1.9))), 10.35, Some(1.8)),
+        (08, Some((10.4, None)), 10.45, None),
+        (09, Some((10.5, Some(3.0))), 10.55, Some(3.0)),
+        (10, Some((10.6, Some(3.1))), 10.85, Some(3.35)),
+        // The hoist stopped responding, the last samples are stale
+        (11, None, 10.95, None),
+        (12, None, 12.0, None),
+        (13, Some((12.0, Some(3.2))), 12.05, Some(3.2)),
+    ];
+    for (step, sample, time, target) in test_data {
+        if let Some((sample_time, length)) = sample {
+            rope.set_at(sample_time, length);
+        }
+        let result = rope.at(time);
+        let ok = match (result, target) {
+            (Some(result), Some(target)) => (result - target).abs() < 1e-9,
+            (result, target) => result == target,
+        };
+        assert!(ok, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+    }
+    test_duration.exit();
+}
+///
+/// Testing [RegisterType] values of the registers read
+#[test]
+fn register_type() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("RopeLength-test-register_type");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let f32_bits = 12.75f32.to_bits();
+    // step, type, registers, target
+    let test_data = [
+        (01, RegisterType::U16, vec![12345], Some(12345.0)),
+        (02, RegisterType::U16, vec![0xFFFF], Some(65535.0)),
+        (03, RegisterType::I16, vec![0xFFFF], Some(-1.0)),
+        (04, RegisterType::I16, vec![0x7FFF], Some(32767.0)),
+        (05, RegisterType::U32, vec![0x0001, 0x86A0], Some(100000.0)),
+        (06, RegisterType::F32, vec![(f32_bits >> 16) as u16, f32_bits as u16], Some(12.75)),
+        (07, RegisterType::F32, vec![0x7FC0, 0x0000], None),
+        (08, RegisterType::U32, vec![0x0001], None),
+        (09, RegisterType::U16, vec![], None),
+    ];
+    for (step, register_type, registers, target) in test_data {
+        let result = register_type.value(&registers);
+        assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+    }
+    // step, type, target
+    let test_data = [
+        (01, "u16", Some((RegisterType::U16, 1))),
+        (02, "I16", Some((RegisterType::I16, 1))),
+        (03, "u32", Some((RegisterType::U32, 2))),
+        (04, " f32 ", Some((RegisterType::F32, 2))),
+        (05, "f64", None),
+    ];
+    for (step, value, target) in test_data {
+        let result = value.parse::<RegisterType>();
+        match target {
+            Some((target, count)) => {
+                let result = result.unwrap();
+                assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+                assert!(result.count() == count, "step {} \nresult: {:?}\ntarget: {:?}", step, result.count(), count);
+            }
+            None => assert!(result.is_err(), "step {} \nresult: {:?}\ntarget: {:?}", step, result, "Err"),
+        }
+    }
+    test_duration.exit();
+}
@@ -0,0 +1,163 @@
+#[cfg(test)]
+use std::time::Duration;
+
+use std::{
+    sync::Once, 
+};
+use sal_core::dbg::Dbg;
+use testing::stuff::max_test_duration::TestDuration;
+use debugging::session::debug_session::{
+    DebugSession, 
+    LogLevel, 
+    Backtrace
+};
+
+use crate::modules::{CameraId, CameraState, CameraStatus, Diagnostics, Metrics, RopeDefect, RopeDefectKind, StageRecord};
+///
+///
+static INIT: Once = Once::new();
+///
+/// once called initialisation
+fn init_once() {
+    INIT.call_once(|| {
+        // implement your initialisation code to be called only once for current test file
+    })
+}
+///
+/// returns:
+///  - ...
+fn init_each() -> () {}
+///
+/// Testing [Metrics] renders the counters & gauges in the Prometheus text exposition format
+#[test]
+fn render() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("Metrics-test-render");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let metrics = Metrics::new();
+    let shared = metrics.clone();
+    let diagnostics = Diagnostics::new();
+    metrics.add_diagnostics(CameraId(0), diagnostics.clone());
+    let camera0 = CameraStatus::new();
+    let camera1 = CameraStatus::new();
+    metrics.add_camera(CameraId(0), camera0.clone());
+    metrics.add_camera(CameraId(1), camera1.clone());
+    camera0.set(CameraState::Streaming);
+    camera1.set(CameraState::Down);
+    camera1.reconnect();
+    diagnostics.record(StageRecord { stage: "initial".to_owned(), elapsed: Duration::from_millis(2), output: Some((1200, 800)), error: None });
+    diagnostics.record(StageRecord { stage: "matcher".to_owned(), elapsed: Duration::from_millis(10), output: Some((1200, 800)), error: None });
+    diagnostics.finish_frame(Some(Duration::from_millis(20)));
+    shared.frame(CameraId(0), true);
+    shared.frame(CameraId(0), true);
+    shared.frame(CameraId(0), false);
+    shared.frame(CameraId(0), true);
+    shared.keypoints(CameraId(0), 42);
+    shared.rejected(CameraId(0), 3);
+    shared.frame(CameraId(1), false);
+    shared.modbus_connected(true);
+    shared.modbus_write_error();
+    shared.modbus_reconnect();
+    shared.modbus_reconnect();
+    shared.modbus_overflow();
+    shared.backlog(7);
+    shared.fusion(5, 2);
+    shared.rope_length("App/HoistService", Some(12.5));
+    shared.rope_length("App/RopeDefectService/HoistService", Some(3.25));
+    shared.hoist_read_error("App/RopeDefectService/HoistService");
+    shared.rope_segment();
+    shared.rope_segment();
+    shared.rope_defect(RopeDefect { kind: RopeDefectKind::Bump, position: 1.5, length: 0.01, deviation: 0.002 });
+    shared.rope_segments_missed(3);
+    let result = metrics.render();
+    log::debug!("{}.render | result: \n{}", dbg, result);
+    let test_data = [
+        (01, "# TYPE position_tracking_frames_total counter"),
+        (02, "position_tracking_frames_total{camera=\"0\"} 4"),
+        (03, "position_tracking_matched_total{camera=\"0\"} 3"),
+        (04, "position_tracking_match_success_ratio{camera=\"0\"} 0.75"),
+        (05, "position_tracking_keypoints{camera=\"0\"} 42"),
+        (06, "position_tracking_filter_rejected_total{camera=\"0\"} 3"),
+        (07, "position_tracking_modbus_connected 1"),
+        (08, "position_tracking_modbus_write_errors_total 1"),
+        (09, "position_tracking_modbus_reconnects_total 2"),
+        (10, "position_tracking_channel_backlog 7"),
+        (11, "# TYPE position_tracking_stage_duration_milliseconds gauge"),
+        (12, "position_tracking_stage_duration_milliseconds{camera=\"0\",stage=\"initial\",stat=\"mean\"} 2"),
+        (13, "position_tracking_stage_duration_milliseconds{camera=\"0\",stage=\"matcher\",stat=\"max\"} 8"),
+        (14, "position_tracking_latency_milliseconds{camera=\"0\",stat=\"p95\"} 20"),
+        (15, "position_tracking_camera_up{camera=\"0\"} 1"),
+        (16, "position_tracking_camera_up{camera=\"1\"} 0"),
+        (17, "position_tracking_camera_reconnects_total{camera=\"1\"} 1"),
+        (18, "position_tracking_fusion_fused_total 5"),
+        (19, "position_tracking_fusion_fallback_total 2"),
+        (20, "position_tracking_rope_length_meters{hoist=\"App/HoistService\"} 12.5"),
+        (21, "position_tracking_hoist_read_errors_total{hoist=\"App/RopeDefectService/HoistService\"} 1"),
+        (22, "position_tracking_rope_segments_total 2"),
+        (23, "position_tracking_rope_defects_total{kind=\"bump\"} 1"),
+        (24, "position_tracking_rope_defects_total{kind=\"groove\"} 0"),
+        (25, "position_tracking_rope_segments_missed_total 3"),
+        (26, "position_tracking_frames_total{camera=\"1\"} 1"),
+        (27, "position_tracking_match_success_ratio{camera=\"1\"} 0"),
+        (28, "position_tracking_keypoints{camera=\"1\"} 0"),
+        (29, "position_tracking_modbus_overflows_total 1"),
+        (30, "position_tracking_rope_length_meters{hoist=\"App/RopeDefectService/HoistService\"} 3.25"),
+        (31, "position_tracking_hoist_read_errors_total{hoist=\"App/HoistService\"} 0"),
+    ];
+    for (step, target) in test_data {
+        let lines: Vec<&str> = result.lines().collect();
+        assert!(lines.contains(&target), "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+    }
+    metrics.rope_length("App/HoistService", None);
+    let result = metrics.render();
+    let target = "position_tracking_rope_length_meters{hoist=\"App/HoistService\"}";
+    assert!(!result.lines().any(|line| line.starts_with(target)), "\nresult: {:?}\ntarget: {:?}", result, format!("no '{target}'"));
+    assert!(metrics.camera_down(), "\nresult: {:?}\ntarget: {:?}", metrics.camera_down(), true);
+    camera1.set(CameraState::Streaming);
+    assert!(!metrics.camera_down(), "\nresult: {:?}\ntarget: {:?}", metrics.camera_down(), false);
+    test_duration.exit();
+}
+///
+/// Testing [Metrics] keeps the recent rope defects published by the `RopeDefectService`
+#[test]
+fn rope_defects() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("Metrics-test-rope_defects");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let metrics = Metrics::new();
+    let shared = metrics.clone();
+    let defect = |kind, position| RopeDefect { kind, position, length: 0.01, deviation: 0.002 };
+    // step, defect published, target recent defects
+    let test_data = [
+        (01, None, vec![]),
+        (02, Some(defect(RopeDefectKind::Bump, 1.5)), vec![defect(RopeDefectKind::Bump, 1.5)]),
+        (03, Some(defect(RopeDefectKind::Narrowing, 2.5)), vec![defect(RopeDefectKind::Bump, 1.5), defect(RopeDefectKind::Narrowing, 2.5)]),
+    ];
+    for (step, published, target) in test_data {
+        if let Some(published) = published {
+            shared.rope_defect(published);
+        }
+        let result = metrics.rope_defects();
+        assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+    }
+    let target = "position_tracking_rope_defects_total{kind=\"narrowing\"} 1";
+    let result = metrics.render();
+    assert!(result.lines().any(|line| line == target), "\nresult: {:?}\ntarget: {:?}", result, target);
+    // The oldest defects are dropped
+    for index in 0..20 {
+        shared.rope_defect(defect(RopeDefectKind::Groove, 10.0 + index as f64));
+    }
+    let result = metrics.rope_defects();
+    let target = (16, Some(defect(RopeDefectKind::Groove, 14.0)), Some(defect(RopeDefectKind::Groove, 29.0)));
+    let result = (result.len(), result.first().copied(), result.last().copied());
+    assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+    test_duration.exit();
+}
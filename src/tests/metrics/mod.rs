@@ -0,0 +1 @@
+mod metrics_test;
\ No newline at end of file
@@ -1 +1,12 @@
-mod filter;
\ No newline at end of file
+mod calibration;
+mod camera_service;
+mod diagnostics;
+mod filter;
+mod fusion;
+mod hoist_service;
+mod metrics;
+mod rope_defect_service;
+mod service_registry;
+mod supervisor;
+mod sway;
+mod world;
\ No newline at end of file
@@ -0,0 +1,3 @@
+mod rope_defect_analysis_test;
+mod rope_profile_test;
+mod segment_tracker_test;
@@ -0,0 +1,153 @@
+#[cfg(test)]
+use std::time::Duration;
+
+use std::{
+    ops::Range,
+    sync::Once,
+};
+use sal_core::dbg::Dbg;
+use sal_sync::services::entity::Name;
+use testing::stuff::max_test_duration::TestDuration;
+use debugging::session::debug_session::{
+    DebugSession,
+    LogLevel,
+    Backtrace
+};
+
+use crate::modules::{RopeConf, RopeDefect, RopeDefectAnalysis, RopeDefectKind, RopeProfile};
+///
+///
+static INIT: Once = Once::new();
+///
+/// once called initialisation
+fn init_once() {
+    INIT.call_once(|| {
+        // implement your initialisation code to be called only once for current test file
+    })
+}
+///
+/// returns:
+///  - ...
+fn init_each() -> () {}
+/// Columns of the frame
+const COLUMNS: usize = 200;
+/// Nominal top & bottom edges of the rope, px, 70 px - 35 mm, the pixel is 0.5 mm
+const EDGES: (f64, f64) = (40.0, 110.0);
+///
+/// Returns the rope 35 mm, widening / narrowing tolerance 2 mm, bump / groove tolerance 1 mm
+fn conf(min_length: f64, pixel_size: Option<f64>) -> RopeConf {
+    RopeConf {
+        name: Name::new("RopeDefectAnalysis-test", "RopeConf"),
+        width: 0.035,
+        segment: 0.1,
+        segment_threshold: 0.005,
+        camera_offset: 0.0,
+        max_speed: 0.4,
+        width_tolerance: 0.002,
+        edge_tolerance: 0.001,
+        min_length,
+        pixel_size,
+    }
+}
+///
+/// Returns the nominal profile with the `edges` of the `columns` replaced
+fn profile(changes: &[(Range<usize>, f64, f64)]) -> RopeProfile {
+    let mut edges = vec![Some(EDGES); COLUMNS];
+    for (columns, top, bottom) in changes {
+        for column in columns.clone() {
+            edges[column] = Some((*top, *bottom));
+        }
+    }
+    RopeProfile::new(edges)
+}
+///
+/// Returns the defect
+fn defect(kind: RopeDefectKind, position: f64, length: f64, deviation: f64) -> RopeDefect {
+    RopeDefect { kind, position, length, deviation }
+}
+///
+/// Returns true if the defects are equal within the rounding
+fn equal(result: &[RopeDefect], target: &[RopeDefect]) -> bool {
+    result.len() == target.len() && result.iter().zip(target).all(|(r, t)| {
+        r.kind == t.kind && (r.position - t.position).abs() < 1e-9 && (r.length - t.length).abs() < 1e-9 && (r.deviation - t.deviation).abs() < 1e-9
+    })
+}
+///
+/// Testing [RopeDefectAnalysis] classifies the deviations of the profile
+#[test]
+fn defects() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("RopeDefectAnalysis-test-defects");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    // Rope position of the center of the frame, m; the center column is 99.5
+    let position = 120.0;
+    // step, min length, changed columns, target defects
+    let test_data = [
+        (01, 0.0, vec![], vec![]),
+        // Top edge 6 px outward on 10 columns, center 54.5
+        (02, 0.0, vec![(50..60, 34.0, 110.0)], vec![defect(RopeDefectKind::Bump, position - 0.0225, 0.005, 0.003)]),
+        // Bottom edge 5 px inward on 10 columns, center 124.5
+        (03, 0.0, vec![(120..130, 40.0, 105.0)], vec![defect(RopeDefectKind::Groove, position + 0.0125, 0.005, 0.0025)]),
+        // Both edges 4 px outward on 20 columns, center 109.5
+        (04, 0.0, vec![(100..120, 36.0, 114.0)], vec![defect(RopeDefectKind::Widening, position + 0.005, 0.01, 0.004)]),
+        // Both edges 3 px inward on 20 columns, center 19.5
+        (05, 0.0, vec![(10..30, 43.0, 107.0)], vec![defect(RopeDefectKind::Narrowing, position - 0.04, 0.01, 0.003)]),
+        // Within the tolerances
+        (06, 0.0, vec![(10..30, 41.0, 109.0), (50..60, 39.0, 110.0)], vec![]),
+        // Shorter than the min length
+        (07, 0.002, vec![(50..53, 34.0, 110.0), (120..130, 40.0, 105.0)], vec![defect(RopeDefectKind::Groove, position + 0.0125, 0.005, 0.0025)]),
+        // The rope shifted locally is not the defect
+        (08, 0.0, vec![(150..160, 44.0, 114.0)], vec![]),
+        // Bump on the widening, the deepest deviation is reported
+        (09, 0.0, vec![(100..110, 36.0, 114.0), (110..115, 30.0, 110.0), (115..116, 32.0, 110.0)], vec![
+            defect(RopeDefectKind::Widening, position + 0.0025, 0.005, 0.004),
+            defect(RopeDefectKind::Bump, position + 0.0065, 0.003, 0.005),
+        ]),
+        // The rope is not found in some columns
+        (10, 0.0, vec![], vec![]),
+    ];
+    for (step, min_length, changes, target) in test_data {
+        let analysis = RopeDefectAnalysis::new(conf(min_length, None));
+        let mut profile = profile(&changes);
+        if step == 10 {
+            let mut edges = profile.edges().to_vec();
+            edges[0..20].fill(None);
+            profile = RopeProfile::new(edges);
+        }
+        let result = analysis.defects(&profile, position);
+        log::debug!("step {} | result: {:?}", step, result);
+        assert!(equal(&result, &target), "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+    }
+    test_duration.exit();
+}
+///
+/// Testing [RopeDefectAnalysis] detects the widening of the whole frame only by the configured pixel size
+#[test]
+fn pixel_size() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("RopeDefectAnalysis-test-pixel_size");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let profile = profile(&[(0..COLUMNS, 35.0, 115.0)]);
+    // step, pixel size, target defects
+    let test_data = [
+        (01, None, vec![]),
+        (02, Some(0.0005), vec![defect(RopeDefectKind::Widening, 3.0, 0.1, 0.005)]),
+        (03, Some(0.0004), vec![defect(RopeDefectKind::Narrowing, 3.0, 0.08, 0.003)]),
+    ];
+    for (step, pixel_size, target) in test_data {
+        let analysis = RopeDefectAnalysis::new(conf(0.0, pixel_size));
+        let result = analysis.defects(&profile, 3.0);
+        log::debug!("step {} | result: {:?}", step, result);
+        assert!(equal(&result, &target), "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+    }
+    assert!(RopeDefectAnalysis::new(conf(0.0, None)).defects(&RopeProfile::default(), 3.0).is_empty(), "step {} \nresult: {:?}\ntarget: {:?}", 4, "defects", "empty");
+    test_duration.exit();
+}
@@ -0,0 +1,107 @@
+#[cfg(test)]
+use std::time::Duration;
+
+use std::{
+    sync::Once,
+};
+use sal_core::dbg::Dbg;
+use testing::stuff::max_test_duration::TestDuration;
+use debugging::session::debug_session::{
+    DebugSession,
+    LogLevel,
+    Backtrace
+};
+
+use crate::modules::RopeProfile;
+///
+///
+static INIT: Once = Once::new();
+///
+/// once called initialisation
+fn init_once() {
+    INIT.call_once(|| {
+        // implement your initialisation code to be called only once for current test file
+    })
+}
+///
+/// returns:
+///  - ...
+fn init_each() -> () {}
+///
+/// Returns the binary mask of the `rows`, '#' - rope, '.' - background
+fn binary(rows: &[&str]) -> (Vec<u8>, usize, usize) {
+    let width = rows[0].len();
+    let mask = rows.iter()
+        .flat_map(|row| row.chars().map(|pixel| if pixel == '#' { 255 } else { 0 }))
+        .collect();
+    (mask, width, rows.len())
+}
+///
+/// Testing [RopeProfile::from_mask] finds the edges of the rope in each column
+#[test]
+fn from_mask() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("RopeProfile-test-from_mask");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let (mask, width, height) = binary(&[
+        "..#.....",
+        "........",
+        "####..##",
+        "#####.##",
+        "######.#",
+        "........",
+        ".......#",
+    ]);
+    let profile = RopeProfile::from_mask(&mask, width, height);
+    // step, column, target edges
+    let test_data = [
+        (01, 0, Some((2.0, 5.0))),
+        (02, 2, Some((2.0, 5.0))),      // the spot above the rope is ignored
+        (03, 4, Some((3.0, 5.0))),
+        (04, 5, Some((4.0, 5.0))),
+        (05, 6, Some((2.0, 4.0))),
+        (06, 7, Some((2.0, 5.0))),      // the spot below the rope is ignored
+    ];
+    for (step, column, target) in test_data {
+        let result = profile.edges()[column];
+        assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+    }
+    let (mask, width, height) = binary(&["....", "....", "##.."]);
+    let profile = RopeProfile::from_mask(&mask, width, height);
+    let result = profile.edges().to_vec();
+    let target = vec![Some((2.0, 3.0)), Some((2.0, 3.0)), None, None];
+    assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", 7, result, target);
+    let (mask, width, height) = binary(&["....", "...."]);
+    let profile = RopeProfile::from_mask(&mask, width, height);
+    assert!(profile.is_empty(), "step {} \nresult: {:?}\ntarget: {:?}", 8, profile, "empty");
+    assert!(profile.median().is_none(), "step {} \nresult: {:?}\ntarget: {:?}", 9, profile.median(), "None");
+    test_duration.exit();
+}
+///
+/// Testing [RopeProfile::median] is not affected by the local deviations
+#[test]
+fn median() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("RopeProfile-test-median");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    // step, edges, target median top, bottom & diameter
+    let test_data = [
+        (01, vec![Some((10.0, 20.0)); 5], Some((10.0, 20.0, 10.0))),
+        (02, vec![Some((10.0, 20.0)), Some((4.0, 20.0)), Some((10.0, 20.0)), None, Some((10.0, 26.0)), Some((10.0, 20.0))], Some((10.0, 20.0, 10.0))),
+        (03, vec![Some((10.0, 20.0)), Some((12.0, 24.0))], Some((11.0, 22.0, 11.0))),
+        (04, vec![None, None], None),
+    ];
+    for (step, edges, target) in test_data {
+        let result = RopeProfile::new(edges).median();
+        assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+    }
+    test_duration.exit();
+}
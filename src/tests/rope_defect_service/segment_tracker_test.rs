@@ -0,0 +1,174 @@
+#[cfg(test)]
+use std::time::Duration;
+
+use std::{
+    sync::Once,
+};
+use sal_core::dbg::Dbg;
+use testing::stuff::max_test_duration::TestDuration;
+use debugging::session::debug_session::{
+    DebugSession,
+    LogLevel,
+    Backtrace
+};
+
+use crate::modules::{RopeLength, SegmentTracker};
+///
+///
+static INIT: Once = Once::new();
+///
+/// once called initialisation
+fn init_once() {
+    INIT.call_once(|| {
+        // implement your initialisation code to be called only once for current test file
+    })
+}
+///
+/// returns:
+///  - ...
+fn init_each() -> () {}
+/// Segment length, m
+const SEGMENT: f64 = 0.1;
+/// Allowed distance from the boundary, m
+const THRESHOLD: f64 = 0.005;
+///
+/// Returns the encoder positions `from` .. `to` with the `step`, m
+fn encoder(from: f64, to: f64, step: f64) -> Vec<f64> {
+    let count = ((to - from) / step).abs().round() as usize;
+    let step = step.copysign(to - from);
+    (0..=count).map(|i| from + i as f64 * step).collect()
+}
+///
+/// Testing [SegmentTracker] triggers each boundary once per crossing in either direction
+#[test]
+fn crossing() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("SegmentTracker-test-crossing");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    // step, encoder positions, target boundaries triggered, target missed
+    let test_data = [
+        (01, encoder(0.05, 0.45, 0.003), vec![1, 2, 3, 4], 0),
+        (02, encoder(0.45, 0.05, 0.003), vec![4, 3, 2, 1], 0),
+        (03, [encoder(0.15, 0.25, 0.003), encoder(0.25, 0.15, 0.003)].concat(), vec![2, 2], 0),
+        // Encoder jitter around the boundary
+        (04, vec![0.094, 0.097, 0.103, 0.099, 0.106, 0.096, 0.104, 0.101, 0.108, 0.115, 0.125], vec![1], 0),
+        // Rope stopped at the boundary
+        (05, [encoder(0.15, 0.2, 0.002), vec![0.2; 50], encoder(0.2, 0.25, 0.002)].concat(), vec![2], 0),
+        // Boundaries passed between the updates
+        (06, vec![0.05, 0.26, 0.30, 0.36, 0.41], vec![3], 3),
+        (07, [encoder(0.05, 0.26, 0.003), vec![0.05]].concat(), vec![1, 2], 2),
+        // Near the boundary, not crossed
+        (08, [encoder(0.05, 0.097, 0.003), encoder(0.097, 0.05, 0.003), encoder(0.05, 0.15, 0.003)].concat(), vec![1], 0),
+    ];
+    for (step, positions, target, target_missed) in test_data {
+        let mut tracker = SegmentTracker::new(SEGMENT, THRESHOLD, 0.0);
+        let result: Vec<i64> = positions.iter()
+            .filter_map(|position| tracker.add(*position))
+            .map(|boundary| boundary.index)
+            .collect();
+        log::debug!("step {} | result: {:?}, missed: {}", step, result, tracker.missed());
+        assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        let result = tracker.missed();
+        assert!(result == target_missed, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target_missed);
+    }
+    test_duration.exit();
+}
+///
+/// Testing [SegmentTracker] rope point under the camera shifted by the camera offset
+#[test]
+fn camera_offset() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("SegmentTracker-test-camera_offset");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    // step, camera offset, encoder positions, target boundary index & rope point under the camera
+    let test_data = [
+        (01, 0.0, encoder(0.05, 0.11, 0.003), (1, 0.098)),
+        (02, 0.03, encoder(0.05, 0.11, 0.003), (1, 0.098)),
+        (03, -0.2, encoder(0.05, 0.11, 0.003), (-1, -0.102)),
+    ];
+    for (step, offset, positions, target) in test_data {
+        let mut tracker = SegmentTracker::new(SEGMENT, THRESHOLD, offset);
+        let result = positions.iter().find_map(|position| tracker.add(*position)).unwrap();
+        log::debug!("step {} | result: {:?}", step, result);
+        assert!(result.index == target.0 && (result.position - target.1).abs() < 1e-9, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+    }
+    test_duration.exit();
+}
+///
+/// Testing [SegmentTracker::reset] forgets the rope position, the boundaries passed meanwhile are not missed
+#[test]
+fn reset() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("SegmentTracker-test-reset");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let mut tracker = SegmentTracker::new(SEGMENT, THRESHOLD, 0.0);
+    tracker.add(0.05);
+    tracker.reset();
+    let result = tracker.add(0.46);
+    assert!(result.is_none(), "step {} \nresult: {:?}\ntarget: {:?}", 1, result, "None");
+    let result = tracker.missed();
+    assert!(result == 0, "step {} \nresult: {:?}\ntarget: {:?}", 2, result, 0);
+    let result = tracker.add(0.501).map(|boundary| boundary.index);
+    assert!(result == Some(5), "step {} \nresult: {:?}\ntarget: {:?}", 3, result, Some(5));
+    test_duration.exit();
+}
+///
+/// Testing [SegmentTracker] by the rope position estimated at the frames between the sparse encoder readings
+#[test]
+fn sparse() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("SegmentTracker-test-sparse");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    // 1 m segments taken within 5 cm, the encoder read each 100 ms & received 5 ms later, the camera at 50 fps, the hoist at 0.8 m/s
+    let (segment, threshold, period, delay, frame) = (1.0, 0.05, 0.1, 0.005, 0.02);
+    // Allowed error of the rope position estimated at the frame, m
+    let accuracy = 0.005;
+    // step, rope position by the time, target boundaries triggered
+    let test_data: [(i32, Box<dyn Fn(f64) -> f64>, Vec<i64>); 3] = [
+        (01, Box::new(|time| 0.3 + 0.8 * time), vec![1, 2, 3, 4]),
+        (02, Box::new(|time| 4.7 - 0.8 * time), vec![4, 3, 2, 1]),
+        // Accelerating from the stop to 0.8 m/s for 1 s
+        (03, Box::new(|time| 0.3 + 0.4 * time.min(1.0).powi(2) + 0.8 * (time - 1.0).max(0.0)), vec![1, 2, 3]),
+    ];
+    for (step, rope_at, target) in test_data {
+        let rope = RopeLength::new();
+        let mut tracker = SegmentTracker::new(segment, threshold, 0.0);
+        let mut result = vec![];
+        let mut reads = 0;
+        for index in 0..=250 {
+            let time = 0.013 + index as f64 * frame;
+            while reads as f64 * period + delay <= time {
+                let read = reads as f64 * period;
+                rope.set_at(read, Some(rope_at(read)));
+                reads += 1;
+            }
+            if let Some(boundary) = rope.at(time).and_then(|position| tracker.add(position)) {
+                // The rope point actually under the camera at the frame
+                let error = (boundary.position - rope_at(time)).abs();
+                assert!(error < accuracy, "step {} \nresult: {:?}\ntarget: {:?}", step, error, format!("< {accuracy}"));
+                result.push(boundary.index);
+            }
+        }
+        log::debug!("step {} | result: {:?}, missed: {}", step, result, tracker.missed());
+        assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        let result = tracker.missed();
+        assert!(result == 0, "step {} \nresult: {:?}\ntarget: {:?}", step, result, 0);
+    }
+    test_duration.exit();
+}
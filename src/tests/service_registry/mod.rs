@@ -0,0 +1 @@
+mod service_registry_test;
\ No newline at end of file
@@ -0,0 +1,172 @@
+#[cfg(test)]
+use std::time::Duration;
+
+use std::{
+    sync::{atomic::{AtomicBool, Ordering}, Arc, Once}, time::Instant, 
+};
+use sal_core::{dbg::Dbg, error::Error};
+use sal_sync::{services::{entity::{Name, Object}, Service}, sync::Handles};
+use testing::stuff::max_test_duration::TestDuration;
+use debugging::session::debug_session::{
+    DebugSession, 
+    LogLevel, 
+    Backtrace
+};
+
+use crate::modules::{CameraId, CameraPosition, Metrics, Position, RopeDefect, RopeDefectKind, ServiceRegistry};
+///
+///
+static INIT: Once = Once::new();
+///
+/// once called initialisation
+fn init_once() {
+    INIT.call_once(|| {
+        // implement your initialisation code to be called only once for current test file
+    })
+}
+///
+/// returns:
+///  - ...
+fn init_each() -> () {}
+///
+/// Service working until exit, finishing the `delay` after exit
+struct MockService {
+    name: Name,
+    delay: std::time::Duration,
+    exit: Arc<AtomicBool>,
+    handles: Arc<Handles<()>>,
+}
+impl MockService {
+    fn new(name: &str, delay: std::time::Duration) -> Self {
+        let name = Name::new("ServiceRegistry-test", name);
+        let dbg = Dbg::new(name.parent(), name.me());
+        Self { name, delay, exit: Arc::new(AtomicBool::new(false)), handles: Arc::new(Handles::new(&dbg)) }
+    }
+}
+impl Object for MockService {
+    fn name(&self) -> Name {
+        self.name.clone()
+    }
+}
+impl std::fmt::Debug for MockService {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter.debug_struct("MockService").field("name", &self.name).finish()
+    }
+}
+impl Service for MockService {
+    fn run(&self) -> Result<(), Error> {
+        self.exit.store(false, Ordering::Release);
+        let exit = self.exit.clone();
+        let delay = self.delay;
+        self.handles.push(std::thread::spawn(move || {
+            while !exit.load(Ordering::Acquire) {
+                std::thread::sleep(std::time::Duration::from_millis(10));
+            }
+            std::thread::sleep(delay);
+        }));
+        Ok(())
+    }
+    fn wait(&self) -> Result<(), Error> {
+        self.handles.wait()
+    }
+    fn is_finished(&self) -> bool {
+        self.handles.is_finished()
+    }
+    fn exit(&self) {
+        self.exit.store(true, Ordering::Release);
+    }
+}
+///
+/// Testing [ServiceRegistry] stops & restarts the services and reports their state
+#[test]
+fn lifecycle() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("ServiceRegistry-test-lifecycle");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(5));
+    test_duration.run().unwrap();
+    let metrics = Metrics::new();
+    let registry = ServiceRegistry::new(&dbg, metrics.clone());
+    registry.add(Box::new(MockService::new("Service1", Duration::ZERO)));
+    registry.add(Box::new(MockService::new("Service2", Duration::ZERO)));
+    metrics.position("Service2", CameraId(2), Position::new(56.0, 78.0));
+    metrics.position("Service2", CameraId(1), Position::new(10.0, 30.0));
+    metrics.position("Service2", CameraId(1), Position::new(12.0, 34.0));
+    registry.run_all().unwrap();
+    // step, action, target finished state of Service1 & Service2
+    let test_data: [(i32, Box<dyn Fn(&ServiceRegistry) -> Result<(), Error>>, (bool, bool)); 5] = [
+        (01, Box::new(|_| Ok(())), (false, false)),
+        (02, Box::new(|registry| registry.stop("Service1")), (true, false)),
+        (03, Box::new(|registry| registry.run("Service1")), (false, false)),
+        (04, Box::new(|registry| registry.restart("Service2")), (false, false)),
+        (05, Box::new(|registry| registry.stop("Service2")), (false, true)),
+    ];
+    for (step, action, target) in test_data {
+        action(&registry).unwrap();
+        let status = registry.status();
+        log::debug!("step {} \nstatus: {:?}", step, status);
+        let result = (status.services[0].finished, status.services[1].finished);
+        assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        for service in &status.services {
+            assert!(service.uptime.is_some() == !service.finished, "step {} \nuptime: {:?}\nfinished: {:?}", step, service.uptime, service.finished);
+        }
+    }
+    let defect = RopeDefect { kind: RopeDefectKind::Groove, position: 2.5, length: 0.01, deviation: 0.002 };
+    metrics.rope_defect(defect);
+    let status = registry.status();
+    // The last position of each camera
+    let target = vec![
+        CameraPosition { camera: 1, position: Position::new(12.0, 34.0) },
+        CameraPosition { camera: 2, position: Position::new(56.0, 78.0) },
+    ];
+    assert!(status.services[1].positions == target, "\nresult: {:?}\ntarget: {:?}", status.services[1].positions, target);
+    assert!(status.services[0].positions.is_empty(), "\nresult: {:?}\ntarget: {:?}", status.services[0].positions, "empty");
+    assert!(status.defects == vec![defect], "\nresult: {:?}\ntarget: {:?}", status.defects, vec![defect]);
+    assert!(registry.stop("Unknown").is_err(), "\nresult: Ok\ntarget: Err");
+    registry.stop("Service1").unwrap();
+    assert!(registry.is_finished(), "\nresult: false\ntarget: true");
+    test_duration.exit();
+}
+///
+/// Testing [ServiceRegistry] isn't locked while the stopped service is finishing
+#[test]
+fn hung() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("ServiceRegistry-test-hung");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(5));
+    test_duration.run().unwrap();
+    let registry = ServiceRegistry::new(&dbg, Metrics::new()).with_stop_timeout(Duration::from_millis(300));
+    registry.add(Box::new(MockService::new("Hung", Duration::from_millis(1000))));
+    registry.add(Box::new(MockService::new("Service", Duration::ZERO)));
+    registry.run_all().unwrap();
+    let stop = {
+        let registry = registry.clone();
+        std::thread::spawn(move || registry.stop("Hung"))
+    };
+    std::thread::sleep(Duration::from_millis(100));
+    let time = Instant::now();
+    let status = registry.status();
+    let result = time.elapsed();
+    assert!(result < Duration::from_millis(100), "step {} \nresult: {:?}\ntarget: {:?}", 1, result, "< 100 ms");
+    let result = (status.services[0].finished, status.services[1].finished);
+    assert!(result == (false, false), "step {} \nresult: {:?}\ntarget: {:?}", 2, result, (false, false));
+    registry.stop("Service").unwrap();
+    let result = stop.join().unwrap();
+    assert!(result.is_err(), "step {} \nresult: {:?}\ntarget: {:?}", 3, result, "Err");
+    // Stopped over the registry, not finished by itself
+    let result = registry.finished().len();
+    assert!(result == 0, "step {} \nresult: {:?}\ntarget: {:?}", 4, result, 0);
+    let result = registry.restart("Hung");
+    assert!(result.is_err(), "step {} \nresult: {:?}\ntarget: {:?}", 5, result, "Err");
+    std::thread::sleep(Duration::from_millis(1000));
+    let result = registry.status().services[0].finished;
+    assert!(result, "step {} \nresult: {:?}\ntarget: {:?}", 6, result, true);
+    let result = registry.finished().len();
+    assert!(result == 0, "step {} \nresult: {:?}\ntarget: {:?}", 7, result, 0);
+    test_duration.exit();
+}
@@ -0,0 +1 @@
+mod supervisor_test;
\ No newline at end of file
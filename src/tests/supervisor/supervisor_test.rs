@@ -0,0 +1,191 @@
+#[cfg(test)]
+use std::time::Duration;
+
+use std::{
+    sync::{atomic::{AtomicBool, AtomicUsize, Ordering}, Arc, Once}, 
+};
+use sal_core::{dbg::Dbg, error::Error};
+use sal_sync::{services::{entity::{Name, Object}, Service}, sync::Handles};
+use testing::stuff::max_test_duration::TestDuration;
+use debugging::session::debug_session::{
+    DebugSession, 
+    LogLevel, 
+    Backtrace
+};
+
+use crate::modules::{self, Metrics, RestartPolicy, ServiceRegistry, Shutdown, Supervisor, SupervisorConf};
+///
+///
+static INIT: Once = Once::new();
+///
+/// once called initialisation
+fn init_once() {
+    INIT.call_once(|| {
+        // implement your initialisation code to be called only once for current test file
+    })
+}
+///
+/// returns:
+///  - ...
+fn init_each() -> () {}
+///
+/// Service finishing after `work` time, with error if `fail`
+struct MockService {
+    name: Name,
+    fail: bool,
+    work: std::time::Duration,
+    /// Service stops on exit
+    obeys: bool,
+    runs: Arc<AtomicUsize>,
+    exit: Arc<AtomicBool>,
+    handles: Arc<Handles<()>>,
+}
+impl MockService {
+    fn new(name: &str, fail: bool, work: std::time::Duration, obeys: bool, runs: Arc<AtomicUsize>) -> Self {
+        let name = Name::new("Supervisor-test", name);
+        let dbg = Dbg::new(name.parent(), name.me());
+        Self { name, fail, work, obeys, runs, exit: Arc::new(AtomicBool::new(false)), handles: Arc::new(Handles::new(&dbg)) }
+    }
+}
+impl Object for MockService {
+    fn name(&self) -> Name {
+        self.name.clone()
+    }
+}
+impl std::fmt::Debug for MockService {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter.debug_struct("MockService").field("name", &self.name).finish()
+    }
+}
+impl Service for MockService {
+    fn run(&self) -> Result<(), Error> {
+        self.runs.fetch_add(1, Ordering::SeqCst);
+        let (work, obeys, exit) = (self.work, self.obeys, self.exit.clone());
+        self.handles.push(std::thread::spawn(move || {
+            let started = std::time::Instant::now();
+            while started.elapsed() < work && !(obeys && exit.load(Ordering::Acquire)) {
+                std::thread::sleep(std::time::Duration::from_millis(5));
+            }
+        }));
+        Ok(())
+    }
+    fn wait(&self) -> Result<(), Error> {
+        self.handles.wait()?;
+        match self.fail {
+            true => Err(Error::new("MockService", "wait").err("Failed")),
+            false => Ok(()),
+        }
+    }
+    fn is_finished(&self) -> bool {
+        self.handles.is_finished()
+    }
+    fn exit(&self) {
+        self.exit.store(true, Ordering::Release);
+    }
+}
+///
+/// Returns [SupervisorConf] with the short delays
+fn conf(restart: RestartPolicy, max_failures: usize) -> SupervisorConf {
+    SupervisorConf {
+        restart,
+        backoff: std::time::Duration::from_millis(10),
+        max_backoff: std::time::Duration::from_millis(40),
+        max_failures,
+        reset_after: std::time::Duration::from_secs(60),
+        ..SupervisorConf::default()
+    }
+}
+///
+/// Testing [backoff] grows with the failures in a row up to the `max`
+#[test]
+fn backoff() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("Supervisor-test-backoff");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let (base, max) = (Duration::from_millis(10), Duration::from_millis(40));
+    let test_data = [
+        (01, 0, 10),
+        (02, 1, 10),
+        (03, 2, 20),
+        (04, 3, 40),
+        (05, 4, 40),
+        (06, 100, 40),
+    ];
+    for (step, failures, target) in test_data {
+        let result = modules::backoff(base, max, failures);
+        let target = Duration::from_millis(target);
+        assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+    }
+    test_duration.exit();
+}
+///
+/// Testing [Supervisor] restarts the failed service and gives up after `max-failures` in a row
+#[test]
+fn restart() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("Supervisor-test-restart");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(5));
+    test_duration.run().unwrap();
+    // step, restart policy, service fails, target result is Ok, target number of the runs
+    let test_data = [
+        (01, RestartPolicy::OnFailure, true, false, 3),
+        (02, RestartPolicy::Always, true, false, 3),
+        (03, RestartPolicy::Never, true, true, 1),
+        (04, RestartPolicy::OnFailure, false, true, 1),
+    ];
+    for (step, restart, fail, target_ok, target_runs) in test_data {
+        let runs = Arc::new(AtomicUsize::new(0));
+        let registry = ServiceRegistry::new(&dbg, Metrics::new());
+        registry.add(Box::new(MockService::new("Service", fail, Duration::from_millis(10), true, runs.clone())));
+        registry.run_all().unwrap();
+        let result = Supervisor::new(&dbg, conf(restart, 3), registry, Arc::new(AtomicBool::new(false))).run();
+        let runs = runs.load(Ordering::SeqCst);
+        assert!(result.is_ok() == target_ok, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target_ok);
+        assert!(runs == target_runs, "step {} \nresult: {:?}\ntarget: {:?}", step, runs, target_runs);
+    }
+    test_duration.exit();
+}
+///
+/// Testing [Supervisor] stops the services on shutdown request in the `shutdown-timeout`
+#[test]
+fn shutdown() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("Supervisor-test-shutdown");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+    test_duration.run().unwrap();
+    // step, service stops on exit, target
+    let test_data = [
+        (01, true, Shutdown::Stopped),
+        (02, false, Shutdown::Timeout),
+    ];
+    for (step, obeys, target) in test_data {
+        let registry = ServiceRegistry::new(&dbg, Metrics::new());
+        registry.add(Box::new(MockService::new("Service1", false, Duration::from_secs(60), true, Arc::new(AtomicUsize::new(0)))));
+        registry.add(Box::new(MockService::new("Service2", false, Duration::from_secs(1), obeys, Arc::new(AtomicUsize::new(0)))));
+        registry.run_all().unwrap();
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let conf = SupervisorConf { shutdown_timeout: Duration::from_millis(200), ..conf(RestartPolicy::OnFailure, 3) };
+        let supervisor = Supervisor::new(&dbg, conf, registry.clone(), shutdown.clone());
+        let shutdown_request = std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(50));
+            shutdown.store(true, Ordering::Release);
+        });
+        let result = supervisor.run().unwrap();
+        shutdown_request.join().unwrap();
+        assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        // Service ignored the exit finishes by itself
+        std::thread::sleep(std::time::Duration::from_secs(1));
+        assert!(registry.is_finished(), "step {} \nresult: false\ntarget: true", step);
+    }
+    test_duration.exit();
+}
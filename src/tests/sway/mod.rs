@@ -0,0 +1,2 @@
+mod oscillation_test;
+mod sway_analysis_test;
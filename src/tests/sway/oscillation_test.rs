@@ -0,0 +1,127 @@
+#[cfg(test)]
+use std::time::Duration;
+
+use std::{
+    f64::consts::PI,
+    sync::Once,
+};
+use sal_core::dbg::Dbg;
+use testing::stuff::max_test_duration::TestDuration;
+use debugging::session::debug_session::{
+    DebugSession,
+    LogLevel,
+    Backtrace
+};
+
+use crate::modules::Oscillation;
+///
+///
+static INIT: Once = Once::new();
+///
+/// once called initialisation
+fn init_once() {
+    INIT.call_once(|| {
+        // implement your initialisation code to be called only once for current test file
+    })
+}
+///
+/// returns:
+///  - ...
+fn init_each() -> () {}
+/// Sample time of the frames, s
+const DT: f64 = 0.04;
+///
+/// Returns deterministic noise in the range `-amplitude..amplitude` of the sample `i`
+fn noise(i: usize, amplitude: f64) -> f64 {
+    amplitude * ((i as f64 * 12.9898).sin() * 43758.5453).fract()
+}
+///
+/// Testing [Oscillation] estimates the period & angular velocity of the swing
+#[test]
+fn swing() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("Oscillation-test-swing");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    // step, amplitude, rad, period, s, noise, rad
+    let test_data = [
+        (01, 0.05, 2.0, 0.0),
+        (02, 0.05, 4.5, 0.0),
+        (03, 0.02, 7.0, 0.001),
+        (04, 0.1, 3.3, 0.0015),
+    ];
+    for (step, amplitude, period, noise_amplitude) in test_data {
+        let mut oscillation = Oscillation::new(0.002, 4, 20.0);
+        let omega = 2.0 * PI / period;
+        let samples = (4.0 * period / DT) as usize;
+        let mut result = None;
+        for i in 0..samples {
+            let time = i as f64 * DT;
+            result = Some(oscillation.add(time, amplitude * (omega * time).sin() + noise(i, noise_amplitude)));
+        }
+        let result = result.unwrap();
+        let time = (samples - 1) as f64 * DT;
+        log::debug!("step {} | result: {:?}", step, result);
+        let target = period;
+        let error = (result.period.unwrap() - target).abs() / target;
+        assert!(error < 0.02, "step {} \nresult: {:?}\ntarget: {:?}", step, result.period, target);
+        // Angular velocity of the last interval, without noise
+        if noise_amplitude == 0.0 {
+            let target = amplitude * omega * (omega * (time - DT / 2.0)).cos();
+            assert!((result.rate - target).abs() < amplitude * omega * 0.01, "step {} \nresult: {:?}\ntarget: {:?}", step, result.rate, target);
+        }
+    }
+    test_duration.exit();
+}
+///
+/// Testing [Oscillation] ignores the noise inside the deadband & forgets the period of the stopped swing
+#[test]
+fn deadband() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("Oscillation-test-deadband");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let mut oscillation = Oscillation::new(0.002, 4, 5.0);
+    let mut time = 0.0;
+    // Noise only
+    for i in 0..500 {
+        let result = oscillation.add(time, noise(i, 0.0019));
+        assert!(result.period.is_none(), "step {} \nresult: {:?}\ntarget: {:?}", 1, result, "period None");
+        time += DT;
+    }
+    // Swing 2 s
+    let mut result = None;
+    for i in 0..200 {
+        result = Some(oscillation.add(time, 0.03 * (PI * i as f64 * DT).sin()));
+        time += DT;
+    }
+    let result = result.unwrap().period.unwrap();
+    assert!((result - 2.0).abs() < 0.04, "step {} \nresult: {:?}\ntarget: {:?}", 2, result, 2.0);
+    // Load stopped off the center longer than `max-period`
+    let mut result = None;
+    for _ in 0..200 {
+        result = Some(oscillation.add(time, 0.01));
+        time += DT;
+    }
+    let result = result.unwrap().period;
+    assert!(result.is_none(), "step {} \nresult: {:?}\ntarget: {:?}", 3, result, "None");
+    // Swing 3 s again, the crossings of the previous swing are forgotten
+    let start = time;
+    let mut periods = vec![];
+    for _ in 0..300 {
+        let result = oscillation.add(time, 0.03 * (2.0 * PI / 3.0 * (time - start) + PI).sin());
+        if let Some(period) = result.period {
+            periods.push(period);
+        }
+        time += DT;
+    }
+    assert!(periods.iter().all(|period| (period - 3.0).abs() < 0.06), "step {} \nresult: {:?}\ntarget: {:?}", 4, periods, 3.0);
+    assert!(!periods.is_empty(), "step {} \nresult: {:?}\ntarget: {:?}", 4, periods, 3.0);
+    test_duration.exit();
+}
@@ -0,0 +1,136 @@
+#[cfg(test)]
+use std::time::Duration;
+
+use std::{
+    f64::consts::PI,
+    sync::Once,
+};
+use sal_core::dbg::Dbg;
+use testing::stuff::max_test_duration::TestDuration;
+use debugging::session::debug_session::{
+    DebugSession,
+    LogLevel,
+    Backtrace
+};
+
+use crate::modules::{Position, SwayAnalysis, SwayConf};
+///
+///
+static INIT: Once = Once::new();
+///
+/// once called initialisation
+fn init_once() {
+    INIT.call_once(|| {
+        // implement your initialisation code to be called only once for current test file
+    })
+}
+///
+/// returns:
+///  - ...
+fn init_each() -> () {}
+/// Gravity acceleration, m/s^2
+const G: f64 = 9.81;
+///
+/// Testing [SwayAnalysis] converts the offset of the load into the angle of the rope
+#[test]
+fn angle() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("SwayAnalysis-test-angle");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    // step, offset, m, length, m, target angles, rad, None - no sway
+    let test_data = [
+        (01, (0.0, 0.0), 10.0, Some((0.0, 0.0))),
+        (02, (1.0, -0.5), 10.0, Some((0.1f64.atan(), (-0.05f64).atan()))),
+        (03, (2.0, 2.0), 2.0, Some((PI / 4.0, PI / 4.0))),
+        (04, (1.0, 1.0), 0.0, None),
+        (05, (1.0, 1.0), -1.0, None),
+    ];
+    for (step, (x, y), length, target) in test_data {
+        let mut sway = SwayAnalysis::new(&SwayConf::default());
+        let result = sway.add(0.0, Position::new(x, y), length);
+        log::debug!("step {} | result: {:?}", step, result);
+        match target {
+            Some((tx, ty)) => {
+                let result = result.unwrap();
+                assert!((result.x.angle - tx).abs() < 1e-12 && (result.y.angle - ty).abs() < 1e-12, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            }
+            None => assert!(result.is_none(), "step {} \nresult: {:?}\ntarget: {:?}", step, result, target),
+        }
+    }
+    test_duration.exit();
+}
+///
+/// Testing [SwayConf::length] of the pendulum by the rope length read & configured
+#[test]
+fn length() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("SwayAnalysis-test-length");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    // step, configured rope length, rope offset, rope length read, target
+    let test_data = [
+        (01, None, 0.0, None, None),
+        (02, None, 0.5, Some(10.0), Some(10.5)),
+        (03, Some(12.0), 0.5, None, Some(12.5)),
+        (04, Some(12.0), -0.5, Some(10.0), Some(9.5)),
+    ];
+    for (step, rope_length, rope_offset, rope, target) in test_data {
+        let conf = SwayConf { rope_length, rope_offset, ..SwayConf::default() };
+        let result = conf.length(rope);
+        assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+    }
+    test_duration.exit();
+}
+///
+/// Testing [SwayAnalysis] of the simulated pendulum, the period is `2 * PI * sqrt(L / g)`
+#[test]
+fn pendulum() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("SwayAnalysis-test-pendulum");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let dt = 0.04;
+    // step, pendulum length, m, initial angle along X & Y, rad
+    let test_data = [
+        (01, 4.0, (0.05, 0.0)),
+        (02, 12.0, (0.03, -0.02)),
+        (03, 25.0, (-0.04, 0.06)),
+    ];
+    for (step, length, (x0, y0)) in test_data {
+        let mut sway = SwayAnalysis::new(&SwayConf::default());
+        let omega = (G / length).sqrt();
+        let target = 2.0 * PI / omega;
+        let mut result = None;
+        let mut time = 0.0;
+        while time < 4.0 * target {
+            // Small angles, the angle is harmonic
+            let (ax, ay) = (x0 * (omega * time).cos(), y0 * (omega * time).cos());
+            let offset = Position::new(length * ax.tan(), length * ay.tan());
+            result = sway.add(time, offset, length);
+            time += dt;
+        }
+        let result = result.unwrap();
+        log::debug!("step {} | result: {:?}, target period: {}", step, result, target);
+        for (axis, amplitude) in [(result.x, x0), (result.y, y0)] {
+            if amplitude == 0.0 {
+                assert!(axis.period.is_none(), "step {} \nresult: {:?}\ntarget: {:?}", step, axis.period, "None");
+            } else {
+                let error = (axis.period.unwrap() - target).abs() / target;
+                assert!(error < 0.02, "step {} \nresult: {:?}\ntarget: {:?}", step, axis.period, target);
+                assert!(axis.angle.abs() <= amplitude.abs() + 1e-9, "step {} \nresult: {:?}\ntarget: {:?}", step, axis.angle, amplitude);
+                assert!(axis.rate.abs() <= amplitude.abs() * omega * 1.01, "step {} \nresult: {:?}\ntarget: {:?}", step, axis.rate, amplitude * omega);
+            }
+        }
+    }
+    test_duration.exit();
+}
@@ -0,0 +1,111 @@
+#[cfg(test)]
+use std::time::Duration;
+
+use std::{
+    sync::Once,
+};
+use sal_core::dbg::Dbg;
+use testing::stuff::max_test_duration::TestDuration;
+use debugging::session::debug_session::{
+    DebugSession,
+    LogLevel,
+    Backtrace
+};
+
+use crate::modules::{Homography, Position};
+///
+///
+static INIT: Once = Once::new();
+///
+/// once called initialisation
+fn init_once() {
+    INIT.call_once(|| {
+        // implement your initialisation code to be called only once for current test file
+    })
+}
+///
+/// returns:
+///  - ...
+fn init_each() -> () {}
+///
+/// Perspective of the camera looking at the plane at the angle, pixel to world, m
+fn truth() -> Homography {
+    Homography::new([
+        [0.0021, 0.0003, -1.2],
+        [-0.0001, 0.0024, -0.9],
+        [0.00002, 0.00011, 1.0],
+    ])
+}
+///
+/// Testing [Homography::from_references] restores the perspective by the reference points
+#[test]
+fn from_references() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("Homography-test-from_references");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let truth = truth();
+    let checks = [
+        Position::new(600.0, 400.0),
+        Position::new(10.0, 790.0),
+        Position::new(1190.0, 20.0),
+        Position::new(333.0, 222.0),
+    ];
+    // step, reference pixels, noise of the world points, m, tolerance, m
+    let test_data = [
+        (01, vec![(100.0, 80.0), (1100.0, 90.0), (1090.0, 720.0), (95.0, 700.0)], 0.0, 1e-6),
+        (02, vec![(100.0, 80.0), (1100.0, 90.0), (1090.0, 720.0), (95.0, 700.0), (600.0, 400.0), (300.0, 600.0)], 0.0, 1e-6),
+        (03, vec![(0.0, 0.0), (1200.0, 0.0), (1200.0, 800.0), (0.0, 800.0), (600.0, 0.0), (600.0, 800.0), (0.0, 400.0), (1200.0, 400.0)], 0.001, 0.005),
+    ];
+    for (step, pixels, noise, tolerance) in test_data {
+        let references: Vec<_> = pixels.iter().enumerate().map(|(i, &(x, y))| {
+            let pixel = Position::new(x, y);
+            let world = truth.apply(pixel).unwrap();
+            // Deterministic noise of the measured world points
+            let sign = if i % 2 == 0 { 1.0 } else { -1.0 };
+            (pixel, Position::new(world.x + sign * noise, world.y - sign * noise))
+        }).collect();
+        let homography = Homography::from_references(&references).unwrap();
+        log::debug!("step {} | homography: {:?}", step, homography.matrix());
+        for pixel in checks {
+            let result = homography.apply(pixel).unwrap();
+            let target = truth.apply(pixel).unwrap();
+            let error = ((result.x - target.x).powi(2) + (result.y - target.y).powi(2)).sqrt();
+            log::debug!("step {} | pixel: {:?}, result: {:?}, target: {:?}, error: {}", step, pixel, result, target, error);
+            assert!(error < tolerance, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+    }
+    test_duration.exit();
+}
+///
+/// Testing [Homography::from_references] rejects the insufficient & degenerate reference points
+#[test]
+fn degenerate() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("Homography-test-degenerate");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    // step, references: pixel x, pixel y, world x, world y
+    let test_data = [
+        (01, vec![]),
+        (02, vec![(0.0, 0.0, 0.0, 0.0), (100.0, 0.0, 1.0, 0.0), (100.0, 100.0, 1.0, 1.0)]),
+        (03, vec![(0.0, 0.0, 0.0, 0.0), (100.0, 0.0, 1.0, 0.0), (200.0, 0.0, 2.0, 0.0), (300.0, 0.0, 3.0, 0.0)]),
+        (04, vec![(0.0, 0.0, 0.0, 0.0), (100.0, 0.0, 1.0, 0.0), (100.0, 100.0, 1.0, 1.0), (100.0, 100.0, 1.0, 1.0)]),
+        (05, vec![(0.0, 0.0, 0.0, 0.0), (100.0, 0.0, 0.0, 0.0), (100.0, 100.0, 0.0, 0.0), (0.0, 100.0, 0.0, 0.0)]),
+    ];
+    for (step, references) in test_data {
+        let references: Vec<_> = references.into_iter()
+            .map(|(px, py, wx, wy)| (Position::new(px, py), Position::new(wx, wy)))
+            .collect();
+        let result = Homography::from_references(&references);
+        log::debug!("step {} | result: {:?}", step, result);
+        assert!(result.is_none(), "step {} \nresult: {:?}\ntarget: {:?}", step, result, "None");
+    }
+    test_duration.exit();
+}
@@ -0,0 +1,3 @@
+mod homography_test;
+mod world_conf_test;
+mod world_transform_test;
\ No newline at end of file
@@ -0,0 +1,87 @@
+#[cfg(test)]
+use std::time::Duration;
+
+use std::{
+    sync::Once,
+};
+use sal_core::dbg::Dbg;
+use sal_sync::services::conf::ConfTree;
+use testing::stuff::max_test_duration::TestDuration;
+use debugging::session::debug_session::{
+    DebugSession,
+    LogLevel,
+    Backtrace
+};
+
+use crate::modules::{Intrinsics, Projection, WorldConf};
+///
+///
+static INIT: Once = Once::new();
+///
+/// once called initialisation
+fn init_once() {
+    INIT.call_once(|| {
+        // implement your initialisation code to be called only once for current test file
+    })
+}
+///
+/// returns:
+///  - ...
+fn init_each() -> () {}
+///
+/// Returns [WorldConf] parsed from the `world` section `yaml`
+fn world(dbg: &Dbg, yaml: &str) -> WorldConf {
+    let conf = serde_yaml::from_str(yaml).unwrap();
+    WorldConf::new(dbg, ConfTree::new_root(conf), Some(INTRINSICS))
+}
+const INTRINSICS: Intrinsics = Intrinsics { fx: 1000.0, fy: 1000.0, cx: 600.0, cy: 400.0 };
+///
+/// Testing [WorldConf] parses the fixed distance or the rope offset of the pinhole projection
+#[test]
+fn pinhole() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("WorldConf-test-pinhole");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    // step, world section, target distance & rope offset, m
+    let test_data = [
+        (01, "pinhole: {distance: 5000}", (Some(5.0), None)),
+        (02, "{units: m, pinhole: {distance: 5}}", (Some(5.0), None)),
+        (03, "pinhole: {rope-offset: -300}", (None, Some(-0.3))),
+    ];
+    for (step, yaml, (distance, rope_offset)) in test_data {
+        let result = world(&dbg, yaml).projection;
+        let target = Projection::Pinhole { intrinsics: INTRINSICS, distance, rope_offset };
+        log::debug!("step {step}   result: {:?}", result);
+        assert!(result == target, "step {step} \nresult: {:?}\ntarget: {:?}", result, target);
+    }
+    test_duration.exit();
+}
+///
+/// Testing [WorldConf] rejects the wrong projection
+#[test]
+fn wrong() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("WorldConf-test-wrong");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let test_data = [
+        // the fixed distance isn't used instead of the unknown rope length
+        (01, "pinhole: {distance: 5000, rope-offset: -300}"),
+        (02, "pinhole: {}"),
+        (03, "pinhole: {distance: 0}"),
+        (04, "{}"),
+        (05, "{pinhole: {distance: 5000}, homography: [[0, 0, 0, 0]]}"),
+    ];
+    for (step, yaml) in test_data {
+        let result = std::panic::catch_unwind(|| world(&dbg, yaml));
+        assert!(result.is_err(), "step {step} \nresult: {:?}\ntarget: {:?}", result, "panic");
+    }
+    test_duration.exit();
+}
@@ -0,0 +1,201 @@
+#[cfg(test)]
+use std::time::Duration;
+
+use std::{
+    sync::Once,
+};
+use sal_core::dbg::Dbg;
+use testing::stuff::max_test_duration::TestDuration;
+use debugging::session::debug_session::{
+    DebugSession,
+    LogLevel,
+    Backtrace
+};
+
+use crate::modules::{Axis, Homography, Intrinsics, Position, Projection, Units, WorldTransform};
+///
+///
+static INIT: Once = Once::new();
+///
+/// once called initialisation
+fn init_once() {
+    INIT.call_once(|| {
+        // implement your initialisation code to be called only once for current test file
+    })
+}
+///
+/// returns:
+///  - ...
+fn init_each() -> () {}
+///
+/// Testing [WorldTransform::world_at] of the pinhole projection with the origin & axes
+#[test]
+fn pinhole() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("WorldTransform-test-pinhole");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    // 1000 px focal length at 5 m - 5 mm per pixel
+    let projection = Projection::Pinhole {
+        intrinsics: Intrinsics { fx: 1000.0, fy: 1000.0, cx: 600.0, cy: 400.0 },
+        distance: Some(5.0),
+        rope_offset: None,
+    };
+    // step, origin, axes, pixel, target, m
+    let test_data = [
+        (01, (0.0, 0.0), [Axis::X, Axis::Y], (600.0, 400.0), (0.0, 0.0)),
+        (02, (0.0, 0.0), [Axis::X, Axis::Y], (700.0, 200.0), (0.5, -1.0)),
+        (03, (0.5, 0.0), [Axis::X, Axis::Y], (700.0, 200.0), (0.0, -1.0)),
+        (04, (0.0, 0.0), [Axis::X, Axis::NegY], (700.0, 200.0), (0.5, 1.0)),
+        (05, (0.0, 0.0), [Axis::Y, Axis::X], (700.0, 200.0), (-1.0, 0.5)),
+        (06, (0.2, -0.4), [Axis::NegY, Axis::NegX], (700.0, 200.0), (0.6, -0.3)),
+    ];
+    for (step, (ox, oy), axes, (px, py), (tx, ty)) in test_data {
+        let transform = WorldTransform::new(projection.clone(), Position::new(ox, oy), axes);
+        let result = transform.world_at(Position::new(px, py), None).unwrap();
+        let target = Position::new(tx, ty);
+        log::debug!("step {} | result: {:?}, target: {:?}", step, result, target);
+        assert!((result.x - target.x).abs() < 1e-9 && (result.y - target.y).abs() < 1e-9, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+    }
+    test_duration.exit();
+}
+///
+/// Testing [WorldTransform::world_at] of the pinhole projection scaled by the rope length
+#[test]
+fn rope() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("WorldTransform-test-rope");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    let intrinsics = Intrinsics { fx: 1000.0, fy: 1000.0, cx: 600.0, cy: 400.0 };
+    // step, rope offset, rope length, pixel, target: None - not projected, m
+    let test_data = [
+        (01, None, None, (700.0, 400.0), Some((0.5, 0.0))),
+        (02, None, Some(10.0), (700.0, 400.0), Some((0.5, 0.0))),
+        // rope length is unknown, the fixed distance isn't used instead
+        (03, Some(0.5), None, (700.0, 400.0), None),
+        (04, Some(0.5), Some(1.5), (700.0, 400.0), Some((0.2, 0.0))),
+        (05, Some(0.5), Some(9.5), (700.0, 400.0), Some((1.0, 0.0))),
+        (06, Some(-0.3), Some(10.3), (600.0, 300.0), Some((0.0, -1.0))),
+        (07, Some(-0.5), Some(0.5), (700.0, 400.0), None),
+    ];
+    for (step, rope_offset, rope, (px, py), target) in test_data {
+        let distance = match rope_offset {
+            Some(_) => None,
+            None => Some(5.0),
+        };
+        let projection = Projection::Pinhole { intrinsics, distance, rope_offset };
+        let transform = WorldTransform::new(projection, Position::new(0.0, 0.0), [Axis::X, Axis::Y]);
+        let result = transform.world_at(Position::new(px, py), rope);
+        log::debug!("step {} | result: {:?}, target: {:?}", step, result, target);
+        match target {
+            Some((tx, ty)) => {
+                let result = result.unwrap();
+                assert!((result.x - tx).abs() < 1e-9 && (result.y - ty).abs() < 1e-9, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            }
+            None => assert!(result.is_none(), "step {} \nresult: {:?}\ntarget: {:?}", step, result, target),
+        }
+    }
+    test_duration.exit();
+}
+///
+/// Testing [WorldTransform::world_at] of the homography fitted by the reference points in the `units`
+#[test]
+fn homography() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("WorldTransform-test-homography");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    // Reference points of the rectangle 2000 x 1500 mm, the camera is slightly rotated
+    let units = Units::Mm;
+    let references: Vec<_> = [
+        (102.0, 87.0, 0.0, 0.0),
+        (1090.0, 92.0, 2000.0, 0.0),
+        (1095.0, 710.0, 2000.0, 1500.0),
+        (98.0, 705.0, 0.0, 1500.0),
+    ].into_iter()
+        .map(|(px, py, wx, wy)| (Position::new(px, py), Position::new(units.to_meters(wx), units.to_meters(wy))))
+        .collect();
+    let homography = Homography::from_references(&references).unwrap();
+    let transform = WorldTransform::new(Projection::Homography(homography), Position::new(1.0, 0.0), [Axis::X, Axis::NegY]);
+    // step, pixel, target, mm
+    let test_data = [
+        (01, (102.0, 87.0), (-1000.0, 0.0)),
+        (02, (1090.0, 92.0), (1000.0, 0.0)),
+        (03, (1095.0, 710.0), (1000.0, -1500.0)),
+        (04, (98.0, 705.0), (-1000.0, -1500.0)),
+    ];
+    for (step, (px, py), (tx, ty)) in test_data {
+        let result = transform.world_at(Position::new(px, py), None).unwrap();
+        let result = Position::new(units.of_meters(result.x), units.of_meters(result.y));
+        let target = Position::new(tx, ty);
+        log::debug!("step {} | result: {:?}, target: {:?}", step, result, target);
+        assert!((result.x - target.x).abs() < 0.01 && (result.y - target.y).abs() < 0.01, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+    }
+    // The center of the rectangle is inside it
+    let result = transform.world_at(Position::new(596.0, 398.0), None).unwrap();
+    assert!(result.x.abs() < 0.05 && result.y < -0.7 && result.y > -0.8, "\nresult: {:?}\ntarget: {:?}", result, "about (0.0, -0.75)");
+    test_duration.exit();
+}
+///
+/// Testing [Units] & [Axis] parsed from the configuration values
+#[test]
+fn parse() {
+    DebugSession::init(LogLevel::Debug, Backtrace::Short);
+    init_once();
+    init_each();
+    let dbg = Dbg::own("WorldTransform-test-parse");
+    log::debug!("\n{}", dbg);
+    let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+    test_duration.run().unwrap();
+    // step, units, target: None - error
+    let test_data = [
+        (01, "m", Some((Units::M, 1.0))),
+        (02, "cm", Some((Units::Cm, 100.0))),
+        (03, " MM ", Some((Units::Mm, 1000.0))),
+        (04, "km", None),
+        (05, "", None),
+    ];
+    for (step, units, target) in test_data {
+        let result = units.parse::<Units>();
+        match target {
+            Some((target, per_meter)) => {
+                let result = result.unwrap();
+                assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+                assert!(result.per_meter() == per_meter, "step {} \nresult: {:?}\ntarget: {:?}", step, result.per_meter(), per_meter);
+                assert!((result.to_meters(result.of_meters(1.5)) - 1.5).abs() < 1e-12, "step {} \nresult: {:?}\ntarget: {:?}", step, result.to_meters(result.of_meters(1.5)), 1.5);
+            }
+            None => assert!(result.is_err(), "step {} \nresult: {:?}\ntarget: {:?}", step, result, "Err"),
+        }
+    }
+    // step, axis, target: None - error
+    let test_data = [
+        (01, "x", Some(Axis::X)),
+        (02, "-x", Some(Axis::NegX)),
+        (03, "Y", Some(Axis::Y)),
+        (04, " -y", Some(Axis::NegY)),
+        (05, "+x", Some(Axis::X)),
+        (06, "z", None),
+        (07, "--x", None),
+    ];
+    for (step, axis, target) in test_data {
+        let result = axis.parse::<Axis>();
+        match target {
+            Some(target) => {
+                let result = result.unwrap();
+                assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            }
+            None => assert!(result.is_err(), "step {} \nresult: {:?}\ntarget: {:?}", step, result, "Err"),
+        }
+    }
+    test_duration.exit();
+}